@@ -0,0 +1,328 @@
+//! Formal charge assignment from connectivity and bond orders.
+//!
+//! Input graphs often carry missing or wrong formal charges, such as a
+//! quaternary ammonium stored as neutral. The pass in this module checks each
+//! supplied charge against the atom's perceived valence and re-derives the
+//! charge from the octet rule where the supplied one admits no Lewis structure.
+
+use crate::convert::ConvertedMolecule;
+use crate::core::atom::{AtomId, Element};
+use crate::perception::{ChemicalPerception, PerceivedAtom};
+use std::ops::RangeInclusive;
+
+/// Charges considered when deriving a replacement for a rejected charge.
+const CHARGE_RANGE: RangeInclusive<i8> = -4..=4;
+
+/// An atom whose supplied formal charge admits no valid Lewis structure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormalChargeDiscrepancy {
+    /// The atom.
+    pub atom: AtomId,
+    /// Formal charge reported by the input graph.
+    pub supplied: i8,
+    /// Charge derived from the octet rule, or `None` when no charge fits the
+    /// drawn bond orders, as for the nitrogen of a nitro group drawn N(=O)=O,
+    /// or when charges of opposite sign fit equally well, as for a neutral
+    /// tricoordinate carbon that may be a carbocation or a carbanion.
+    pub derived: Option<i8>,
+}
+
+/// Result of a formal charge assignment pass.
+#[derive(Debug, Clone)]
+pub struct FormalChargeAssignment {
+    /// Copy of the input with every derivable charge corrected. Bond orders
+    /// and hydrogen counts are copied unchanged.
+    pub corrected: ConvertedMolecule,
+    /// Atoms whose supplied charge was rejected, ordered by atom identifier.
+    pub discrepancies: Vec<FormalChargeDiscrepancy>,
+}
+
+/// Checks every supplied charge and corrects those that do not fit.
+///
+/// Metals and elements without a main-group valence electron count keep
+/// their supplied charge, since the octet rule says nothing about them.
+pub(crate) fn assign(perception: &ChemicalPerception) -> FormalChargeAssignment {
+    let mut corrected = ConvertedMolecule::from_perception(perception, |bond| bond.order);
+    let mut discrepancies = Vec::new();
+
+    for atom in &perception.atoms {
+        if atom.element.is_metal() {
+            continue;
+        }
+        let Some(valence_electrons) = atom.element.valence_electrons() else {
+            continue;
+        };
+        if shell_electrons(atom, valence_electrons, atom.effective_charge()).is_some() {
+            continue;
+        }
+
+        let derived =
+            derive_charge(atom, valence_electrons).map(|charge| charge - atom.ligand_charge);
+
+        if let Some(charge) = derived {
+            let new_id = corrected.atom_id(atom.id).expect(
+                "from_perception copies every atom once from_topology has rejected self-loops",
+            );
+            corrected
+                .molecule
+                .set_formal_charge(new_id, charge)
+                .expect("mapped atom was inserted by from_perception");
+        }
+        discrepancies.push(FormalChargeDiscrepancy {
+            atom: atom.id,
+            supplied: atom.formal_charge,
+            derived,
+        });
+    }
+
+    discrepancies.sort_unstable_by_key(|discrepancy| discrepancy.atom);
+    FormalChargeAssignment {
+        corrected,
+        discrepancies,
+    }
+}
+
+/// Picks the smallest charge that gives `atom` a Lewis structure, or `None`
+/// when there is none or the choice is ambiguous.
+///
+/// Among charges of the same magnitude, a plain octet beats an open sextet
+/// on elements with more than four valence electrons (methoxide rather than
+/// an oxenium ion), and either beats an expanded octet (sulfonium rather than
+/// a ten-electron sulfur anion). Candidates that remain tied, such as the
+/// carbocation and carbanion of a neutral tricoordinate carbon, are left for
+/// the caller to resolve.
+fn derive_charge(atom: &PerceivedAtom, valence_electrons: u8) -> Option<i8> {
+    let rank = |shell: i16| {
+        if shell > 8 {
+            2
+        } else if shell < 8 && valence_electrons > 4 {
+            1
+        } else {
+            0
+        }
+    };
+    let candidates: Vec<(u8, usize, i8)> = CHARGE_RANGE
+        .filter_map(|charge| {
+            shell_electrons(atom, valence_electrons, charge)
+                .map(|shell| (charge.unsigned_abs(), rank(shell), charge))
+        })
+        .collect();
+    let best = candidates
+        .iter()
+        .map(|&(size, rank, _)| (size, rank))
+        .min()?;
+    let mut tied = candidates
+        .iter()
+        .filter(|&&(size, rank, _)| (size, rank) == best);
+
+    match (tied.next(), tied.next()) {
+        (Some(&(_, _, charge)), None) => Some(charge),
+        _ => None,
+    }
+}
+
+/// Counts the valence-shell electrons of `atom` at the given charge, or
+/// returns `None` if they do not form a Lewis structure.
+///
+/// The non-bonding electrons left after bonds and radicals must pair up, and
+/// the shell may fall at most one pair short of the octet (carbocations,
+/// boranes). Only hydrogen, helium and second-period atoms are capped at a
+/// full shell; heavier atoms may expand their octet.
+fn shell_electrons(atom: &PerceivedAtom, valence_electrons: u8, charge: i8) -> Option<i16> {
+    let bonding = i16::from(atom.total_valence);
+    let radicals = i16::from(atom.radical_electrons);
+    let non_bonding = i16::from(valence_electrons) - i16::from(charge) - bonding - radicals;
+    if non_bonding < 0 || non_bonding % 2 != 0 {
+        return None;
+    }
+
+    let shell = 2 * bonding + non_bonding + radicals;
+    let capacity = shell_capacity(atom.element);
+    let too_many = capacity.is_some_and(|capacity| shell > capacity);
+    let too_few = shell < capacity.unwrap_or(8) - 2;
+    (!too_many && !too_few).then_some(shell)
+}
+
+/// Returns the closed-shell electron count of elements that cannot expand
+/// their octet, or `None` for heavier elements.
+pub(crate) fn shell_capacity(element: Element) -> Option<i16> {
+    match element.period() {
+        1 => Some(2),
+        2 => Some(8),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::bond::BondOrder;
+    use crate::graph::traits::{AtomView, MoleculeGraph};
+    use crate::molecule::Molecule;
+
+    fn assign_molecule(molecule: &Molecule) -> FormalChargeAssignment {
+        let perception = ChemicalPerception::from_graph(molecule).expect("perception failed");
+        assign(&perception)
+    }
+
+    fn corrected_charge(assignment: &FormalChargeAssignment, atom: AtomId) -> i8 {
+        let new_id = assignment.corrected.atom_id(atom).expect("atom missing");
+        assignment
+            .corrected
+            .molecule
+            .atom(new_id)
+            .expect("atom missing")
+            .formal_charge()
+    }
+
+    fn add_methyl(molecule: &mut Molecule, atom: AtomId) -> AtomId {
+        let carbon = molecule.add_atom(Element::C, 0);
+        molecule
+            .set_implicit_hydrogens(carbon, 3)
+            .expect("failed to set hydrogens");
+        molecule
+            .add_bond(atom, carbon, BondOrder::Single)
+            .expect("failed to add methyl bond");
+        carbon
+    }
+
+    #[test]
+    fn neutral_quaternary_ammonium_becomes_cationic() {
+        let mut molecule = Molecule::new();
+        let nitrogen = molecule.add_atom(Element::N, 0);
+        for _ in 0..4 {
+            add_methyl(&mut molecule, nitrogen);
+        }
+
+        let assignment = assign_molecule(&molecule);
+        assert_eq!(
+            assignment.discrepancies,
+            vec![FormalChargeDiscrepancy {
+                atom: nitrogen,
+                supplied: 0,
+                derived: Some(1),
+            }]
+        );
+        assert_eq!(corrected_charge(&assignment, nitrogen), 1);
+    }
+
+    #[test]
+    fn missing_oxygen_charges_follow_the_octet_rule() {
+        // Trimethyloxonium and methoxide, both drawn neutral.
+        let mut molecule = Molecule::new();
+        let oxonium = molecule.add_atom(Element::O, 0);
+        for _ in 0..3 {
+            add_methyl(&mut molecule, oxonium);
+        }
+        let methoxide = molecule.add_atom(Element::O, 0);
+        add_methyl(&mut molecule, methoxide);
+
+        let assignment = assign_molecule(&molecule);
+        assert_eq!(assignment.discrepancies.len(), 2);
+        assert_eq!(corrected_charge(&assignment, oxonium), 1);
+        assert_eq!(corrected_charge(&assignment, methoxide), -1);
+    }
+
+    #[test]
+    fn pentavalent_nitro_nitrogen_has_no_valid_charge() {
+        let mut molecule = Molecule::new();
+        let nitrogen = molecule.add_atom(Element::N, 0);
+        add_methyl(&mut molecule, nitrogen);
+        for _ in 0..2 {
+            let oxygen = molecule.add_atom(Element::O, 0);
+            molecule
+                .add_bond(nitrogen, oxygen, BondOrder::Double)
+                .expect("failed to add N=O bond");
+        }
+
+        let assignment = assign_molecule(&molecule);
+        assert_eq!(
+            assignment.discrepancies,
+            vec![FormalChargeDiscrepancy {
+                atom: nitrogen,
+                supplied: 0,
+                derived: None,
+            }]
+        );
+        assert_eq!(corrected_charge(&assignment, nitrogen), 0);
+    }
+
+    #[test]
+    fn neutral_tricoordinate_carbon_is_ambiguous() {
+        // CH3 drawn neutral without a radical: the methyl cation and the
+        // methyl anion fit equally well, so neither is chosen.
+        let mut molecule = Molecule::new();
+        let carbon = molecule.add_atom(Element::C, 0);
+        molecule
+            .set_implicit_hydrogens(carbon, 3)
+            .expect("failed to set hydrogens");
+
+        let assignment = assign_molecule(&molecule);
+        assert_eq!(
+            assignment.discrepancies,
+            vec![FormalChargeDiscrepancy {
+                atom: carbon,
+                supplied: 0,
+                derived: None,
+            }]
+        );
+        assert_eq!(corrected_charge(&assignment, carbon), 0);
+    }
+
+    #[test]
+    fn neutral_trivalent_sulfur_becomes_sulfonium() {
+        // The anion would need ten valence electrons on sulfur.
+        let mut molecule = Molecule::new();
+        let sulfur = molecule.add_atom(Element::S, 0);
+        for _ in 0..3 {
+            add_methyl(&mut molecule, sulfur);
+        }
+
+        let assignment = assign_molecule(&molecule);
+        assert_eq!(
+            assignment.discrepancies,
+            vec![FormalChargeDiscrepancy {
+                atom: sulfur,
+                supplied: 0,
+                derived: Some(1),
+            }]
+        );
+        assert_eq!(corrected_charge(&assignment, sulfur), 1);
+    }
+
+    #[test]
+    fn valid_charges_are_kept() {
+        let mut molecule = Molecule::new();
+        // Carbocation with an open sextet.
+        let cation = molecule.add_atom(Element::C, 1);
+        add_methyl(&mut molecule, cation);
+        molecule
+            .set_implicit_hydrogens(cation, 2)
+            .expect("failed to set hydrogens");
+        // Methyl radical.
+        let radical = molecule.add_atom(Element::C, 0);
+        molecule
+            .set_implicit_hydrogens(radical, 3)
+            .expect("failed to set hydrogens");
+        molecule
+            .set_radical_electrons(radical, 1)
+            .expect("failed to set radical");
+        // Dimethyl sulfoxide with an expanded octet on sulfur.
+        let sulfur = molecule.add_atom(Element::S, 0);
+        add_methyl(&mut molecule, sulfur);
+        add_methyl(&mut molecule, sulfur);
+        let oxygen = molecule.add_atom(Element::O, 0);
+        molecule
+            .add_bond(sulfur, oxygen, BondOrder::Double)
+            .expect("failed to add S=O bond");
+
+        let assignment = assign_molecule(&molecule);
+        assert!(assignment.discrepancies.is_empty());
+        for atom in molecule.atoms() {
+            assert_eq!(
+                corrected_charge(&assignment, atom.id()),
+                atom.formal_charge()
+            );
+        }
+    }
+}
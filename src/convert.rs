@@ -0,0 +1,151 @@
+//! Conversion of perceived molecules into normalised [`Molecule`] copies.
+//!
+//! Downstream tools disagree on how aromatic systems should be written: some
+//! only accept localised single/double bonds, others expect explicit aromatic
+//! bond types. The converters in this module rebuild a [`Molecule`] from a
+//! completed perception snapshot and remember how identifiers were remapped.
+
+use crate::core::atom::AtomId;
+use crate::core::bond::{BondId, BondOrder};
+use crate::molecule::Molecule;
+use crate::perception::{ChemicalPerception, PerceivedBond};
+use std::collections::HashMap;
+
+/// A [`Molecule`] rebuilt from another graph, with identifier mappings.
+///
+/// Atoms and bonds are inserted in the iteration order of the source graph,
+/// so the new identifiers are dense indices. The mappings translate between
+/// them and the identifiers of the source graph.
+#[derive(Clone, Debug)]
+pub struct ConvertedMolecule {
+    /// The rebuilt molecule.
+    pub molecule: Molecule,
+    /// Source atom identifier for each new atom, indexed by the new [`AtomId`].
+    pub original_atom_ids: Vec<AtomId>,
+    /// Source bond identifier for each new bond, indexed by the new [`BondId`].
+    pub original_bond_ids: Vec<BondId>,
+    atom_ids: HashMap<AtomId, AtomId>,
+    bond_ids: HashMap<BondId, BondId>,
+}
+
+impl ConvertedMolecule {
+    /// Rebuilds a molecule from a perception snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `perception` - Completed perception snapshot of the source graph.
+    /// * `order_of` - Chooses the bond order written for each perceived bond.
+    pub(crate) fn from_perception(
+        perception: &ChemicalPerception,
+        order_of: impl Fn(&PerceivedBond) -> BondOrder,
+    ) -> Self {
+        let mut molecule = Molecule::new();
+        let mut original_atom_ids = Vec::with_capacity(perception.atoms.len());
+        let mut atom_ids = HashMap::with_capacity(perception.atoms.len());
+
+        for atom in &perception.atoms {
+            let new_id = molecule.add_atom(atom.element, atom.formal_charge);
+            molecule
+                .set_implicit_hydrogens(new_id, atom.implicit_hydrogens)
+                .expect("atom was just inserted");
+            molecule
+                .set_radical_electrons(new_id, atom.radical_electrons)
+                .expect("atom was just inserted");
+            original_atom_ids.push(atom.id);
+            atom_ids.insert(atom.id, new_id);
+        }
+
+        let mut original_bond_ids = Vec::with_capacity(perception.bonds.len());
+        let mut bond_ids = HashMap::with_capacity(perception.bonds.len());
+
+        for bond in &perception.bonds {
+            let new_id = molecule
+                .add_bond(
+                    atom_ids[&bond.start_atom_id],
+                    atom_ids[&bond.end_atom_id],
+                    order_of(bond),
+                )
+                .expect("from_topology rejects self-loops and duplicate bonds");
+            original_bond_ids.push(bond.id);
+            bond_ids.insert(bond.id, new_id);
+        }
+
+        Self {
+            molecule,
+            original_atom_ids,
+            original_bond_ids,
+            atom_ids,
+            bond_ids,
+        }
+    }
+
+    /// Returns the new identifier of a source atom, if it exists.
+    pub fn atom_id(&self, original: AtomId) -> Option<AtomId> {
+        self.atom_ids.get(&original).copied()
+    }
+
+    /// Returns the new identifier of a source bond, if it exists.
+    pub fn bond_id(&self, original: BondId) -> Option<BondId> {
+        self.bond_ids.get(&original).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::atom::Element;
+    use crate::graph::traits::{AtomView, BondView, MoleculeGraph};
+
+    fn aromatic_pyridine() -> Molecule {
+        let mut molecule = Molecule::new();
+        let nitrogen = molecule.add_atom(Element::N, 0);
+        let carbons: Vec<AtomId> = (0..5).map(|_| molecule.add_atom(Element::C, 0)).collect();
+        let ring: Vec<AtomId> = std::iter::once(nitrogen).chain(carbons).collect();
+        for i in 0..ring.len() {
+            molecule
+                .add_bond(ring[i], ring[(i + 1) % ring.len()], BondOrder::Aromatic)
+                .expect("failed to add ring bond");
+        }
+        for &carbon in &ring[1..] {
+            molecule
+                .set_implicit_hydrogens(carbon, 1)
+                .expect("failed to set implicit hydrogens");
+        }
+        molecule
+    }
+
+    #[test]
+    fn kekule_conversion_localises_aromatic_bonds_and_keeps_mappings() {
+        let source = aromatic_pyridine();
+        let perception = ChemicalPerception::from_graph(&source).expect("perception failed");
+        let converted = ConvertedMolecule::from_perception(&perception, |bond| {
+            bond.kekule_order.unwrap_or(bond.order)
+        });
+
+        let orders: Vec<BondOrder> = converted
+            .molecule
+            .bonds()
+            .map(|bond| bond.order())
+            .collect();
+        assert_eq!(
+            orders
+                .iter()
+                .filter(|&&order| order == BondOrder::Double)
+                .count(),
+            3
+        );
+        assert!(!orders.contains(&BondOrder::Aromatic));
+
+        for (new_id, &original) in converted.original_atom_ids.iter().enumerate() {
+            assert_eq!(converted.atom_id(original), Some(new_id));
+            let atom = converted.molecule.atom(new_id).expect("atom missing");
+            let source_atom = source.atom(original).expect("source atom missing");
+            assert_eq!(atom.element(), source_atom.element());
+            assert_eq!(atom.implicit_hydrogens(), source_atom.implicit_hydrogens());
+        }
+        for (new_id, &original) in converted.original_bond_ids.iter().enumerate() {
+            assert_eq!(converted.bond_id(original), Some(new_id));
+        }
+        assert_eq!(converted.atom_id(99), None);
+    }
+}
@@ -4,6 +4,8 @@
 //! `Element` enumeration, which captures the subset of the periodic table
 //! supported by the Pauling perception pipeline.
 
+use crate::core::periodic::{self, ELEMENTS, ElementData};
+
 /// Unique identifier for an atom inside a molecular graph.
 pub type AtomId = usize;
 
@@ -40,6 +42,13 @@ macro_rules! define_elements {
                 self as u8
             }
 
+            /// Returns the element symbol, e.g. `"Cl"`.
+            pub fn symbol(self) -> &'static str {
+                match self {
+                    $(Element::$name => stringify!($name),)*
+                }
+            }
+
             /// Returns the number of valence electrons used for octet heuristics.
             ///
             /// Only main-group elements with well-defined valence configurations are
@@ -62,6 +71,37 @@ macro_rules! define_elements {
                 Some(electrons)
             }
 
+            /// Returns the valences the element may take at the given formal charge.
+            ///
+            /// A charged atom is treated like the neutral element with the same
+            /// number of valence electrons, so N⁺ takes the valence of carbon and
+            /// O⁻ that of fluorine. Hydrogen and second-period atoms obey the
+            /// duet or octet rule and have a single valence; heavier atoms may
+            /// also expand their octet two electrons at a time, giving
+            /// `[2, 4, 6]` for neutral sulfur. Returns `None` for elements
+            /// without a [`valence_electrons`](Self::valence_electrons) count and
+            /// an empty list when the charge leaves no valid electron count.
+            pub fn allowed_valences(self, charge: i8) -> Option<Vec<u8>> {
+                let electrons = i16::from(self.valence_electrons()?) - i16::from(charge);
+                let shell: i16 = if self.period() == 1 { 2 } else { 8 };
+                if !(0..=shell).contains(&electrons) {
+                    return Some(Vec::new());
+                }
+                let lowest = electrons.min(shell - electrons);
+                let expands = self.period() > 2;
+                let highest = if expands { electrons } else { lowest };
+                Some((lowest..=highest).step_by(2).map(|valence| valence as u8).collect())
+            }
+
+            /// Returns the valences of the neutral element, e.g. `[3]` for
+            /// nitrogen and `[2, 4, 6]` for sulfur.
+            ///
+            /// Equivalent to [`allowed_valences`](Self::allowed_valences) at
+            /// zero charge.
+            pub fn default_valences(self) -> Option<Vec<u8>> {
+                self.allowed_valences(0)
+            }
+
             /// Checks whether the element frequently participates in conjugation.
             ///
             /// The perception pipeline treats these elements as candidates for
@@ -70,10 +110,23 @@ macro_rules! define_elements {
                 matches!(
                     self,
                     Element::B | Element::C | Element::N | Element::O | Element::P |
-                    Element::S | Element::Si | Element::As | Element::Se | Element::Ge |
+                    Element::S | Element::Si | Element::As | Element::Se | Element::Te | Element::Ge |
                     Element::F | Element::Cl | Element::Br | Element::I
                 )
             }
+
+            /// Checks whether the element is a metal.
+            ///
+            /// Metalloids (B, Si, Ge, As, Sb, Te) count as non-metals because
+            /// their bonds are covalent and handled like those of carbon.
+            pub fn is_metal(self) -> bool {
+                use Element::*;
+                !matches!(
+                    self,
+                    H | He | B | C | N | O | F | Ne | Si | P | S | Cl | Ar | Ge | As | Se |
+                    Br | Kr | Sb | Te | I | Xe | At | Rn | Ts | Og
+                )
+            }
         }
 
         impl std::str::FromStr for Element {
@@ -103,6 +156,75 @@ macro_rules! define_elements {
     };
 }
 
+impl Element {
+    fn data(self) -> &'static ElementData {
+        &ELEMENTS[usize::from(self.atomic_number()) - 1]
+    }
+
+    /// Returns the English name of the element, e.g. `"Sulfur"`.
+    pub fn name(self) -> &'static str {
+        self.data().name
+    }
+
+    /// Returns the standard atomic weight in daltons.
+    ///
+    /// Elements without a stable isotope report the mass number of their
+    /// longest-lived isotope.
+    pub fn average_mass(self) -> f64 {
+        self.data().average_mass
+    }
+
+    /// Returns the exact mass of the most abundant isotope in daltons.
+    ///
+    /// Elements without a stable isotope report their longest-lived isotope.
+    pub fn monoisotopic_mass(self) -> f64 {
+        self.data().monoisotopic_mass
+    }
+
+    /// Returns the Pauling electronegativity of the element.
+    ///
+    /// Returns `None` for He, Ne, Ar and the elements from lawrencium
+    /// onwards, which have no tabulated value.
+    pub fn electronegativity(self) -> Option<f64> {
+        self.data().electronegativity
+    }
+
+    /// Returns the single-bond covalent radius in ångströms.
+    ///
+    /// Returns `None` from berkelium onwards.
+    pub fn covalent_radius(self) -> Option<f64> {
+        self.data().covalent_radius
+    }
+
+    /// Returns the van der Waals radius in ångströms.
+    ///
+    /// Returns `None` for most transition metals, lanthanides and actinides,
+    /// which have no reliable value.
+    pub fn van_der_waals_radius(self) -> Option<f64> {
+        self.data().van_der_waals_radius
+    }
+
+    /// Returns the period (row of the periodic table), from 1 to 7.
+    pub fn period(self) -> u8 {
+        periodic::period(self.atomic_number())
+    }
+
+    /// Returns the IUPAC group, from 1 to 18.
+    ///
+    /// Returns `None` for the f-block elements La–Yb and Ac–No; Lu and Lr
+    /// are placed in group 3.
+    pub fn group(self) -> Option<u8> {
+        periodic::group(self.atomic_number())
+    }
+}
+
+impl std::fmt::Display for Element {
+    /// Formats the element as its symbol.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
 define_elements!(
     H = 1,
     He = 2,
@@ -223,3 +345,63 @@ define_elements!(
     Ts = 117,
     Og = 118,
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allowed_valences_follow_isoelectronic_neutral_elements() {
+        assert_eq!(Element::C.allowed_valences(0), Some(vec![4]));
+        assert_eq!(Element::N.allowed_valences(0), Some(vec![3]));
+        assert_eq!(Element::N.allowed_valences(1), Some(vec![4]));
+        assert_eq!(Element::O.allowed_valences(-1), Some(vec![1]));
+        assert_eq!(Element::C.allowed_valences(1), Some(vec![3]));
+        assert_eq!(Element::B.allowed_valences(-1), Some(vec![4]));
+        assert_eq!(Element::H.allowed_valences(0), Some(vec![1]));
+        assert_eq!(Element::H.allowed_valences(1), Some(vec![0]));
+        assert_eq!(Element::Fe.allowed_valences(0), None);
+    }
+
+    #[test]
+    fn heavier_elements_may_expand_their_octet() {
+        assert_eq!(Element::S.allowed_valences(0), Some(vec![2, 4, 6]));
+        assert_eq!(Element::P.allowed_valences(0), Some(vec![3, 5]));
+        assert_eq!(Element::S.allowed_valences(1), Some(vec![3, 5]));
+        assert_eq!(Element::Cl.allowed_valences(0), Some(vec![1, 3, 5, 7]));
+        assert_eq!(Element::Xe.allowed_valences(0), Some(vec![0, 2, 4, 6, 8]));
+        assert_eq!(Element::O.allowed_valences(-3), Some(vec![]));
+    }
+
+    #[test]
+    fn periodic_data_matches_reference_values() {
+        assert_eq!(Element::Cl.symbol(), "Cl");
+        assert_eq!(Element::Cl.to_string(), "Cl");
+        assert_eq!(Element::S.name(), "Sulfur");
+        assert_eq!(Element::C.average_mass(), 12.011);
+        assert_eq!(Element::C.monoisotopic_mass(), 12.0);
+        assert_eq!(Element::O.electronegativity(), Some(3.44));
+        assert_eq!(Element::N.covalent_radius(), Some(0.71));
+        assert_eq!(Element::C.van_der_waals_radius(), Some(1.70));
+        assert_eq!(Element::S.default_valences(), Some(vec![2, 4, 6]));
+        assert_eq!((Element::Fe.period(), Element::Fe.group()), (4, Some(8)));
+        assert_eq!((Element::Og.period(), Element::Og.group()), (7, Some(18)));
+        assert_eq!(Element::Ce.group(), None);
+    }
+
+    #[test]
+    fn periods_and_groups_cover_the_table() {
+        let mut per_period = [0; 7];
+        let mut cells = std::collections::HashSet::new();
+        for atomic_number in 1..=118 {
+            let element = Element::from_atomic_number(atomic_number).unwrap();
+            assert_eq!(element.symbol().parse::<Element>(), Ok(element));
+            per_period[usize::from(element.period()) - 1] += 1;
+            if let Some(group) = element.group() {
+                assert!(cells.insert((element.period(), group)), "{element}");
+            }
+        }
+        assert_eq!(per_period, [2, 8, 8, 18, 18, 32, 32]);
+        assert_eq!(cells.len(), 2 + 8 + 8 + 18 * 4);
+    }
+}
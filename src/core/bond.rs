@@ -17,19 +17,86 @@ pub enum BondOrder {
     Triple,
     /// Aromatic bond flagged by the input or detected by perception stages.
     Aromatic,
+    /// Quadruple bond containing four shared electron pairs, as between the
+    /// metals of a dimetal complex.
+    Quadruple,
+    /// Coordinate bond whose electron pair comes from the start atom (the
+    /// donor) and is accepted by the end atom.
+    Dative,
+    /// Connection with no bonding electrons, e.g. an unspecified contact.
+    Zero,
+    /// Electrostatic contact between the ions of a salt pair.
+    Ionic,
+    /// Hydrogen bond between a donor hydrogen and an acceptor atom.
+    Hydrogen,
 }
 
 impl BondOrder {
     /// Returns the valence contribution represented by this bond order.
     ///
     /// Aromatic bonds yield a multiplicity of 1 because the explicit electron
-    /// counting is deferred to the Kekulé resonance model.
+    /// counting is deferred to the Kekulé resonance model. A dative bond shares
+    /// one electron pair; zero-order, ionic and hydrogen bonds share none.
     pub fn multiplicity(self) -> u8 {
         match self {
             BondOrder::Single => 1,
             BondOrder::Double => 2,
             BondOrder::Triple => 3,
             BondOrder::Aromatic => 1, // Placeholder value; effective multiplicity is handled after kekulization.
+            BondOrder::Quadruple => 4,
+            BondOrder::Dative => 1,
+            BondOrder::Zero | BondOrder::Ionic | BondOrder::Hydrogen => 0,
         }
     }
+
+    /// Returns the valence this bond adds to one of its endpoints.
+    ///
+    /// A dative bond counts towards the valence of its acceptor (end) only,
+    /// because the donor's lone pair is already part of its valence shell. All
+    /// other orders add their [`multiplicity`](Self::multiplicity) to both
+    /// ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `at_start` - Whether the endpoint is the bond's start atom.
+    pub fn valence_contribution(self, at_start: bool) -> u8 {
+        match self {
+            BondOrder::Dative if at_start => 0,
+            order => order.multiplicity(),
+        }
+    }
+
+    /// Checks whether the bond holds atoms together covalently.
+    ///
+    /// Zero-order, ionic and hydrogen bonds are contacts rather than bonds:
+    /// the perception pipeline leaves them out of atom degrees, ring
+    /// perception and resonance expansion.
+    pub fn is_covalent(self) -> bool {
+        !matches!(
+            self,
+            BondOrder::Zero | BondOrder::Ionic | BondOrder::Hydrogen
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dative_bond_adds_valence_to_the_acceptor_only() {
+        assert_eq!(BondOrder::Dative.valence_contribution(true), 0);
+        assert_eq!(BondOrder::Dative.valence_contribution(false), 1);
+        assert_eq!(BondOrder::Double.valence_contribution(true), 2);
+        assert_eq!(BondOrder::Quadruple.valence_contribution(false), 4);
+    }
+
+    #[test]
+    fn contacts_are_not_covalent_and_carry_no_multiplicity() {
+        for order in [BondOrder::Zero, BondOrder::Ionic, BondOrder::Hydrogen] {
+            assert!(!order.is_covalent());
+            assert_eq!(order.multiplicity(), 0);
+        }
+        assert!(BondOrder::Dative.is_covalent());
+    }
 }
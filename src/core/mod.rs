@@ -7,3 +7,5 @@
 pub mod atom;
 /// Bond-centric primitives such as [`BondId`](crate::BondId) and [`BondOrder`](crate::BondOrder).
 pub mod bond;
+/// Periodic table data read through the accessors on [`Element`](crate::Element).
+pub(crate) mod periodic;
@@ -0,0 +1,208 @@
+//! Periodic table data for every supported element.
+//!
+//! The table is indexed by atomic number and read through the accessors on
+//! [`Element`](crate::Element). Sources:
+//!
+//! * Average masses: IUPAC 2013 standard atomic weights (conventional values
+//!   for elements with an interval); elements without a stable isotope take
+//!   the mass number of their longest-lived isotope.
+//! * Monoisotopic masses: AME2016 mass of the most abundant isotope, or of
+//!   the longest-lived isotope for elements without a stable one.
+//! * Electronegativities: Pauling scale.
+//! * Covalent radii: Cordero et al. (2008), single-bond sp³ values for carbon
+//!   and low-spin values for Mn, Fe and Co.
+//! * Van der Waals radii: Bondi (1964), with Mantina et al. (2009) for the
+//!   main-group elements Bondi left out.
+
+/// Tabulated properties of one element.
+pub(crate) struct ElementData {
+    /// English name, in IUPAC spelling.
+    pub(crate) name: &'static str,
+    /// Standard atomic weight in daltons.
+    pub(crate) average_mass: f64,
+    /// Mass of the most abundant isotope in daltons.
+    pub(crate) monoisotopic_mass: f64,
+    /// Pauling electronegativity.
+    pub(crate) electronegativity: Option<f64>,
+    /// Single-bond covalent radius in ångströms.
+    pub(crate) covalent_radius: Option<f64>,
+    /// Van der Waals radius in ångströms.
+    pub(crate) van_der_waals_radius: Option<f64>,
+}
+
+const fn data(
+    name: &'static str,
+    average_mass: f64,
+    monoisotopic_mass: f64,
+    electronegativity: Option<f64>,
+    covalent_radius: Option<f64>,
+    van_der_waals_radius: Option<f64>,
+) -> ElementData {
+    ElementData {
+        name,
+        average_mass,
+        monoisotopic_mass,
+        electronegativity,
+        covalent_radius,
+        van_der_waals_radius,
+    }
+}
+
+/// Element data indexed by atomic number minus one.
+#[rustfmt::skip]
+pub(crate) static ELEMENTS: [ElementData; 118] = [
+    data("Hydrogen", 1.008, 1.00782503223, Some(2.20), Some(0.31), Some(1.20)),
+    data("Helium", 4.002602, 4.00260325413, None, Some(0.28), Some(1.40)),
+    data("Lithium", 6.94, 7.0160034366, Some(0.98), Some(1.28), Some(1.82)),
+    data("Beryllium", 9.0121831, 9.012183065, Some(1.57), Some(0.96), Some(1.53)),
+    data("Boron", 10.81, 11.00930536, Some(2.04), Some(0.84), Some(1.92)),
+    data("Carbon", 12.011, 12.0, Some(2.55), Some(0.76), Some(1.70)),
+    data("Nitrogen", 14.007, 14.00307400443, Some(3.04), Some(0.71), Some(1.55)),
+    data("Oxygen", 15.999, 15.99491461957, Some(3.44), Some(0.66), Some(1.52)),
+    data("Fluorine", 18.998403163, 18.99840316273, Some(3.98), Some(0.57), Some(1.47)),
+    data("Neon", 20.1797, 19.9924401762, None, Some(0.58), Some(1.54)),
+    data("Sodium", 22.98976928, 22.989769282, Some(0.93), Some(1.66), Some(2.27)),
+    data("Magnesium", 24.305, 23.985041697, Some(1.31), Some(1.41), Some(1.73)),
+    data("Aluminium", 26.9815385, 26.98153853, Some(1.61), Some(1.21), Some(1.84)),
+    data("Silicon", 28.085, 27.97692653465, Some(1.90), Some(1.11), Some(2.10)),
+    data("Phosphorus", 30.973761998, 30.97376199842, Some(2.19), Some(1.07), Some(1.80)),
+    data("Sulfur", 32.06, 31.9720711744, Some(2.58), Some(1.05), Some(1.80)),
+    data("Chlorine", 35.45, 34.968852682, Some(3.16), Some(1.02), Some(1.75)),
+    data("Argon", 39.948, 39.9623831237, None, Some(1.06), Some(1.88)),
+    data("Potassium", 39.0983, 38.9637064864, Some(0.82), Some(2.03), Some(2.75)),
+    data("Calcium", 40.078, 39.962590863, Some(1.00), Some(1.76), Some(2.31)),
+    data("Scandium", 44.955908, 44.95590828, Some(1.36), Some(1.70), None),
+    data("Titanium", 47.867, 47.94794198, Some(1.54), Some(1.60), None),
+    data("Vanadium", 50.9415, 50.94395704, Some(1.63), Some(1.53), None),
+    data("Chromium", 51.9961, 51.94050623, Some(1.66), Some(1.39), None),
+    data("Manganese", 54.938044, 54.93804391, Some(1.55), Some(1.39), None),
+    data("Iron", 55.845, 55.93493633, Some(1.83), Some(1.32), None),
+    data("Cobalt", 58.933194, 58.93319429, Some(1.88), Some(1.26), None),
+    data("Nickel", 58.6934, 57.93534241, Some(1.91), Some(1.24), Some(1.63)),
+    data("Copper", 63.546, 62.92959772, Some(1.90), Some(1.32), Some(1.40)),
+    data("Zinc", 65.38, 63.92914201, Some(1.65), Some(1.22), Some(1.39)),
+    data("Gallium", 69.723, 68.9255735, Some(1.81), Some(1.22), Some(1.87)),
+    data("Germanium", 72.630, 73.921177761, Some(2.01), Some(1.20), Some(2.11)),
+    data("Arsenic", 74.921595, 74.92159457, Some(2.18), Some(1.19), Some(1.85)),
+    data("Selenium", 78.971, 79.9165218, Some(2.55), Some(1.20), Some(1.90)),
+    data("Bromine", 79.904, 78.9183376, Some(2.96), Some(1.20), Some(1.85)),
+    data("Krypton", 83.798, 83.9114977282, Some(3.00), Some(1.16), Some(2.02)),
+    data("Rubidium", 85.4678, 84.9117897379, Some(0.82), Some(2.20), Some(3.03)),
+    data("Strontium", 87.62, 87.9056125, Some(0.95), Some(1.95), Some(2.49)),
+    data("Yttrium", 88.90584, 88.9058403, Some(1.22), Some(1.90), None),
+    data("Zirconium", 91.224, 89.9046977, Some(1.33), Some(1.75), None),
+    data("Niobium", 92.90637, 92.906373, Some(1.60), Some(1.64), None),
+    data("Molybdenum", 95.95, 97.90540482, Some(2.16), Some(1.54), None),
+    data("Technetium", 98.0, 97.9072124, Some(1.90), Some(1.47), None),
+    data("Ruthenium", 101.07, 101.9043441, Some(2.20), Some(1.46), None),
+    data("Rhodium", 102.90550, 102.905498, Some(2.28), Some(1.42), None),
+    data("Palladium", 106.42, 105.9034804, Some(2.20), Some(1.39), Some(1.63)),
+    data("Silver", 107.8682, 106.9050916, Some(1.93), Some(1.45), Some(1.72)),
+    data("Cadmium", 112.414, 113.90336509, Some(1.69), Some(1.44), Some(1.58)),
+    data("Indium", 114.818, 114.903878776, Some(1.78), Some(1.42), Some(1.93)),
+    data("Tin", 118.710, 119.90220163, Some(1.96), Some(1.39), Some(2.17)),
+    data("Antimony", 121.760, 120.903812, Some(2.05), Some(1.39), Some(2.06)),
+    data("Tellurium", 127.60, 129.906222748, Some(2.10), Some(1.38), Some(2.06)),
+    data("Iodine", 126.90447, 126.9044719, Some(2.66), Some(1.39), Some(1.98)),
+    data("Xenon", 131.293, 131.9041550856, Some(2.60), Some(1.40), Some(2.16)),
+    data("Caesium", 132.90545196, 132.905451961, Some(0.79), Some(2.44), Some(3.43)),
+    data("Barium", 137.327, 137.905247, Some(0.89), Some(2.15), Some(2.68)),
+    data("Lanthanum", 138.90547, 138.9063563, Some(1.10), Some(2.07), None),
+    data("Cerium", 140.116, 139.9054431, Some(1.12), Some(2.04), None),
+    data("Praseodymium", 140.90766, 140.9076576, Some(1.13), Some(2.03), None),
+    data("Neodymium", 144.242, 141.907729, Some(1.14), Some(2.01), None),
+    data("Promethium", 145.0, 144.9127559, Some(1.13), Some(1.99), None),
+    data("Samarium", 150.36, 151.9197397, Some(1.17), Some(1.98), None),
+    data("Europium", 151.964, 152.921238, Some(1.20), Some(1.98), None),
+    data("Gadolinium", 157.25, 157.9241123, Some(1.20), Some(1.96), None),
+    data("Terbium", 158.92535, 158.9253547, Some(1.10), Some(1.94), None),
+    data("Dysprosium", 162.500, 163.9291819, Some(1.22), Some(1.92), None),
+    data("Holmium", 164.93033, 164.9303288, Some(1.23), Some(1.92), None),
+    data("Erbium", 167.259, 165.9302995, Some(1.24), Some(1.89), None),
+    data("Thulium", 168.93422, 168.9342179, Some(1.25), Some(1.90), None),
+    data("Ytterbium", 173.045, 173.9388664, Some(1.10), Some(1.87), None),
+    data("Lutetium", 174.9668, 174.9407752, Some(1.27), Some(1.87), None),
+    data("Hafnium", 178.49, 179.946557, Some(1.30), Some(1.75), None),
+    data("Tantalum", 180.94788, 180.9479958, Some(1.50), Some(1.70), None),
+    data("Tungsten", 183.84, 183.95093092, Some(2.36), Some(1.62), None),
+    data("Rhenium", 186.207, 186.9557501, Some(1.90), Some(1.51), None),
+    data("Osmium", 190.23, 191.961477, Some(2.20), Some(1.44), None),
+    data("Iridium", 192.217, 192.9629216, Some(2.20), Some(1.41), None),
+    data("Platinum", 195.084, 194.9647917, Some(2.28), Some(1.36), Some(1.75)),
+    data("Gold", 196.966569, 196.96656879, Some(2.54), Some(1.36), Some(1.66)),
+    data("Mercury", 200.592, 201.9706434, Some(2.00), Some(1.32), Some(1.55)),
+    data("Thallium", 204.38, 204.9744278, Some(1.62), Some(1.45), Some(1.96)),
+    data("Lead", 207.2, 207.9766525, Some(2.33), Some(1.46), Some(2.02)),
+    data("Bismuth", 208.98040, 208.9803991, Some(2.02), Some(1.48), Some(2.07)),
+    data("Polonium", 209.0, 208.9824308, Some(2.00), Some(1.40), Some(1.97)),
+    data("Astatine", 210.0, 209.9871479, Some(2.20), Some(1.50), Some(2.02)),
+    data("Radon", 222.0, 222.0175782, Some(2.20), Some(1.50), Some(2.20)),
+    data("Francium", 223.0, 223.019736, Some(0.70), Some(2.60), Some(3.48)),
+    data("Radium", 226.0, 226.0254103, Some(0.90), Some(2.21), Some(2.83)),
+    data("Actinium", 227.0, 227.0277523, Some(1.10), Some(2.15), None),
+    data("Thorium", 232.0377, 232.0380558, Some(1.30), Some(2.06), None),
+    data("Protactinium", 231.03588, 231.0358842, Some(1.50), Some(2.00), None),
+    data("Uranium", 238.02891, 238.0507884, Some(1.38), Some(1.96), Some(1.86)),
+    data("Neptunium", 237.0, 237.0481736, Some(1.36), Some(1.90), None),
+    data("Plutonium", 244.0, 244.0642053, Some(1.28), Some(1.87), None),
+    data("Americium", 243.0, 243.0613813, Some(1.13), Some(1.80), None),
+    data("Curium", 247.0, 247.0703541, Some(1.28), Some(1.69), None),
+    data("Berkelium", 247.0, 247.0703073, Some(1.30), None, None),
+    data("Californium", 251.0, 251.0795886, Some(1.30), None, None),
+    data("Einsteinium", 252.0, 252.08298, Some(1.30), None, None),
+    data("Fermium", 257.0, 257.0951061, Some(1.30), None, None),
+    data("Mendelevium", 258.0, 258.0984315, Some(1.30), None, None),
+    data("Nobelium", 259.0, 259.10103, Some(1.30), None, None),
+    data("Lawrencium", 266.0, 266.11983, None, None, None),
+    data("Rutherfordium", 267.0, 267.12179, None, None, None),
+    data("Dubnium", 268.0, 268.12567, None, None, None),
+    data("Seaborgium", 269.0, 269.12863, None, None, None),
+    data("Bohrium", 270.0, 270.13336, None, None, None),
+    data("Hassium", 269.0, 269.13375, None, None, None),
+    data("Meitnerium", 278.0, 278.15631, None, None, None),
+    data("Darmstadtium", 281.0, 281.16451, None, None, None),
+    data("Roentgenium", 282.0, 282.16912, None, None, None),
+    data("Copernicium", 285.0, 285.17712, None, None, None),
+    data("Nihonium", 286.0, 286.18221, None, None, None),
+    data("Flerovium", 289.0, 289.19042, None, None, None),
+    data("Moscovium", 290.0, 290.19598, None, None, None),
+    data("Livermorium", 293.0, 293.20449, None, None, None),
+    data("Tennessine", 294.0, 294.21046, None, None, None),
+    data("Oganesson", 294.0, 294.21392, None, None, None),
+];
+
+/// Returns the period (row) of the element with the given atomic number.
+pub(crate) fn period(atomic_number: u8) -> u8 {
+    match atomic_number {
+        0..=2 => 1,
+        3..=10 => 2,
+        11..=18 => 3,
+        19..=36 => 4,
+        37..=54 => 5,
+        55..=86 => 6,
+        _ => 7,
+    }
+}
+
+/// Returns the IUPAC group (1–18) of the element with the given atomic
+/// number, or `None` for La–Yb and Ac–No. Lutetium and lawrencium sit in
+/// group 3.
+pub(crate) fn group(atomic_number: u8) -> Option<u8> {
+    let group = match atomic_number {
+        1 => 1,
+        2 => 18,
+        3..=4 => atomic_number - 2,
+        5..=10 => atomic_number + 8,
+        11..=12 => atomic_number - 10,
+        13..=18 => atomic_number,
+        19..=36 => atomic_number - 18,
+        37..=54 => atomic_number - 36,
+        55..=56 => atomic_number - 54,
+        71 | 103 => 3,
+        72..=86 => atomic_number - 68,
+        87..=88 => atomic_number - 86,
+        104..=118 => atomic_number - 100,
+        _ => return None,
+    };
+    Some(group)
+}
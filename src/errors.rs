@@ -1,24 +1,429 @@
 //! Error types emitted while running the perception pipeline.
 
-use crate::core::atom::AtomId;
+use crate::core::atom::{AtomId, Element};
+use crate::core::bond::BondId;
+use std::fmt;
 use thiserror::Error;
 
 /// Error returned when a perception stage cannot complete successfully.
-#[derive(Debug, Error)]
+///
+/// Every variant names the atoms and bonds at fault, and
+/// [`stage`](Self::stage) tells which part of the pipeline gave up. The
+/// [`Display`](fmt::Display) rendering leads with the stage and describes the
+/// problem in chemical terms, e.g. `Kekulization: no alternating single and
+/// double bond pattern found for the aromatic system of atoms 0, 1, 2, 3, 4
+/// after 100000 attempts`.
+#[derive(Debug)]
 pub enum PerceptionError {
-    /// The input graph references an atom identifier that was not provided.
-    #[error("graph integrity error: bond references non-existent atom ID {0}")]
-    InconsistentGraph(AtomId),
+    /// A bond references an atom identifier that was not provided.
+    InconsistentGraph {
+        /// The bond with the dangling reference.
+        bond: BondId,
+        /// The missing atom identifier.
+        atom: AtomId,
+    },
 
     /// Multiple edges connect the same pair of atoms in the source graph.
-    #[error("invalid graph topology: duplicate bond detected between atoms {start} and {end}")]
-    DuplicateBond { start: AtomId, end: AtomId },
+    DuplicateBond {
+        /// First atom of the repeated bond, as given by the graph.
+        start: AtomId,
+        /// Second atom of the repeated bond, as given by the graph.
+        end: AtomId,
+        /// The earlier bond followed by the one that repeats it.
+        bonds: Vec<BondId>,
+    },
 
-    /// Kekulization exhausted its attempt budget without finding a valid pattern.
-    #[error("kekulization failed for an aromatic component after {0} attempts")]
-    KekulizationFailed(usize),
+    /// A bond starts and ends at the same atom.
+    SelfLoop {
+        /// The looping bond.
+        bond: BondId,
+        /// The atom at both ends of the bond.
+        atom: AtomId,
+    },
 
-    /// The ring perception stage reported a failure.
-    #[error("ring perception failed: {0}")]
-    RingPerceptionFailed(String),
+    /// The smallest set of smallest rings of a ring system could not be
+    /// completed.
+    RingPerceptionFailed {
+        /// Atoms of the connected component, sorted by identifier.
+        atoms: Vec<AtomId>,
+        /// Bonds of the connected component, sorted by identifier.
+        bonds: Vec<BondId>,
+        /// Independent rings implied by the component's topology.
+        expected_rings: usize,
+        /// Independent rings actually found.
+        found_rings: usize,
+    },
+
+    /// Bonds explicitly marked as aromatic do not describe a valid aromatic system.
+    InvalidAromaticInput {
+        /// What is wrong with the annotated atoms and bonds.
+        issue: AromaticInputIssue,
+        /// Atoms involved in the problem, sorted by identifier.
+        atoms: Vec<AtomId>,
+        /// Explicitly aromatic bonds involved in the problem, sorted by identifier.
+        bonds: Vec<BondId>,
+        /// Alternative edits, any one of which is expected to resolve the problem.
+        suggested_fixes: Vec<AromaticFix>,
+    },
+
+    /// Kekulization exhausted its attempt budget without finding a valid
+    /// pattern for an aromatic component.
+    KekulizationFailed {
+        /// Atoms of the aromatic component, sorted by identifier.
+        atoms: Vec<AtomId>,
+        /// Bonds of the aromatic component, sorted by identifier.
+        bonds: Vec<BondId>,
+        /// Attempts spent before giving up.
+        attempts: usize,
+    },
+
+    /// Atoms have valences not allowed for their element and charge, and
+    /// [`PerceptionOptions::strict_valence`](crate::PerceptionOptions::strict_valence) is set.
+    InvalidValence {
+        /// One entry per offending atom, ordered by atom identifier.
+        errors: Vec<ValenceError>,
+    },
+}
+
+impl PerceptionError {
+    /// Returns the pipeline stage that raised the error.
+    pub fn stage(&self) -> PerceptionStage {
+        match self {
+            PerceptionError::InconsistentGraph { .. }
+            | PerceptionError::DuplicateBond { .. }
+            | PerceptionError::SelfLoop { .. } => PerceptionStage::GraphConstruction,
+            PerceptionError::RingPerceptionFailed { .. } => PerceptionStage::RingPerception,
+            PerceptionError::InvalidAromaticInput { .. } => PerceptionStage::AromaticityValidation,
+            PerceptionError::KekulizationFailed { .. } => PerceptionStage::Kekulization,
+            PerceptionError::InvalidValence { .. } => PerceptionStage::ValenceCheck,
+        }
+    }
+
+    /// Returns the atoms at fault, sorted by identifier.
+    pub fn atoms(&self) -> Vec<AtomId> {
+        let mut atoms = match self {
+            PerceptionError::InconsistentGraph { atom, .. }
+            | PerceptionError::SelfLoop { atom, .. } => vec![*atom],
+            PerceptionError::DuplicateBond { start, end, .. } => vec![*start, *end],
+            PerceptionError::RingPerceptionFailed { atoms, .. }
+            | PerceptionError::InvalidAromaticInput { atoms, .. }
+            | PerceptionError::KekulizationFailed { atoms, .. } => atoms.clone(),
+            PerceptionError::InvalidValence { errors } => {
+                errors.iter().map(|error| error.atom).collect()
+            }
+        };
+        atoms.sort_unstable();
+        atoms.dedup();
+        atoms
+    }
+
+    /// Returns the bonds at fault, sorted by identifier.
+    ///
+    /// Empty for [`InvalidValence`](Self::InvalidValence), which concerns atoms
+    /// only.
+    pub fn bonds(&self) -> Vec<BondId> {
+        let mut bonds = match self {
+            PerceptionError::InconsistentGraph { bond, .. }
+            | PerceptionError::SelfLoop { bond, .. } => vec![*bond],
+            PerceptionError::DuplicateBond { bonds, .. }
+            | PerceptionError::RingPerceptionFailed { bonds, .. }
+            | PerceptionError::InvalidAromaticInput { bonds, .. }
+            | PerceptionError::KekulizationFailed { bonds, .. } => bonds.clone(),
+            PerceptionError::InvalidValence { .. } => Vec::new(),
+        };
+        bonds.sort_unstable();
+        bonds.dedup();
+        bonds
+    }
+}
+
+impl fmt::Display for PerceptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.stage())?;
+        match self {
+            PerceptionError::InconsistentGraph { bond, atom } => write!(
+                f,
+                "bond {bond} refers to atom {atom}, which is not part of the molecule"
+            ),
+            PerceptionError::DuplicateBond { start, end, bonds } => write!(
+                f,
+                "atoms {start} and {end} are joined by more than one bond (bonds {})",
+                join(bonds, ", ")
+            ),
+            PerceptionError::SelfLoop { bond, atom } => {
+                write!(f, "bond {bond} joins atom {atom} to itself")
+            }
+            PerceptionError::RingPerceptionFailed {
+                atoms,
+                expected_rings,
+                found_rings,
+                ..
+            } => write!(
+                f,
+                "found only {found_rings} of the {expected_rings} independent rings expected in the ring system of atoms {}",
+                join(atoms, ", ")
+            ),
+            PerceptionError::InvalidAromaticInput {
+                issue,
+                atoms,
+                bonds,
+                suggested_fixes,
+            } => {
+                write!(
+                    f,
+                    "{issue} at atoms {} (aromatic bonds {})",
+                    join(atoms, ", "),
+                    join(bonds, ", ")
+                )?;
+                if !suggested_fixes.is_empty() {
+                    write!(f, "; to fix, {}", join(suggested_fixes, " or "))?;
+                }
+                Ok(())
+            }
+            PerceptionError::KekulizationFailed {
+                atoms, attempts, ..
+            } => write!(
+                f,
+                "no alternating single and double bond pattern found for the aromatic system of atoms {} after {attempts} attempts",
+                join(atoms, ", ")
+            ),
+            PerceptionError::InvalidValence { errors } => f.write_str(&join(errors, "; ")),
+        }
+    }
+}
+
+impl std::error::Error for PerceptionError {}
+
+/// Part of the perception pipeline at which a [`PerceptionError`] arose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PerceptionStage {
+    /// Reading atoms and bonds from the input graph.
+    GraphConstruction,
+    /// Finding the smallest set of smallest rings.
+    RingPerception,
+    /// Checking bonds explicitly marked as aromatic.
+    AromaticityValidation,
+    /// Assigning alternating single and double bonds to aromatic components.
+    Kekulization,
+    /// Checking atom valences under
+    /// [`PerceptionOptions::strict_valence`](crate::PerceptionOptions::strict_valence).
+    ValenceCheck,
+}
+
+impl fmt::Display for PerceptionStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PerceptionStage::GraphConstruction => "graph construction",
+            PerceptionStage::RingPerception => "ring perception",
+            PerceptionStage::AromaticityValidation => "aromaticity validation",
+            PerceptionStage::Kekulization => "Kekulization",
+            PerceptionStage::ValenceCheck => "valence check",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Joins displayable items with a separator.
+fn join<T: fmt::Display>(items: &[T], separator: &str) -> String {
+    items
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// An atom whose valence is not allowed for its element and charge.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error(
+    "atom {atom} ({element:?}, charge {charge}) has valence {actual}, expected one of {expected:?}"
+)]
+pub struct ValenceError {
+    /// The offending atom.
+    pub atom: AtomId,
+    /// Element of the atom.
+    pub element: Element,
+    /// Charge the valence was checked at, including any charge taken over
+    /// from detached metal–ligand bonds.
+    pub charge: i8,
+    /// Sum of the atom's bond orders and hydrogens.
+    pub actual: u8,
+    /// Valences allowed at `charge`, less any unpaired electrons.
+    pub expected: Vec<u8>,
+}
+
+/// Reason an explicit aromatic annotation was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AromaticInputIssue {
+    /// An aromatic bond does not belong to any ring.
+    AcyclicBond,
+    /// An atom with an aromatic bond cannot adopt sp2 hybridisation, for
+    /// example an sp3 carbon with four neighbours.
+    NotSp2Capable,
+    /// The atoms that need a π bond cannot be paired up by the aromatic bonds,
+    /// so no Kekulé structure exists.
+    NoKekuleStructure,
+}
+
+impl fmt::Display for AromaticInputIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            AromaticInputIssue::AcyclicBond => "aromatic bond outside of any ring",
+            AromaticInputIssue::NotSp2Capable => "aromatic atom cannot be sp2 hybridised",
+            AromaticInputIssue::NoKekuleStructure => "aromatic system has no Kekulé structure",
+        };
+        f.write_str(message)
+    }
+}
+
+/// An edit to the input graph suggested to repair explicit aromatic input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AromaticFix {
+    /// Add a hydrogen to the atom, turning a pyridine-type nitrogen into a
+    /// pyrrole-type one.
+    AddHydrogen(AtomId),
+    /// Give the atom the stated formal charge.
+    SetFormalCharge {
+        /// Atom whose charge should change.
+        atom: AtomId,
+        /// Suggested formal charge.
+        charge: i8,
+    },
+    /// Replace the aromatic bond with a localised single or double bond.
+    MarkNonAromatic(BondId),
+}
+
+impl fmt::Display for AromaticFix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AromaticFix::AddHydrogen(atom) => write!(f, "add a hydrogen to atom {atom}"),
+            AromaticFix::SetFormalCharge { atom, charge } => {
+                write!(f, "set the formal charge of atom {atom} to {charge:+}")
+            }
+            AromaticFix::MarkNonAromatic(bond) => {
+                write!(f, "draw bond {bond} as a single or double bond")
+            }
+        }
+    }
+}
+
+/// A soft anomaly noticed while perceiving a graph that did not stop the run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PerceptionWarning {
+    /// Several rings of one size are interchangeable, so the SSSR reported is
+    /// one arbitrary choice, as for the faces of cubane.
+    AmbiguousSssr {
+        /// Number of atoms in each interchangeable ring.
+        ring_size: usize,
+        /// Atoms of the rings left out of the SSSR, sorted by identifier.
+        atoms: Vec<AtomId>,
+    },
+    /// Ring perception found fewer independent rings in a connected
+    /// component than its topology implies; the rings found are kept.
+    IncompleteSssr {
+        /// Atoms of the component, sorted by identifier.
+        atoms: Vec<AtomId>,
+        /// Bonds of the component, sorted by identifier.
+        bonds: Vec<BondId>,
+        /// Independent rings implied by the component's topology.
+        expected_rings: usize,
+        /// Independent rings actually found.
+        found_rings: usize,
+    },
+    /// A fused ring system has more connected ring subsets than the
+    /// aromaticity search examines, so rings beyond the limit may be left
+    /// without aromatic flags.
+    AromaticSubsetsTruncated {
+        /// Atoms of the searched rings, sorted by identifier.
+        atoms: Vec<AtomId>,
+        /// Bonds of the searched rings, sorted by identifier.
+        bonds: Vec<BondId>,
+    },
+    /// An aromatic component had no valence-consistent Kekulé structure, and
+    /// the fallback search left atoms that need a π bond without a double bond.
+    AromaticPiBondDropped {
+        /// Atoms left without a Kekulé double bond, sorted by identifier.
+        atoms: Vec<AtomId>,
+        /// Bonds of the aromatic component, sorted by identifier.
+        bonds: Vec<BondId>,
+    },
+    /// An aromatic component admits no Kekulé structure and was left
+    /// delocalised without Kekulé orders. Only raised when
+    /// [`PerceptionOptions::lenient_kekulization`](crate::PerceptionOptions::lenient_kekulization)
+    /// is set.
+    UnkekulizedComponent {
+        /// Atoms of the component, sorted by identifier.
+        atoms: Vec<AtomId>,
+        /// Bonds of the component, sorted by identifier.
+        bonds: Vec<BondId>,
+    },
+    /// The element has no main-group valence electron count, so its lone
+    /// pairs were taken to be zero.
+    MissingValenceElectrons {
+        /// The atom.
+        atom: AtomId,
+        /// Element of the atom.
+        element: Element,
+    },
+    /// Bonds, charge and radicals account for more electrons than the atom
+    /// has, so its lone-pair estimate was clamped at zero.
+    ClampedLonePairs {
+        /// The atom.
+        atom: AtomId,
+        /// Electrons missing from the atom's valence shell.
+        deficit: u8,
+    },
+    /// The atom's steric number matches no supported hybridization.
+    UnknownHybridization {
+        /// The atom.
+        atom: AtomId,
+        /// Bonded neighbours plus lone pairs.
+        steric_number: u8,
+    },
+}
+
+impl fmt::Display for PerceptionWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PerceptionWarning::AmbiguousSssr { ring_size, atoms } => write!(
+                f,
+                "SSSR is not unique: {ring_size}-membered rings through atoms {atoms:?} are interchangeable"
+            ),
+            PerceptionWarning::IncompleteSssr {
+                atoms,
+                expected_rings,
+                found_rings,
+                ..
+            } => write!(
+                f,
+                "found only {found_rings} of the {expected_rings} independent rings expected in the ring system of atoms {atoms:?}"
+            ),
+            PerceptionWarning::AromaticSubsetsTruncated { atoms, .. } => write!(
+                f,
+                "too many ring subsets to search in the fused system of atoms {atoms:?}; some rings may lack aromatic flags"
+            ),
+            PerceptionWarning::AromaticPiBondDropped { atoms, .. } => write!(
+                f,
+                "no valence-consistent Kekulé structure: atoms {atoms:?} lost their aromatic π bond"
+            ),
+            PerceptionWarning::UnkekulizedComponent { atoms, .. } => write!(
+                f,
+                "aromatic component through atoms {atoms:?} has no Kekulé structure; left delocalised"
+            ),
+            PerceptionWarning::MissingValenceElectrons { atom, element } => write!(
+                f,
+                "atom {atom} ({element:?}) has no valence electron count; lone pairs assumed zero"
+            ),
+            PerceptionWarning::ClampedLonePairs { atom, deficit } => write!(
+                f,
+                "atom {atom} is {deficit} electron(s) short for its bonds and charge; lone pairs clamped to zero"
+            ),
+            PerceptionWarning::UnknownHybridization {
+                atom,
+                steric_number,
+            } => write!(
+                f,
+                "atom {atom} has steric number {steric_number}, which matches no hybridization"
+            ),
+        }
+    }
 }
@@ -0,0 +1,394 @@
+//! Molecular formulas, masses and net charges.
+//!
+//! A [`MolecularFormula`] counts the atoms of each element, with explicit and
+//! implicit hydrogens counted alike, and records the net charge. It can be
+//! built from any [`MoleculeGraph`] or parsed from a formula string, and two
+//! formulas compare equal when they describe the same composition and charge.
+
+use crate::core::atom::Element;
+use crate::graph::traits::{AtomView, MoleculeGraph};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Rest mass of the electron in daltons.
+const ELECTRON_MASS: f64 = 0.000_548_579_909_065;
+
+/// Error emitted when a formula string cannot be parsed.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FormulaParseError {
+    /// The formula contains no atoms.
+    #[error("formula is empty")]
+    Empty,
+
+    /// A symbol at the given byte offset is not an element.
+    #[error("unknown element symbol '{1}' at position {0}")]
+    UnknownElement(usize, String),
+
+    /// A character at the given byte offset cannot appear in a formula.
+    #[error("unexpected character '{1}' at position {0}")]
+    UnexpectedCharacter(usize, char),
+
+    /// Parentheses are not balanced.
+    #[error("unbalanced parenthesis at position {0}")]
+    UnbalancedParenthesis(usize),
+
+    /// A count or charge is too large to represent.
+    #[error("number at position {0} is too large")]
+    Overflow(usize),
+}
+
+/// Elemental composition and net charge of a molecule.
+///
+/// Formats in Hill order: carbon first, then hydrogen, then every other
+/// element alphabetically by symbol; without carbon, all elements are
+/// alphabetical. A non-zero charge follows as `+`, `-`, `+2`, `-3` and so on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MolecularFormula {
+    /// Atom counts keyed by atomic number; zero counts are never stored.
+    counts: BTreeMap<u8, u32>,
+    charge: i32,
+}
+
+impl MolecularFormula {
+    /// Counts the atoms, implicit hydrogens and formal charges of a graph.
+    pub fn from_graph<G: MoleculeGraph>(graph: &G) -> Self {
+        let mut formula = Self::default();
+        for atom in graph.atoms() {
+            formula.add(atom.element(), 1);
+            formula.add(Element::H, u32::from(atom.implicit_hydrogens()));
+            formula.charge += i32::from(atom.formal_charge());
+        }
+        formula
+    }
+
+    /// Returns the number of atoms of `element`.
+    pub fn count(&self, element: Element) -> u32 {
+        self.counts
+            .get(&element.atomic_number())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns each element present and its count, in Hill order.
+    pub fn elements(&self) -> Vec<(Element, u32)> {
+        let mut elements: Vec<(Element, u32)> = self
+            .counts
+            .iter()
+            .filter_map(|(&number, &count)| {
+                Element::from_atomic_number(number).map(|element| (element, count))
+            })
+            .collect();
+        let has_carbon = self.count(Element::C) > 0;
+        elements.sort_by_key(|&(element, _)| {
+            let rank = match element {
+                Element::C if has_carbon => 0,
+                Element::H if has_carbon => 1,
+                _ => 2,
+            };
+            (rank, element.symbol())
+        });
+        elements
+    }
+
+    /// Returns the net charge.
+    pub fn charge(&self) -> i32 {
+        self.charge
+    }
+
+    /// Returns the total number of atoms, hydrogens included.
+    pub fn atom_count(&self) -> u32 {
+        self.counts.values().sum()
+    }
+
+    /// Returns the number of atoms other than hydrogen.
+    pub fn heavy_atom_count(&self) -> u32 {
+        self.atom_count() - self.count(Element::H)
+    }
+
+    /// Returns the average molecular mass in daltons, from standard atomic
+    /// weights.
+    pub fn average_mass(&self) -> f64 {
+        self.mass(Element::average_mass)
+    }
+
+    /// Returns the monoisotopic mass in daltons, from the most abundant
+    /// isotope of each element.
+    ///
+    /// The mass of the electrons removed or added by the net charge is
+    /// included, so this is the exact mass of the ion.
+    pub fn monoisotopic_mass(&self) -> f64 {
+        self.mass(Element::monoisotopic_mass) - f64::from(self.charge) * ELECTRON_MASS
+    }
+
+    fn mass(&self, element_mass: fn(Element) -> f64) -> f64 {
+        self.elements()
+            .into_iter()
+            .map(|(element, count)| element_mass(element) * f64::from(count))
+            .sum()
+    }
+
+    fn add(&mut self, element: Element, count: u32) {
+        if count > 0 {
+            *self.counts.entry(element.atomic_number()).or_insert(0) += count;
+        }
+    }
+}
+
+impl fmt::Display for MolecularFormula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (element, count) in self.elements() {
+            write!(f, "{element}")?;
+            if count > 1 {
+                write!(f, "{count}")?;
+            }
+        }
+        let sign = if self.charge > 0 { '+' } else { '-' };
+        match self.charge.unsigned_abs() {
+            0 => Ok(()),
+            1 => write!(f, "{sign}"),
+            magnitude => write!(f, "{sign}{magnitude}"),
+        }
+    }
+}
+
+impl FromStr for MolecularFormula {
+    type Err = FormulaParseError;
+
+    /// Parses a formula such as `C2H6O`, `CH3COO-`, `Ca(OH)2` or `SO4-2`.
+    ///
+    /// Element symbols are case-sensitive, so `CO` is carbon monoxide and `Co`
+    /// is cobalt. Groups in parentheses or square brackets may carry a
+    /// multiplier. The formula may end with a charge written as a run of
+    /// signs (`--`) or as a sign followed by a number (`-2`). A number
+    /// followed by a sign (`2-`) is read as a charge only after whitespace,
+    /// since `S2-` is the disulfide radical anion.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            text: s.trim(),
+            position: 0,
+        };
+        let counts = parser.group(None)?;
+        let charge = parser.charge()?;
+
+        let mut formula = MolecularFormula {
+            counts: BTreeMap::new(),
+            charge,
+        };
+        for (number, count) in counts {
+            if let Some(element) = Element::from_atomic_number(number) {
+                formula.add(element, count);
+            }
+        }
+        if formula.counts.is_empty() {
+            return Err(FormulaParseError::Empty);
+        }
+        Ok(formula)
+    }
+}
+
+/// Recursive-descent parser over a formula string.
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    /// Parses atoms and nested groups until the closing bracket `close`, or
+    /// until the charge or the end of the text when `close` is `None`.
+    fn group(&mut self, close: Option<char>) -> Result<BTreeMap<u8, u32>, FormulaParseError> {
+        let mut counts = BTreeMap::new();
+        loop {
+            let start = self.position;
+            let part = match self.peek() {
+                Some(c) if Some(c) == close => {
+                    self.position += 1;
+                    return Ok(counts);
+                }
+                Some(c @ ('(' | '[')) => {
+                    self.position += 1;
+                    let closing = if c == '(' { ')' } else { ']' };
+                    self.group(Some(closing))?
+                }
+                Some(c) if c.is_ascii_uppercase() => {
+                    let element = self.element()?;
+                    BTreeMap::from([(element.atomic_number(), 1)])
+                }
+                Some(')' | ']') => return Err(FormulaParseError::UnbalancedParenthesis(start)),
+                Some('+' | '-' | ' ' | '\t') | None if close.is_none() => return Ok(counts),
+                None => return Err(FormulaParseError::UnbalancedParenthesis(self.text.len())),
+                Some(c) => return Err(FormulaParseError::UnexpectedCharacter(start, c)),
+            };
+
+            let multiplier = self.number()?.unwrap_or(1);
+            for (number, count) in part {
+                let total = count
+                    .checked_mul(multiplier)
+                    .and_then(|count| count.checked_add(counts.get(&number).copied().unwrap_or(0)))
+                    .ok_or(FormulaParseError::Overflow(start))?;
+                counts.insert(number, total);
+            }
+        }
+    }
+
+    /// Reads an uppercase letter and an optional lowercase letter as a
+    /// case-sensitive element symbol.
+    fn element(&mut self) -> Result<Element, FormulaParseError> {
+        let start = self.position;
+        let rest = &self.text[start..];
+        let length = 1 + rest[1..]
+            .chars()
+            .take_while(char::is_ascii_lowercase)
+            .count();
+        let symbol = &rest[..length];
+        self.position += length;
+        (1..=118)
+            .filter_map(Element::from_atomic_number)
+            .find(|element| element.symbol() == symbol)
+            .ok_or_else(|| FormulaParseError::UnknownElement(start, symbol.to_string()))
+    }
+
+    /// Reads an unsigned decimal number, if one follows.
+    fn number(&mut self) -> Result<Option<u32>, FormulaParseError> {
+        let start = self.position;
+        let digits = self.text[start..]
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .count();
+        if digits == 0 {
+            return Ok(None);
+        }
+        self.position += digits;
+        self.text[start..self.position]
+            .parse()
+            .map(Some)
+            .map_err(|_| FormulaParseError::Overflow(start))
+    }
+
+    /// Parses the optional trailing charge: a run of identical signs, a
+    /// sign followed by a number, or a number followed by a sign.
+    fn charge(&mut self) -> Result<i32, FormulaParseError> {
+        let rest = &self.text[self.position..];
+        let text = rest.trim_start();
+        let offset = self.position + rest.len() - text.len();
+        let Some(first) = text.chars().next() else {
+            return Ok(0);
+        };
+
+        let (sign, magnitude) = if text.chars().all(|c| c == first) && matches!(first, '+' | '-') {
+            (first, Ok(text.len()))
+        } else if let Some(digits) = text.strip_prefix(['+', '-']) {
+            (first, digits.parse::<usize>())
+        } else if let Some(digits) = text.strip_suffix(['+', '-']) {
+            (
+                text.chars().last().unwrap_or(first),
+                digits.parse::<usize>(),
+            )
+        } else {
+            return Err(FormulaParseError::UnexpectedCharacter(offset, first));
+        };
+
+        let magnitude = match magnitude {
+            Ok(magnitude) => magnitude,
+            Err(error) if *error.kind() == std::num::IntErrorKind::PosOverflow => {
+                return Err(FormulaParseError::Overflow(offset));
+            }
+            Err(_) => return Err(FormulaParseError::UnexpectedCharacter(offset, first)),
+        };
+        let magnitude =
+            i32::try_from(magnitude).map_err(|_| FormulaParseError::Overflow(offset))?;
+        Ok(if sign == '+' { magnitude } else { -magnitude })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::bond::BondOrder;
+    use crate::molecule::Molecule;
+
+    fn acetate() -> Molecule {
+        let mut acetate = Molecule::new();
+        let methyl = acetate.add_atom(Element::C, 0);
+        acetate
+            .set_implicit_hydrogens(methyl, 3)
+            .expect("failed to set hydrogens");
+        let carboxyl = acetate.add_atom(Element::C, 0);
+        let carbonyl = acetate.add_atom(Element::O, 0);
+        let oxide = acetate.add_atom(Element::O, -1);
+        acetate
+            .add_bond(methyl, carboxyl, BondOrder::Single)
+            .expect("failed to add C-C bond");
+        acetate
+            .add_bond(carboxyl, carbonyl, BondOrder::Double)
+            .expect("failed to add C=O bond");
+        acetate
+            .add_bond(carboxyl, oxide, BondOrder::Single)
+            .expect("failed to add C-O bond");
+        acetate
+    }
+
+    #[test]
+    fn graph_formula_counts_implicit_hydrogens_and_charge() {
+        let formula = MolecularFormula::from_graph(&acetate());
+        assert_eq!(formula.to_string(), "C2H3O2-");
+        assert_eq!(formula.count(Element::H), 3);
+        assert_eq!(formula.charge(), -1);
+        assert_eq!(formula.atom_count(), 7);
+        assert_eq!(formula.heavy_atom_count(), 4);
+        assert!((formula.average_mass() - 59.044).abs() < 1e-3);
+        assert!((formula.monoisotopic_mass() - 59.013853).abs() < 1e-6);
+    }
+
+    #[test]
+    fn hill_order_is_alphabetical_without_carbon() {
+        let formula: MolecularFormula = "ClNaO4".parse().unwrap();
+        assert_eq!(formula.to_string(), "ClNaO4");
+        let formula: MolecularFormula = "H2SO4".parse().unwrap();
+        assert_eq!(formula.to_string(), "H2O4S");
+        let formula: MolecularFormula = "ClCH2Br".parse().unwrap();
+        assert_eq!(formula.to_string(), "CH2BrCl");
+    }
+
+    #[test]
+    fn parser_accepts_groups_and_charge_notations() {
+        let parse = |text: &str| text.parse::<MolecularFormula>().unwrap();
+        assert_eq!(parse("CH3COO-"), MolecularFormula::from_graph(&acetate()));
+        assert_eq!(parse("Ca(OH)2").to_string(), "CaH2O2");
+        assert_eq!(parse("[Fe(CN)6]-4").to_string(), "C6FeN6-4");
+        assert_eq!(parse("S2-").count(Element::S), 2);
+        for text in ["SO4 2-", "SO4-2", "SO4--", "O4S-2"] {
+            assert_eq!(parse(text).to_string(), "O4S-2", "{text}");
+            assert_eq!(parse(text).charge(), -2, "{text}");
+        }
+        assert_eq!(parse("NH4+").charge(), 1);
+        assert_eq!(parse("CO").count(Element::Co), 0);
+        assert_eq!(parse("Co").count(Element::Co), 1);
+    }
+
+    #[test]
+    fn parser_rejects_malformed_formulas() {
+        let parse = |text: &str| text.parse::<MolecularFormula>().unwrap_err();
+        assert_eq!(parse(""), FormulaParseError::Empty);
+        assert_eq!(
+            parse("CXy2"),
+            FormulaParseError::UnknownElement(1, "Xy".to_string())
+        );
+        assert_eq!(
+            parse("c6h6"),
+            FormulaParseError::UnexpectedCharacter(0, 'c')
+        );
+        assert_eq!(parse("Ca(OH2"), FormulaParseError::UnbalancedParenthesis(6));
+        assert_eq!(parse("CaOH)2"), FormulaParseError::UnbalancedParenthesis(4));
+        assert_eq!(
+            parse("NH4+-"),
+            FormulaParseError::UnexpectedCharacter(3, '+')
+        );
+        assert_eq!(parse("C99999999999"), FormulaParseError::Overflow(1));
+    }
+}
@@ -17,6 +17,26 @@ pub trait AtomView {
 
     /// Returns the formal charge stored on the atom.
     fn formal_charge(&self) -> i8;
+
+    /// Returns the number of hydrogens attached to the atom that are not
+    /// present as explicit atoms in the graph.
+    ///
+    /// Graphs that store every hydrogen explicitly can rely on the default of
+    /// zero. Aromatic input with implicit hydrogens should report them so that
+    /// pyrrole-type nitrogens (`[nH]`) can be told apart from pyridine-type
+    /// ones (`n`).
+    fn implicit_hydrogens(&self) -> u8 {
+        0
+    }
+
+    /// Returns the number of unpaired electrons on the atom.
+    ///
+    /// Closed-shell graphs can rely on the default of zero. Radicals such as
+    /// the allyl or benzyl radical, nitroxides and phenoxyls report one;
+    /// triplet carbenes report two.
+    fn radical_electrons(&self) -> u8 {
+        0
+    }
 }
 
 /// Read-only view over a bond supplied by a user-defined molecular graph.
@@ -14,7 +14,7 @@
 //! 3.  **Kekulization:** Assigns a valid Kekulé structure to aromatic rings.
 //! 4.  **Atom State Perception:** Calculates valence, lone pairs, and hybridization.
 //! 5.  **Resonance Identification:** Groups all conjugated atoms and bonds into
-//!     distinct resonance systems.
+//!     resonance systems, one per connected π plane.
 //!
 //! The library is designed to be flexible. It operates on any data structure that
 //! implements the [`traits::MoleculeGraph`] trait, allowing seamless integration with
@@ -68,15 +68,41 @@
 //! # Ok::<(), PerceptionError>(())
 //! ```
 
+mod charge;
+mod convert;
 mod core;
 mod errors;
+mod formula;
 mod graph;
 mod molecule;
+mod oxidation;
 mod perception;
+mod report;
 mod resonance;
+mod standardiser;
 
+/// Checks supplied formal charges and re-derives those that do not fit.
+pub use crate::assign_formal_charges_impl::assign_formal_charges;
+/// Assigns every atom an oxidation state by electronegativity partitioning.
+pub use crate::assign_oxidation_states_impl::assign_oxidation_states;
+/// Enumerates every simple cycle of a molecular graph up to a size bound.
+pub use crate::find_cycles_impl::find_cycles;
 /// The primary entry point to the `pauling` perception pipeline.
 pub use crate::find_resonance_systems_impl::find_resonance_systems;
+/// Runs the perception pipeline with caller-supplied [`PerceptionOptions`].
+pub use crate::find_resonance_systems_impl::find_resonance_systems_with_options;
+/// Runs the perception pipeline and also returns its non-fatal warnings.
+pub use crate::find_resonance_systems_impl::find_resonance_systems_with_warnings;
+/// Counts the elements, hydrogens and net charge of a molecular graph.
+pub use crate::molecular_formula_impl::molecular_formula;
+/// Runs the perception pipeline and reports resonance and ring metadata.
+pub use crate::perceive_impl::perceive;
+/// Rewrites semipolar functional groups into one drawing convention.
+pub use crate::standardise_impl::standardise;
+/// Rewrites perceived aromatic bonds to `BondOrder::Aromatic`.
+pub use crate::to_aromatic_molecule_impl::to_aromatic_molecule;
+/// Rewrites aromatic bonds into their assigned Kekulé single/double orders.
+pub use crate::to_kekule_molecule_impl::to_kekule_molecule;
 
 /// A stable, user-facing identifier for an atom.
 pub use core::atom::AtomId;
@@ -87,19 +113,223 @@ pub use core::bond::BondId;
 /// An enumeration of bond orders (Single, Double, etc.).
 pub use core::bond::BondOrder;
 
+/// A suggested repair for invalid explicit aromatic input.
+pub use errors::AromaticFix;
+/// The reason explicit aromatic input was rejected.
+pub use errors::AromaticInputIssue;
 /// The error type for all fallible perception operations.
 pub use errors::PerceptionError;
-/// Represents a single, connected network of conjugated atoms and bonds.
+/// The pipeline stage at which a [`PerceptionError`] arose.
+pub use errors::PerceptionStage;
+/// A soft anomaly that did not stop the perception pipeline.
+pub use errors::PerceptionWarning;
+/// An atom whose valence is not allowed for its element and charge.
+pub use errors::ValenceError;
+/// Aromaticity conventions of common cheminformatics toolkits.
+pub use perception::AromaticityModel;
+/// VSEPR geometry perceived around an atom.
+pub use perception::Geometry;
+/// Hybridization state perceived for an atom.
+pub use perception::Hybridization;
+/// Settings that tune the perception pipeline.
+pub use perception::PerceptionOptions;
+/// A ring reported by cycle enumeration, with atoms in sorted and path order.
+pub use perception::Ring;
+/// Aromatic, antiaromatic or non-aromatic classification of a ring or ring system.
+pub use perception::RingAromaticity;
+/// Hybridization, steric number and geometry of a single atom.
+pub use report::AtomReport;
+/// Metal atom and its ligands, reported in metal-aware mode.
+pub use report::MetalCentre;
+/// Ligand fragment bound to a metal centre.
+pub use report::MetalLigand;
+/// Resonance systems and warnings, returned by [`find_resonance_systems_with_warnings`].
+pub use report::PerceptionOutcome;
+/// The full result of a perception run, returned by [`perceive`].
+pub use report::PerceptionReport;
+/// A single ring and its aromatic character within a [`PerceptionReport`].
+pub use report::RingReport;
+/// A fused ring system and its aromatic character within a [`PerceptionReport`].
+pub use report::RingSystemReport;
+/// A set of atoms and bonds conjugated through one π plane.
 pub use resonance::ResonanceSystem;
+/// A semipolar bond rewritten by [`standardise`].
+pub use standardiser::AppliedRule;
+/// Drawing convention selected for [`standardise`].
+pub use standardiser::StandardForm;
+/// Standardised copy and rule log returned by [`standardise`].
+pub use standardiser::Standardisation;
+/// Functional group recognised by [`standardise`].
+pub use standardiser::StandardisationRule;
 
+/// Corrected copy and discrepancy list returned by [`assign_formal_charges`].
+pub use charge::FormalChargeAssignment;
+/// An atom whose supplied formal charge was rejected.
+pub use charge::FormalChargeDiscrepancy;
+/// A [`Molecule`] rebuilt by a converter, with mappings to the source identifiers.
+pub use convert::ConvertedMolecule;
+/// Error returned when a formula string cannot be parsed.
+pub use formula::FormulaParseError;
+/// Elemental composition and net charge returned by [`molecular_formula`].
+pub use formula::MolecularFormula;
 /// A simple, in-memory molecular graph implementation for examples and testing.
 pub use molecule::Molecule;
 /// Errors that can occur during the construction of a [`Molecule`].
 pub use molecule::MoleculeBuildError;
+/// Oxidation state of one atom returned by [`assign_oxidation_states`].
+pub use oxidation::OxidationState;
+/// Treatment of delocalised systems selected for [`assign_oxidation_states`].
+pub use oxidation::OxidationStateModel;
 
 /// The core traits (`MoleculeGraph`, `AtomView`, `BondView`) for graph abstraction.
 pub use crate::graph::traits;
 
+mod assign_formal_charges_impl {
+    use super::*;
+    use crate::graph::traits::MoleculeGraph;
+    use crate::perception::ChemicalPerception;
+
+    /// Derives the formal charge of every atom from its bonds and hydrogens and
+    /// compares it with the charge supplied by the graph.
+    ///
+    /// The perception pipeline is run on `graph`, so aromatic bonds are counted
+    /// with their Kekulé orders. A supplied charge is kept whenever it leaves
+    /// the atom with paired non-bonding electrons and a valence shell no more
+    /// than one pair short of the octet; second-period atoms may not exceed it.
+    /// Otherwise the smallest charge that satisfies the octet rule replaces
+    /// it, and the atom is listed as a discrepancy. Metals and elements without
+    /// a main-group valence electron count are left unchecked.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - A reference to any type that implements the [`MoleculeGraph`]
+    ///   trait. The graph is treated as read-only.
+    /// * `options` - Settings applied to the perception pipeline.
+    ///
+    /// # Returns
+    ///
+    /// A [`FormalChargeAssignment`] holding a copy of `graph` with corrected
+    /// charges and the list of atoms whose charge was rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PerceptionError`] under the same conditions as
+    /// [`find_resonance_systems`].
+    ///
+    /// # Examples
+    ///
+    /// A tetramethylammonium nitrogen stored as neutral is corrected to `+1`.
+    ///
+    /// ```
+    /// use pauling::traits::{AtomView, MoleculeGraph};
+    /// use pauling::{
+    ///     assign_formal_charges, BondOrder, Element, Molecule, PerceptionError,
+    ///     PerceptionOptions,
+    /// };
+    ///
+    /// let mut ammonium = Molecule::new();
+    /// let n = ammonium.add_atom(Element::N, 0);
+    /// for _ in 0..4 {
+    ///     let c = ammonium.add_atom(Element::C, 0);
+    ///     ammonium.set_implicit_hydrogens(c, 3).unwrap();
+    ///     ammonium.add_bond(n, c, BondOrder::Single).unwrap();
+    /// }
+    ///
+    /// let assignment = assign_formal_charges(&ammonium, &PerceptionOptions::default())?;
+    /// assert_eq!(assignment.discrepancies.len(), 1);
+    /// assert_eq!(assignment.discrepancies[0].derived, Some(1));
+    /// let corrected = &assignment.corrected;
+    /// let n = corrected.atom_id(n).unwrap();
+    /// assert_eq!(corrected.molecule.atom(n).unwrap().formal_charge(), 1);
+    /// # Ok::<(), PerceptionError>(())
+    /// ```
+    pub fn assign_formal_charges<G: MoleculeGraph>(
+        graph: &G,
+        options: &PerceptionOptions,
+    ) -> Result<FormalChargeAssignment, PerceptionError> {
+        let perception = ChemicalPerception::from_graph_with_options(graph, options)?;
+
+        Ok(charge::assign(&perception))
+    }
+}
+
+mod assign_oxidation_states_impl {
+    use super::*;
+    use crate::graph::traits::MoleculeGraph;
+    use crate::perception::ChemicalPerception;
+
+    /// Assigns every atom an oxidation state by handing each bonding pair to
+    /// the more electronegative end of its bond.
+    ///
+    /// Pairs shared between atoms of equal Pauling electronegativity, including
+    /// every homonuclear bond, are split evenly, as are bonds to elements
+    /// without a tabulated electronegativity. Implicit hydrogens count like
+    /// explicit ones, aromatic bonds use their Kekulé orders, and a dative
+    /// bond's pair starts out on its donor. In metal-aware mode, detached
+    /// metal–ligand bonds are treated as ionic.
+    ///
+    /// With [`OxidationStateModel::ResonanceAveraged`], the states of each
+    /// delocalised system are averaged over its resonance contributors, so the
+    /// result no longer depends on which contributor was drawn.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - A reference to any type that implements the [`MoleculeGraph`]
+    ///   trait. The graph is treated as read-only.
+    /// * `options` - Settings applied to the perception pipeline.
+    /// * `model` - Whether delocalised systems are averaged over contributors.
+    ///
+    /// # Returns
+    ///
+    /// One [`OxidationState`] per atom, ordered by atom identifier.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PerceptionError`] under the same conditions as
+    /// [`find_resonance_systems`].
+    ///
+    /// # Examples
+    ///
+    /// The methyl carbon of acetic acid sits at `-3` and the carboxyl carbon
+    /// at `+3`.
+    ///
+    /// ```
+    /// use pauling::{
+    ///     assign_oxidation_states, BondOrder, Element, Molecule, OxidationStateModel,
+    ///     PerceptionError, PerceptionOptions,
+    /// };
+    ///
+    /// let mut acid = Molecule::new();
+    /// let methyl = acid.add_atom(Element::C, 0);
+    /// acid.set_implicit_hydrogens(methyl, 3).unwrap();
+    /// let carboxyl = acid.add_atom(Element::C, 0);
+    /// let carbonyl = acid.add_atom(Element::O, 0);
+    /// let hydroxyl = acid.add_atom(Element::O, 0);
+    /// acid.set_implicit_hydrogens(hydroxyl, 1).unwrap();
+    /// acid.add_bond(methyl, carboxyl, BondOrder::Single).unwrap();
+    /// acid.add_bond(carboxyl, carbonyl, BondOrder::Double).unwrap();
+    /// acid.add_bond(carboxyl, hydroxyl, BondOrder::Single).unwrap();
+    ///
+    /// let states = assign_oxidation_states(
+    ///     &acid,
+    ///     &PerceptionOptions::default(),
+    ///     OxidationStateModel::Localised,
+    /// )?;
+    /// let values: Vec<f64> = states.iter().map(|state| state.value).collect();
+    /// assert_eq!(values, vec![-3.0, 3.0, -2.0, -2.0]);
+    /// # Ok::<(), PerceptionError>(())
+    /// ```
+    pub fn assign_oxidation_states<G: MoleculeGraph>(
+        graph: &G,
+        options: &PerceptionOptions,
+        model: OxidationStateModel,
+    ) -> Result<Vec<OxidationState>, PerceptionError> {
+        let perception = ChemicalPerception::from_graph_with_options(graph, options)?;
+
+        Ok(oxidation::assign(&perception, model))
+    }
+}
+
 mod find_resonance_systems_impl {
     use super::*;
     use crate::graph::traits::MoleculeGraph;
@@ -122,7 +352,11 @@ mod find_resonance_systems_impl {
     ///
     /// On success, returns a `Vec<ResonanceSystem>`. Each system contains the
     /// atom and bond identifiers (as provided by the input graph) that belong
-    /// to a single, continuous conjugated network.
+    /// to one continuous conjugated network in a single π plane. Systems are
+    /// disjoint except at sp centres, whose two orthogonal π planes may belong
+    /// to different systems: the two systems of an allene share its central
+    /// carbon, and the out-of-plane system of vinylacetylene repeats its
+    /// C≡C bond.
     ///
     /// # Errors
     ///
@@ -179,4 +413,539 @@ mod find_resonance_systems_impl {
 
         Ok(systems)
     }
+
+    /// Finds all resonance systems using caller-supplied [`PerceptionOptions`].
+    ///
+    /// This behaves like [`find_resonance_systems`] but lets the caller tune
+    /// individual perception stages, for example to select the
+    /// [`AromaticityModel`] closest to the toolkit a data set originated from.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - A reference to any type that implements the [`MoleculeGraph`]
+    ///   trait. The graph is treated as read-only.
+    /// * `options` - Settings applied to the perception pipeline.
+    ///
+    /// # Returns
+    ///
+    /// On success, returns a `Vec<ResonanceSystem>` exactly like
+    /// [`find_resonance_systems`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PerceptionError`] under the same conditions as
+    /// [`find_resonance_systems`].
+    ///
+    /// # Examples
+    ///
+    /// The tropylium cation is aromatic under the default model but not under
+    /// MMFF94, which only considers five- and six-membered rings. Its ring
+    /// bonds are conjugated either way, so both models find the same system
+    /// and only the ring classification differs.
+    ///
+    /// ```
+    /// use pauling::{
+    ///     find_resonance_systems_with_options, perceive, AromaticityModel, BondOrder, Element,
+    ///     Molecule, PerceptionError, PerceptionOptions, RingAromaticity,
+    /// };
+    ///
+    /// let mut tropylium = Molecule::new();
+    /// let c: Vec<_> = (0..7)
+    ///     .map(|i| tropylium.add_atom(Element::C, if i == 0 { 1 } else { 0 }))
+    ///     .collect();
+    /// for i in 0..7 {
+    ///     let order = if i % 2 == 1 { BondOrder::Double } else { BondOrder::Single };
+    ///     tropylium.add_bond(c[i], c[(i + 1) % 7], order).unwrap();
+    /// }
+    ///
+    /// let default_options = PerceptionOptions::default();
+    /// let mmff94_options = PerceptionOptions {
+    ///     aromaticity_model: AromaticityModel::Mmff94,
+    ///     ..PerceptionOptions::default()
+    /// };
+    ///
+    /// let systems = find_resonance_systems_with_options(&tropylium, &mmff94_options)?;
+    /// assert_eq!(systems.len(), 1);
+    /// assert_eq!(systems[0].atoms.len(), 7);
+    /// assert_eq!(
+    ///     systems,
+    ///     find_resonance_systems_with_options(&tropylium, &default_options)?
+    /// );
+    ///
+    /// let default_report = perceive(&tropylium, &default_options)?;
+    /// assert_eq!(default_report.rings[0].aromaticity, RingAromaticity::Aromatic);
+    /// let mmff94_report = perceive(&tropylium, &mmff94_options)?;
+    /// assert_eq!(mmff94_report.rings[0].aromaticity, RingAromaticity::NonAromatic);
+    /// # Ok::<(), PerceptionError>(())
+    /// ```
+    pub fn find_resonance_systems_with_options<G: MoleculeGraph>(
+        graph: &G,
+        options: &PerceptionOptions,
+    ) -> Result<Vec<ResonanceSystem>, PerceptionError> {
+        let perception = ChemicalPerception::from_graph_with_options(graph, options)?;
+
+        let systems = resonance::find_systems(&perception);
+
+        Ok(systems)
+    }
+
+    /// Finds all resonance systems and reports the soft anomalies met on the way.
+    ///
+    /// This behaves like [`find_resonance_systems_with_options`] but also
+    /// returns the [`PerceptionWarning`]s recorded by the pipeline stages, such
+    /// as an ambiguous or incomplete SSSR, a fused ring system too large for
+    /// the aromatic subset search, an element without a valence electron
+    /// count, a clamped lone-pair estimate, an unknown hybridization, or
+    /// aromatic atoms left without a π bond by the fallback Kekulé search. Warnings never
+    /// change the systems found; they flag structures worth a second look.
+    /// With [`PerceptionOptions::lenient_kekulization`] set, aromatic
+    /// components without a Kekulé structure are listed here too instead of
+    /// aborting the run.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - A reference to any type that implements the [`MoleculeGraph`]
+    ///   trait. The graph is treated as read-only.
+    /// * `options` - Settings applied to the perception pipeline.
+    ///
+    /// # Returns
+    ///
+    /// A [`PerceptionOutcome`] holding the resonance systems and the warnings
+    /// in pipeline order.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PerceptionError`] under the same conditions as
+    /// [`find_resonance_systems`].
+    ///
+    /// # Examples
+    ///
+    /// Any five of the six faces of cubane form an SSSR.
+    ///
+    /// ```
+    /// use pauling::{
+    ///     find_resonance_systems_with_warnings, BondOrder, Element, Molecule, PerceptionError,
+    ///     PerceptionOptions, PerceptionWarning,
+    /// };
+    ///
+    /// let mut cubane = Molecule::new();
+    /// let c: Vec<_> = (0..8).map(|_| cubane.add_atom(Element::C, 0)).collect();
+    /// for &(a, b) in &[
+    ///     (0, 1), (1, 2), (2, 3), (3, 0), (4, 5), (5, 6),
+    ///     (6, 7), (7, 4), (0, 4), (1, 5), (2, 6), (3, 7),
+    /// ] {
+    ///     cubane.add_bond(c[a], c[b], BondOrder::Single).unwrap();
+    /// }
+    /// for &atom in &c {
+    ///     cubane.set_implicit_hydrogens(atom, 1).unwrap();
+    /// }
+    ///
+    /// let outcome = find_resonance_systems_with_warnings(&cubane, &PerceptionOptions::default())?;
+    /// assert!(outcome.systems.is_empty());
+    /// assert!(matches!(
+    ///     outcome.warnings[..],
+    ///     [PerceptionWarning::AmbiguousSssr { ring_size: 4, .. }]
+    /// ));
+    /// # Ok::<(), PerceptionError>(())
+    /// ```
+    pub fn find_resonance_systems_with_warnings<G: MoleculeGraph>(
+        graph: &G,
+        options: &PerceptionOptions,
+    ) -> Result<PerceptionOutcome, PerceptionError> {
+        let perception = ChemicalPerception::from_graph_with_options(graph, options)?;
+
+        Ok(PerceptionOutcome {
+            systems: resonance::find_systems(&perception),
+            warnings: perception.warnings,
+        })
+    }
+}
+
+mod molecular_formula_impl {
+    use super::*;
+    use crate::graph::traits::MoleculeGraph;
+
+    /// Computes the molecular formula of a graph.
+    ///
+    /// Every atom is counted once by element, implicit hydrogens are added to
+    /// the hydrogen count, and formal charges are summed into the net charge.
+    /// No perception is run, so this works on any graph, including ones the
+    /// pipeline would reject.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - A reference to any type that implements the [`MoleculeGraph`]
+    ///   trait. The graph is treated as read-only.
+    ///
+    /// # Returns
+    ///
+    /// A [`MolecularFormula`] that formats in Hill order and reports the
+    /// average and monoisotopic masses, net charge and heavy-atom count. It
+    /// compares equal to a formula parsed from a string with the same
+    /// composition and charge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pauling::{molecular_formula, BondOrder, Element, MolecularFormula, Molecule};
+    ///
+    /// // Ethanol with implicit hydrogens.
+    /// let mut ethanol = Molecule::new();
+    /// let c1 = ethanol.add_atom(Element::C, 0);
+    /// let c2 = ethanol.add_atom(Element::C, 0);
+    /// let o = ethanol.add_atom(Element::O, 0);
+    /// ethanol.set_implicit_hydrogens(c1, 3).unwrap();
+    /// ethanol.set_implicit_hydrogens(c2, 2).unwrap();
+    /// ethanol.set_implicit_hydrogens(o, 1).unwrap();
+    /// ethanol.add_bond(c1, c2, BondOrder::Single).unwrap();
+    /// ethanol.add_bond(c2, o, BondOrder::Single).unwrap();
+    ///
+    /// let formula = molecular_formula(&ethanol);
+    /// assert_eq!(formula.to_string(), "C2H6O");
+    /// assert_eq!(formula.heavy_atom_count(), 3);
+    /// assert_eq!(formula.charge(), 0);
+    /// assert!((formula.average_mass() - 46.069).abs() < 1e-3);
+    /// assert!((formula.monoisotopic_mass() - 46.041865).abs() < 1e-6);
+    /// assert_eq!(formula, "CH3CH2OH".parse::<MolecularFormula>().unwrap());
+    /// ```
+    pub fn molecular_formula<G: MoleculeGraph>(graph: &G) -> MolecularFormula {
+        MolecularFormula::from_graph(graph)
+    }
+}
+
+mod perceive_impl {
+    use super::*;
+    use crate::graph::traits::MoleculeGraph;
+    use crate::perception::ChemicalPerception;
+
+    /// Runs the full perception pipeline and returns a [`PerceptionReport`].
+    ///
+    /// In addition to the resonance systems produced by
+    /// [`find_resonance_systems_with_options`], the report gives the
+    /// hybridization and VSEPR geometry of every atom and classifies every
+    /// SSSR ring and every fused ring system as aromatic, antiaromatic or
+    /// non-aromatic. When [`PerceptionOptions::metal_aware`] is set, it also
+    /// lists every metal centre with its ligands.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - A reference to any type that implements the [`MoleculeGraph`]
+    ///   trait. The graph is treated as read-only.
+    /// * `options` - Settings applied to the perception pipeline.
+    ///
+    /// # Returns
+    ///
+    /// A [`PerceptionReport`] describing the graph's atoms, resonance systems,
+    /// rings, fused ring systems, and metal centres.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PerceptionError`] under the same conditions as
+    /// [`find_resonance_systems`].
+    ///
+    /// # Examples
+    ///
+    /// Cyclobutadiene has four π electrons and is reported as antiaromatic.
+    ///
+    /// ```
+    /// use pauling::{
+    ///     perceive, BondOrder, Element, Molecule, PerceptionError, PerceptionOptions,
+    ///     RingAromaticity,
+    /// };
+    ///
+    /// let mut cyclobutadiene = Molecule::new();
+    /// let c: Vec<_> = (0..4).map(|_| cyclobutadiene.add_atom(Element::C, 0)).collect();
+    /// cyclobutadiene.add_bond(c[0], c[1], BondOrder::Double).unwrap();
+    /// cyclobutadiene.add_bond(c[1], c[2], BondOrder::Single).unwrap();
+    /// cyclobutadiene.add_bond(c[2], c[3], BondOrder::Double).unwrap();
+    /// cyclobutadiene.add_bond(c[3], c[0], BondOrder::Single).unwrap();
+    ///
+    /// let report = perceive(&cyclobutadiene, &PerceptionOptions::default())?;
+    /// assert_eq!(report.rings.len(), 1);
+    /// assert_eq!(report.rings[0].aromaticity, RingAromaticity::Antiaromatic);
+    /// assert_eq!(report.ring_systems[0].aromaticity, RingAromaticity::Antiaromatic);
+    /// # Ok::<(), PerceptionError>(())
+    /// ```
+    pub fn perceive<G: MoleculeGraph>(
+        graph: &G,
+        options: &PerceptionOptions,
+    ) -> Result<PerceptionReport, PerceptionError> {
+        let perception = ChemicalPerception::from_graph_with_options(graph, options)?;
+
+        Ok(PerceptionReport::from_perception(&perception))
+    }
+}
+
+mod standardise_impl {
+    use super::*;
+    use crate::graph::traits::MoleculeGraph;
+    use crate::perception::ChemicalPerception;
+
+    /// Rewrites nitro groups, N-oxides, sulfoxides and ylides into one
+    /// drawing convention.
+    ///
+    /// The same group can be drawn charge-separated (N⁺(=O)O⁻, S⁺–O⁻, P⁺–C⁻)
+    /// or as a double bond on a hypervalent centre (N(=O)=O, S=O, P=C), and
+    /// perception only recognises hypervalent resonance bridges in the latter.
+    /// Run this before perception so that both drawings give the same result.
+    /// Only the topology of `graph` is read; no aromaticity or Kekulé
+    /// structure is perceived.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - A reference to any type that implements the [`MoleculeGraph`]
+    ///   trait. The graph is treated as read-only.
+    /// * `form` - The drawing convention to rewrite groups into.
+    ///
+    /// # Returns
+    ///
+    /// A [`Standardisation`] holding the rewritten [`Molecule`], mappings to
+    /// the identifiers of `graph`, and an [`AppliedRule`] for each rewrite.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PerceptionError::InconsistentGraph`],
+    /// [`PerceptionError::DuplicateBond`] or [`PerceptionError::SelfLoop`] if
+    /// the graph topology is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pauling::traits::{AtomView, MoleculeGraph};
+    /// use pauling::{
+    ///     standardise, BondOrder, Element, Molecule, PerceptionError, StandardForm,
+    ///     StandardisationRule,
+    /// };
+    ///
+    /// // Nitromethane drawn with a pentavalent nitrogen.
+    /// let mut nitromethane = Molecule::new();
+    /// let c = nitromethane.add_atom(Element::C, 0);
+    /// nitromethane.set_implicit_hydrogens(c, 3).unwrap();
+    /// let n = nitromethane.add_atom(Element::N, 0);
+    /// nitromethane.add_bond(c, n, BondOrder::Single).unwrap();
+    /// for _ in 0..2 {
+    ///     let o = nitromethane.add_atom(Element::O, 0);
+    ///     nitromethane.add_bond(n, o, BondOrder::Double).unwrap();
+    /// }
+    ///
+    /// let standardised = standardise(&nitromethane, StandardForm::ChargeSeparated)?;
+    /// assert_eq!(standardised.applied.len(), 1);
+    /// assert_eq!(standardised.applied[0].rule, StandardisationRule::Nitro);
+    /// let charges: Vec<i8> = standardised
+    ///     .molecule
+    ///     .molecule
+    ///     .atoms()
+    ///     .map(|atom| atom.formal_charge())
+    ///     .collect();
+    /// assert_eq!(charges, [0, 1, -1, 0]);
+    /// # Ok::<(), PerceptionError>(())
+    /// ```
+    pub fn standardise<G: MoleculeGraph>(
+        graph: &G,
+        form: StandardForm,
+    ) -> Result<Standardisation, PerceptionError> {
+        let perception = ChemicalPerception::from_topology(graph)?;
+
+        Ok(standardiser::standardise(perception, form))
+    }
+}
+
+mod to_kekule_molecule_impl {
+    use super::*;
+    use crate::graph::traits::MoleculeGraph;
+    use crate::perception::ChemicalPerception;
+
+    /// Builds a copy of a molecular graph with every aromatic bond localised.
+    ///
+    /// The perception pipeline is run on `graph`, and each aromatic bond is
+    /// written with the single or double order assigned by Kekulization. All
+    /// other bonds, atoms, charges, and implicit hydrogen counts are copied
+    /// unchanged, except that hydrogens guessed for ambiguous pyrrole-type
+    /// nitrogens are included. Components left delocalised under
+    /// [`PerceptionOptions::lenient_kekulization`] keep their aromatic bonds.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - A reference to any type that implements the [`MoleculeGraph`]
+    ///   trait. The graph is treated as read-only.
+    /// * `options` - Settings applied to the perception pipeline.
+    ///
+    /// # Returns
+    ///
+    /// A [`ConvertedMolecule`] holding the Kekulé-form [`Molecule`] together
+    /// with mappings between its identifiers and those of `graph`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PerceptionError`] under the same conditions as
+    /// [`find_resonance_systems`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pauling::traits::{BondView, MoleculeGraph};
+    /// use pauling::{
+    ///     to_kekule_molecule, BondOrder, Element, Molecule, PerceptionError, PerceptionOptions,
+    /// };
+    ///
+    /// let mut benzene = Molecule::new();
+    /// let c: Vec<_> = (0..6).map(|_| benzene.add_atom(Element::C, 0)).collect();
+    /// for i in 0..6 {
+    ///     benzene.add_bond(c[i], c[(i + 1) % 6], BondOrder::Aromatic).unwrap();
+    ///     benzene.set_implicit_hydrogens(c[i], 1).unwrap();
+    /// }
+    ///
+    /// let kekule = to_kekule_molecule(&benzene, &PerceptionOptions::default())?;
+    /// let doubles = kekule
+    ///     .molecule
+    ///     .bonds()
+    ///     .filter(|bond| bond.order() == BondOrder::Double)
+    ///     .count();
+    /// assert_eq!(doubles, 3);
+    /// assert_eq!(kekule.original_atom_ids, c);
+    /// # Ok::<(), PerceptionError>(())
+    /// ```
+    pub fn to_kekule_molecule<G: MoleculeGraph>(
+        graph: &G,
+        options: &PerceptionOptions,
+    ) -> Result<ConvertedMolecule, PerceptionError> {
+        let perception = ChemicalPerception::from_graph_with_options(graph, options)?;
+
+        Ok(ConvertedMolecule::from_perception(&perception, |bond| {
+            if bond.is_aromatic {
+                bond.kekule_order.unwrap_or(bond.order)
+            } else {
+                bond.order
+            }
+        }))
+    }
+}
+
+mod to_aromatic_molecule_impl {
+    use super::*;
+    use crate::graph::traits::MoleculeGraph;
+    use crate::perception::ChemicalPerception;
+
+    /// Builds a copy of a molecular graph with every aromatic bond written as
+    /// [`BondOrder::Aromatic`].
+    ///
+    /// The perception pipeline is run on `graph`, and each bond flagged
+    /// aromatic (explicitly or by Hückel perception) is rewritten to
+    /// `BondOrder::Aromatic`. All other bonds are copied unchanged. Because
+    /// Kekulé-drawn and aromatic-drawn inputs of the same compound normalise to
+    /// the same bond orders, the result is suitable for comparing them.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - A reference to any type that implements the [`MoleculeGraph`]
+    ///   trait. The graph is treated as read-only.
+    /// * `options` - Settings applied to the perception pipeline; the
+    ///   aromaticity model decides which rings are rewritten.
+    ///
+    /// # Returns
+    ///
+    /// A [`ConvertedMolecule`] holding the aromatic-form [`Molecule`] together
+    /// with mappings between its identifiers and those of `graph`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PerceptionError`] under the same conditions as
+    /// [`find_resonance_systems`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pauling::traits::{BondView, MoleculeGraph};
+    /// use pauling::{
+    ///     to_aromatic_molecule, BondOrder, Element, Molecule, PerceptionError, PerceptionOptions,
+    /// };
+    ///
+    /// let mut benzene = Molecule::new();
+    /// let c: Vec<_> = (0..6).map(|_| benzene.add_atom(Element::C, 0)).collect();
+    /// for i in 0..6 {
+    ///     let order = if i % 2 == 0 { BondOrder::Double } else { BondOrder::Single };
+    ///     benzene.add_bond(c[i], c[(i + 1) % 6], order).unwrap();
+    /// }
+    ///
+    /// let aromatic = to_aromatic_molecule(&benzene, &PerceptionOptions::default())?;
+    /// assert!(aromatic
+    ///     .molecule
+    ///     .bonds()
+    ///     .all(|bond| bond.order() == BondOrder::Aromatic));
+    /// # Ok::<(), PerceptionError>(())
+    /// ```
+    pub fn to_aromatic_molecule<G: MoleculeGraph>(
+        graph: &G,
+        options: &PerceptionOptions,
+    ) -> Result<ConvertedMolecule, PerceptionError> {
+        let perception = ChemicalPerception::from_graph_with_options(graph, options)?;
+
+        Ok(ConvertedMolecule::from_perception(&perception, |bond| {
+            if bond.is_aromatic {
+                BondOrder::Aromatic
+            } else {
+                bond.order
+            }
+        }))
+    }
+}
+
+mod find_cycles_impl {
+    use super::*;
+    use crate::graph::traits::MoleculeGraph;
+    use crate::perception::ChemicalPerception;
+
+    /// Enumerates every simple cycle of a molecular graph up to a size bound.
+    ///
+    /// The perception pipeline works with the Smallest Set of Smallest Rings,
+    /// which is only a basis of the cycle space. Analyses such as macrocycle
+    /// detection or annulene checks need every cycle instead; this function
+    /// walks all simple cycles restricted to the ring bonds of the graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - A reference to any type that implements the [`MoleculeGraph`]
+    ///   trait. The graph is treated as read-only.
+    /// * `max_size` - Largest cycle, in atoms, that should be reported.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<Ring>` ordered by ring size. Each [`Ring`] lists its atoms both
+    /// sorted (`atom_ids`) and in traversal order (`path`).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PerceptionError`] if the input graph is structurally
+    /// inconsistent (e.g., contains dangling bonds or duplicate bonds).
+    ///
+    /// # Examples
+    ///
+    /// Naphthalene has two six-membered rings and a ten-membered envelope.
+    ///
+    /// ```
+    /// use pauling::{find_cycles, BondOrder, Element, Molecule, PerceptionError};
+    ///
+    /// let mut naphthalene = Molecule::new();
+    /// let c: Vec<_> = (0..10).map(|_| naphthalene.add_atom(Element::C, 0)).collect();
+    /// for (a, b) in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0),
+    ///                (5, 6), (6, 7), (7, 8), (8, 9), (9, 4)] {
+    ///     naphthalene.add_bond(c[a], c[b], BondOrder::Single).unwrap();
+    /// }
+    ///
+    /// let cycles = find_cycles(&naphthalene, 10)?;
+    /// let sizes: Vec<_> = cycles.iter().map(|ring| ring.size()).collect();
+    /// assert_eq!(sizes, vec![6, 6, 10]);
+    ///
+    /// // Only the two six-membered rings survive a tighter bound.
+    /// assert_eq!(find_cycles(&naphthalene, 8)?.len(), 2);
+    /// # Ok::<(), PerceptionError>(())
+    /// ```
+    pub fn find_cycles<G: MoleculeGraph>(
+        graph: &G,
+        max_size: usize,
+    ) -> Result<Vec<Ring>, PerceptionError> {
+        let perception = ChemicalPerception::from_topology(graph)?;
+
+        Ok(perception::find_cycles(&perception, max_size))
+    }
 }
@@ -31,6 +31,8 @@ pub struct Atom {
     id: AtomId,
     element: Element,
     formal_charge: i8,
+    implicit_hydrogens: u8,
+    radical_electrons: u8,
 }
 
 impl AtomView for Atom {
@@ -43,6 +45,12 @@ impl AtomView for Atom {
     fn formal_charge(&self) -> i8 {
         self.formal_charge
     }
+    fn implicit_hydrogens(&self) -> u8 {
+        self.implicit_hydrogens
+    }
+    fn radical_electrons(&self) -> u8 {
+        self.radical_electrons
+    }
 }
 
 /// Concrete bond data stored inside [`Molecule`].
@@ -99,11 +107,85 @@ impl Molecule {
             id,
             element,
             formal_charge,
+            implicit_hydrogens: 0,
+            radical_electrons: 0,
         });
         self.adjacency.push(Vec::new());
         id
     }
 
+    /// Sets the number of implicit hydrogens carried by an atom.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_id` - Identifier of the atom to update.
+    /// * `count` - Number of hydrogens not represented as explicit atoms.
+    ///
+    /// # Errors
+    ///
+    /// * [`MoleculeBuildError::AtomNotFound`] if the atom is missing.
+    pub fn set_implicit_hydrogens(
+        &mut self,
+        atom_id: AtomId,
+        count: u8,
+    ) -> Result<(), MoleculeBuildError> {
+        let max_id = self.atoms.len().saturating_sub(1);
+        let atom = self
+            .atoms
+            .get_mut(atom_id)
+            .ok_or(MoleculeBuildError::AtomNotFound(atom_id, max_id))?;
+        atom.implicit_hydrogens = count;
+        Ok(())
+    }
+
+    /// Sets the formal charge carried by an atom.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_id` - Identifier of the atom to update.
+    /// * `charge` - New formal charge, e.g. `1` for an ammonium nitrogen.
+    ///
+    /// # Errors
+    ///
+    /// * [`MoleculeBuildError::AtomNotFound`] if the atom is missing.
+    pub fn set_formal_charge(
+        &mut self,
+        atom_id: AtomId,
+        charge: i8,
+    ) -> Result<(), MoleculeBuildError> {
+        let max_id = self.atoms.len().saturating_sub(1);
+        let atom = self
+            .atoms
+            .get_mut(atom_id)
+            .ok_or(MoleculeBuildError::AtomNotFound(atom_id, max_id))?;
+        atom.formal_charge = charge;
+        Ok(())
+    }
+
+    /// Sets the number of unpaired electrons carried by an atom.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_id` - Identifier of the atom to update.
+    /// * `count` - Number of unpaired electrons, e.g. `1` for a radical centre.
+    ///
+    /// # Errors
+    ///
+    /// * [`MoleculeBuildError::AtomNotFound`] if the atom is missing.
+    pub fn set_radical_electrons(
+        &mut self,
+        atom_id: AtomId,
+        count: u8,
+    ) -> Result<(), MoleculeBuildError> {
+        let max_id = self.atoms.len().saturating_sub(1);
+        let atom = self
+            .atoms
+            .get_mut(atom_id)
+            .ok_or(MoleculeBuildError::AtomNotFound(atom_id, max_id))?;
+        atom.radical_electrons = count;
+        Ok(())
+    }
+
     /// Connects two atoms with a bond of the given order.
     ///
     /// # Arguments
@@ -335,4 +417,54 @@ mod tests {
         let bonds_of_hydrogen: Vec<_> = molecule.bonds_of_atom(hydrogen_id).collect();
         assert_eq!(bonds_of_hydrogen, vec![second_bond]);
     }
+
+    #[test]
+    fn set_implicit_hydrogens_updates_atom_and_rejects_missing_ids() {
+        let mut molecule = Molecule::new();
+        let nitrogen_id = molecule.add_atom(Element::N, 0);
+
+        assert_eq!(molecule.atom(nitrogen_id).unwrap().implicit_hydrogens(), 0);
+        molecule
+            .set_implicit_hydrogens(nitrogen_id, 1)
+            .expect("setting implicit hydrogens failed");
+        assert_eq!(molecule.atom(nitrogen_id).unwrap().implicit_hydrogens(), 1);
+
+        assert_eq!(
+            molecule.set_implicit_hydrogens(5, 1),
+            Err(MoleculeBuildError::AtomNotFound(5, 0))
+        );
+    }
+
+    #[test]
+    fn set_formal_charge_updates_atom_and_rejects_missing_ids() {
+        let mut molecule = Molecule::new();
+        let nitrogen_id = molecule.add_atom(Element::N, 0);
+
+        molecule
+            .set_formal_charge(nitrogen_id, 1)
+            .expect("setting formal charge failed");
+        assert_eq!(molecule.atom(nitrogen_id).unwrap().formal_charge(), 1);
+
+        assert_eq!(
+            molecule.set_formal_charge(2, -1),
+            Err(MoleculeBuildError::AtomNotFound(2, 0))
+        );
+    }
+
+    #[test]
+    fn set_radical_electrons_updates_atom_and_rejects_missing_ids() {
+        let mut molecule = Molecule::new();
+        let oxygen_id = molecule.add_atom(Element::O, 0);
+
+        assert_eq!(molecule.atom(oxygen_id).unwrap().radical_electrons(), 0);
+        molecule
+            .set_radical_electrons(oxygen_id, 1)
+            .expect("setting radical electrons failed");
+        assert_eq!(molecule.atom(oxygen_id).unwrap().radical_electrons(), 1);
+
+        assert_eq!(
+            molecule.set_radical_electrons(3, 1),
+            Err(MoleculeBuildError::AtomNotFound(3, 0))
+        );
+    }
 }
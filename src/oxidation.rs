@@ -0,0 +1,570 @@
+//! Oxidation states from electronegativity-based bond partitioning.
+//!
+//! Every bonding pair is credited to the more electronegative end of its bond.
+//! Pairs between atoms of equal electronegativity, which includes every
+//! homonuclear bond, are split evenly. Formal charges already split each pair
+//! evenly, so an atom's oxidation state is its formal charge plus one for
+//! every pair it loses to a more electronegative partner and minus one for
+//! every pair it gains from a less electronegative one.
+//!
+//! Delocalised systems can optionally be averaged over their resonance
+//! contributors, which gives the fractional states expected for the terminal
+//! carbons of an allyl cation or the ring carbons of a phenoxide.
+
+use crate::charge::shell_capacity;
+use crate::core::atom::{AtomId, Element};
+use crate::core::bond::BondOrder;
+use crate::perception::ChemicalPerception;
+use crate::resonance;
+use std::collections::HashMap;
+
+/// Most contributors averaged over for one delocalised system.
+const CONTRIBUTOR_LIMIT: usize = 1024;
+
+/// Upper bound on the search steps spent enumerating one system.
+const SEARCH_LIMIT: usize = 100_000;
+
+/// How oxidation states treat delocalised systems.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OxidationStateModel {
+    /// Partition the bonds as drawn, with aromatic rings in their assigned
+    /// Kekulé structure. Every state is an integer.
+    #[default]
+    Localised,
+    /// Average the states of each delocalised system over its resonance
+    /// contributors, weighted equally.
+    ///
+    /// Contributors keep the drawn number of π bonds and radicals, put at most
+    /// the drawn amount of charge on the system, and give every atom an octet,
+    /// or a sextet for elements with at most four valence electrons. Systems
+    /// whose drawn structure breaks these rules, or that have more than 1024
+    /// contributors, keep their localised states.
+    ResonanceAveraged,
+}
+
+/// Oxidation state of a single atom.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OxidationState {
+    /// The atom.
+    pub atom: AtomId,
+    /// Element of the atom.
+    pub element: Element,
+    /// Oxidation state; fractional only under
+    /// [`OxidationStateModel::ResonanceAveraged`].
+    pub value: f64,
+}
+
+/// Assigns an oxidation state to every atom, ordered by atom identifier.
+///
+/// Implicit hydrogens are partitioned like explicit ones. In metal-aware
+/// mode, detached metal–ligand bonds are treated as ionic: each ligand keeps
+/// the charge it took over, and the metal gains its opposite.
+pub(crate) fn assign(
+    perception: &ChemicalPerception,
+    model: OxidationStateModel,
+) -> Vec<OxidationState> {
+    let mut values: Vec<f64> = (0..perception.atoms.len())
+        .map(|atom_idx| f64::from(localised_state(perception, atom_idx)))
+        .collect();
+    add_ligand_charges(perception, &mut values);
+
+    if model == OxidationStateModel::ResonanceAveraged {
+        for (atoms, bonds) in merged_systems(perception) {
+            if let Some(shifts) = average_shifts(perception, &atoms, &bonds) {
+                for (atom_idx, shift) in atoms.into_iter().zip(shifts) {
+                    values[atom_idx] += shift;
+                }
+            }
+        }
+    }
+
+    let mut states: Vec<OxidationState> = perception
+        .atoms
+        .iter()
+        .zip(values)
+        .map(|(atom, value)| OxidationState {
+            atom: atom.id,
+            element: atom.element,
+            value,
+        })
+        .collect();
+    states.sort_unstable_by_key(|state| state.atom);
+    states
+}
+
+/// Returns `+1` when `partner` takes the bonding pairs from `atom`, `-1` when
+/// `atom` takes them, and `0` when they are split evenly.
+fn polarity(atom: Element, partner: Element) -> i16 {
+    if atom == partner {
+        return 0;
+    }
+    match (atom.electronegativity(), partner.electronegativity()) {
+        (Some(own), Some(other)) if other > own => 1,
+        (Some(own), Some(other)) if other < own => -1,
+        _ => 0,
+    }
+}
+
+/// Computes the oxidation state of an atom from its drawn or Kekulé bonds.
+fn localised_state(perception: &ChemicalPerception, atom_idx: usize) -> i16 {
+    let atom = &perception.atoms[atom_idx];
+    let mut state = i16::from(atom.effective_charge())
+        + i16::from(atom.implicit_hydrogens) * polarity(atom.element, Element::H);
+
+    for &(neighbor_idx, bond_id) in &perception.adjacency[atom_idx] {
+        let bond = &perception.bonds[perception.bond_id_to_index[&bond_id]];
+        let partner = perception.atoms[neighbor_idx].element;
+        let pull = polarity(atom.element, partner);
+        state += match bond.kekule_order.unwrap_or(bond.order) {
+            // The donor owns the pair and loses one electron per even split.
+            BondOrder::Dative if bond.start_atom_id == atom.id => pull + 1,
+            BondOrder::Dative => pull - 1,
+            order => i16::from(order.multiplicity()) * pull,
+        };
+    }
+    state
+}
+
+/// Credits each metal with the charge its ligands took over from detached
+/// metal–ligand bonds, shared equally among the metals a ligand atom binds.
+fn add_ligand_charges(perception: &ChemicalPerception, values: &mut [f64]) {
+    let mut metal_bonds: HashMap<usize, Vec<usize>> = HashMap::new();
+    for bond in perception.bonds.iter().filter(|bond| bond.is_metal_ligand) {
+        let start_idx = perception.atom_id_to_index[&bond.start_atom_id];
+        let end_idx = perception.atom_id_to_index[&bond.end_atom_id];
+        let (metal_idx, ligand_idx) = if perception.atoms[start_idx].element.is_metal() {
+            (start_idx, end_idx)
+        } else {
+            (end_idx, start_idx)
+        };
+        metal_bonds.entry(ligand_idx).or_default().push(metal_idx);
+    }
+
+    for (ligand_idx, metals) in metal_bonds {
+        let share = -f64::from(perception.atoms[ligand_idx].ligand_charge) / metals.len() as f64;
+        for metal_idx in metals {
+            values[metal_idx] += share;
+        }
+    }
+}
+
+/// Unites resonance systems that share atoms, which happens where π planes
+/// cross, and returns their atom and bond indices.
+fn merged_systems(perception: &ChemicalPerception) -> Vec<(Vec<usize>, Vec<usize>)> {
+    let mut merged: Vec<(Vec<usize>, Vec<usize>)> = Vec::new();
+    for system in resonance::find_systems(perception) {
+        let mut atoms: Vec<usize> = system
+            .atoms
+            .iter()
+            .map(|atom_id| perception.atom_id_to_index[atom_id])
+            .collect();
+        let mut bonds: Vec<usize> = system
+            .bonds
+            .iter()
+            .map(|bond_id| perception.bond_id_to_index[bond_id])
+            .collect();
+
+        merged.retain(|(other_atoms, other_bonds)| {
+            if other_atoms.iter().any(|atom_idx| atoms.contains(atom_idx)) {
+                atoms.extend_from_slice(other_atoms);
+                bonds.extend_from_slice(other_bonds);
+                false
+            } else {
+                true
+            }
+        });
+        atoms.sort_unstable();
+        atoms.dedup();
+        bonds.sort_unstable();
+        bonds.dedup();
+        merged.push((atoms, bonds));
+    }
+    merged
+}
+
+/// A delocalised system set up for contributor enumeration.
+struct Contributors<'a> {
+    perception: &'a ChemicalPerception,
+    /// Atom indices of the system.
+    atoms: &'a [usize],
+    /// Bond indices of the system, as `(bond, start, end)` positions in `atoms`.
+    bonds: Vec<(usize, usize, usize)>,
+    /// Valence of each atom outside the π bonds of the system.
+    sigma_valence: Vec<u8>,
+    /// Position in `bonds` after which each atom's π bonds are all chosen.
+    last_bond: Vec<usize>,
+    /// Net formal charge of the drawn system.
+    charge: i16,
+    /// Total magnitude of the drawn formal charges.
+    separation: i16,
+    /// π bonds chosen so far and for each atom.
+    pi: Vec<u8>,
+    atom_pi: Vec<u8>,
+    /// Charges an atom may take once its π bonds are chosen.
+    charges: Vec<Vec<i8>>,
+    found: Vec<(Vec<u8>, Vec<i8>)>,
+    steps: usize,
+}
+
+/// Averages the oxidation-state shift of each system atom over the system's
+/// resonance contributors, or returns `None` if the system is not averaged.
+fn average_shifts(
+    perception: &ChemicalPerception,
+    atoms: &[usize],
+    bonds: &[usize],
+) -> Option<Vec<f64>> {
+    let position: HashMap<usize, usize> = atoms
+        .iter()
+        .enumerate()
+        .map(|(position, &atom_idx)| (atom_idx, position))
+        .collect();
+
+    let mut drawn_pi = Vec::with_capacity(bonds.len());
+    let mut system_bonds = Vec::with_capacity(bonds.len());
+    let mut sigma_valence: Vec<u8> = Vec::with_capacity(atoms.len());
+    for &atom_idx in atoms {
+        let atom = &perception.atoms[atom_idx];
+        if atom.element.is_metal() || atom.element.valence_electrons().is_none() {
+            return None;
+        }
+        sigma_valence.push(atom.total_valence);
+    }
+    for &bond_idx in bonds {
+        let bond = &perception.bonds[bond_idx];
+        let pi = match bond.kekule_order.unwrap_or(bond.order) {
+            BondOrder::Single => 0,
+            BondOrder::Double => 1,
+            BondOrder::Triple => 2,
+            _ => return None,
+        };
+        let start = position[&perception.atom_id_to_index[&bond.start_atom_id]];
+        let end = position[&perception.atom_id_to_index[&bond.end_atom_id]];
+        sigma_valence[start] -= pi;
+        sigma_valence[end] -= pi;
+        drawn_pi.push(pi);
+        system_bonds.push((bond_idx, start, end));
+    }
+
+    let mut last_bond = vec![0; atoms.len()];
+    for (bond_pos, &(_, start, end)) in system_bonds.iter().enumerate() {
+        last_bond[start] = bond_pos;
+        last_bond[end] = bond_pos;
+    }
+
+    let drawn_charges: Vec<i8> = atoms
+        .iter()
+        .map(|&atom_idx| perception.atoms[atom_idx].formal_charge)
+        .collect();
+    let mut search = Contributors {
+        perception,
+        atoms,
+        bonds: system_bonds,
+        sigma_valence,
+        last_bond,
+        charge: drawn_charges.iter().map(|&charge| i16::from(charge)).sum(),
+        separation: drawn_charges
+            .iter()
+            .map(|&charge| i16::from(charge).abs())
+            .sum(),
+        pi: Vec::with_capacity(bonds.len()),
+        atom_pi: vec![0; atoms.len()],
+        charges: vec![Vec::new(); atoms.len()],
+        found: Vec::new(),
+        steps: 0,
+    };
+
+    // The drawn structure must itself be a valid contributor.
+    for (position, &atom_idx) in atoms.iter().enumerate() {
+        let valence = perception.atoms[atom_idx].total_valence;
+        if !search
+            .allowed_charges(position, valence)
+            .contains(&drawn_charges[position])
+        {
+            return None;
+        }
+    }
+
+    // Seed every atom at its σ valence; atoms with system bonds are revisited
+    // once their π bonds are chosen.
+    for position in 0..atoms.len() {
+        let valence = search.sigma_valence[position];
+        search.charges[position] = search.allowed_charges(position, valence);
+    }
+
+    let total_pi = drawn_pi.iter().map(|&pi| u16::from(pi)).sum();
+    if !search.extend(total_pi) || search.found.is_empty() {
+        return None;
+    }
+
+    let mut shifts = vec![0.0; atoms.len()];
+    for (pi, charges) in &search.found {
+        for position in 0..atoms.len() {
+            shifts[position] += f64::from(charges[position] - drawn_charges[position]);
+        }
+        for (bond_pos, &(_, start, end)) in search.bonds.iter().enumerate() {
+            let change = i16::from(pi[bond_pos]) - i16::from(drawn_pi[bond_pos]);
+            if change == 0 {
+                continue;
+            }
+            let start_element = perception.atoms[atoms[start]].element;
+            let end_element = perception.atoms[atoms[end]].element;
+            shifts[start] += f64::from(change * polarity(start_element, end_element));
+            shifts[end] += f64::from(change * polarity(end_element, start_element));
+        }
+    }
+    let count = search.found.len() as f64;
+    Some(shifts.into_iter().map(|shift| shift / count).collect())
+}
+
+impl Contributors<'_> {
+    /// Chooses the π order of the next bond, recording every complete
+    /// contributor. Returns `false` once a limit is exceeded.
+    fn extend(&mut self, remaining_pi: u16) -> bool {
+        self.steps += 1;
+        if self.steps > SEARCH_LIMIT || self.found.len() > CONTRIBUTOR_LIMIT {
+            return false;
+        }
+
+        let bond_pos = self.pi.len();
+        if bond_pos == self.bonds.len() {
+            if remaining_pi == 0 {
+                let mut charges = Vec::with_capacity(self.atoms.len());
+                return self.assign_charges(&mut charges, 0, 0);
+            }
+            return true;
+        }
+        let bonds_left = (self.bonds.len() - bond_pos) as u16;
+        if remaining_pi > 2 * bonds_left {
+            return true;
+        }
+
+        let (_, start, end) = self.bonds[bond_pos];
+        for pi in 0..=remaining_pi.min(2) as u8 {
+            self.pi.push(pi);
+            self.atom_pi[start] += pi;
+            self.atom_pi[end] += pi;
+
+            let mut complete = true;
+            for position in [start, end] {
+                if complete && self.last_bond[position] == bond_pos {
+                    let valence = self.sigma_valence[position] + self.atom_pi[position];
+                    self.charges[position] = self.allowed_charges(position, valence);
+                    complete = !self.charges[position].is_empty();
+                }
+            }
+            let within_limits = !complete || self.extend(remaining_pi - u16::from(pi));
+
+            self.atom_pi[start] -= pi;
+            self.atom_pi[end] -= pi;
+            self.pi.pop();
+            if !within_limits {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Picks a charge for each atom from `position` on so that the system
+    /// keeps its drawn net charge without more charge separation.
+    fn assign_charges(&mut self, charges: &mut Vec<i8>, charge: i16, separation: i16) -> bool {
+        let position = charges.len();
+        if position == self.atoms.len() {
+            if charge == self.charge {
+                self.found.push((self.pi.clone(), charges.clone()));
+            }
+            return self.found.len() <= CONTRIBUTOR_LIMIT;
+        }
+
+        for candidate in self.charges[position].clone() {
+            let separation = separation + i16::from(candidate).abs();
+            if separation > self.separation {
+                continue;
+            }
+            charges.push(candidate);
+            let within_limits =
+                self.assign_charges(charges, charge + i16::from(candidate), separation);
+            charges.pop();
+            if !within_limits {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Lists the charges from -1 to +1 that give the atom a closed shell at
+    /// the given valence, or a sextet for elements with at most four valence
+    /// electrons.
+    fn allowed_charges(&self, position: usize, valence: u8) -> Vec<i8> {
+        let atom = &self.perception.atoms[self.atoms[position]];
+        let Some(valence_electrons) = atom.element.valence_electrons() else {
+            return Vec::new();
+        };
+        let bonding = i16::from(valence);
+        let radicals = i16::from(atom.radical_electrons);
+        let full_shell = shell_capacity(atom.element).unwrap_or(8);
+        let smallest_shell = if valence_electrons <= 4 {
+            full_shell - 2
+        } else {
+            full_shell
+        };
+
+        let lowest = atom.formal_charge.min(-1);
+        let highest = atom.formal_charge.max(1);
+        (lowest..=highest)
+            .filter(|&charge| {
+                let non_bonding =
+                    i16::from(valence_electrons) - i16::from(charge) - bonding - radicals;
+                if non_bonding < 0 || non_bonding % 2 != 0 {
+                    return false;
+                }
+                let shell = 2 * bonding + non_bonding + radicals;
+                shell >= smallest_shell
+                    && shell_capacity(atom.element).is_none_or(|capacity| shell <= capacity)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::molecule::Molecule;
+    use crate::perception::PerceptionOptions;
+
+    fn states(molecule: &Molecule, model: OxidationStateModel) -> Vec<f64> {
+        let perception = ChemicalPerception::from_graph(molecule).expect("perception failed");
+        assign(&perception, model)
+            .into_iter()
+            .map(|state| state.value)
+            .collect()
+    }
+
+    fn add_carbon(molecule: &mut Molecule, hydrogens: u8) -> AtomId {
+        let carbon = molecule.add_atom(Element::C, 0);
+        molecule
+            .set_implicit_hydrogens(carbon, hydrogens)
+            .expect("failed to set hydrogens");
+        carbon
+    }
+
+    #[test]
+    fn bonds_are_partitioned_by_electronegativity() {
+        // Ethanol: CH3-CH2-OH.
+        let mut ethanol = Molecule::new();
+        let methyl = add_carbon(&mut ethanol, 3);
+        let methylene = add_carbon(&mut ethanol, 2);
+        let oxygen = ethanol.add_atom(Element::O, 0);
+        ethanol
+            .set_implicit_hydrogens(oxygen, 1)
+            .expect("failed to set hydrogens");
+        ethanol
+            .add_bond(methyl, methylene, BondOrder::Single)
+            .expect("failed to add C-C bond");
+        ethanol
+            .add_bond(methylene, oxygen, BondOrder::Single)
+            .expect("failed to add C-O bond");
+        assert_eq!(
+            states(&ethanol, OxidationStateModel::Localised),
+            vec![-3.0, -1.0, -2.0]
+        );
+
+        // Carbon dioxide and hydrogen peroxide.
+        let mut molecule = Molecule::new();
+        let carbon = molecule.add_atom(Element::C, 0);
+        for _ in 0..2 {
+            let oxygen = molecule.add_atom(Element::O, 0);
+            molecule
+                .add_bond(carbon, oxygen, BondOrder::Double)
+                .expect("failed to add C=O bond");
+        }
+        let peroxide: Vec<AtomId> = (0..2)
+            .map(|_| {
+                let oxygen = molecule.add_atom(Element::O, 0);
+                molecule
+                    .set_implicit_hydrogens(oxygen, 1)
+                    .expect("failed to set hydrogens");
+                oxygen
+            })
+            .collect();
+        molecule
+            .add_bond(peroxide[0], peroxide[1], BondOrder::Single)
+            .expect("failed to add O-O bond");
+        assert_eq!(
+            states(&molecule, OxidationStateModel::Localised),
+            vec![4.0, -2.0, -2.0, -1.0, -1.0]
+        );
+    }
+
+    #[test]
+    fn dative_pair_goes_to_the_more_electronegative_end() {
+        // H3N->BF3.
+        let mut adduct = Molecule::new();
+        let nitrogen = adduct.add_atom(Element::N, 0);
+        adduct
+            .set_implicit_hydrogens(nitrogen, 3)
+            .expect("failed to set hydrogens");
+        let boron = adduct.add_atom(Element::B, 0);
+        adduct
+            .add_bond(nitrogen, boron, BondOrder::Dative)
+            .expect("failed to add dative bond");
+        for _ in 0..3 {
+            let fluorine = adduct.add_atom(Element::F, 0);
+            adduct
+                .add_bond(boron, fluorine, BondOrder::Single)
+                .expect("failed to add B-F bond");
+        }
+        assert_eq!(
+            states(&adduct, OxidationStateModel::Localised),
+            vec![-3.0, 3.0, -1.0, -1.0, -1.0]
+        );
+    }
+
+    #[test]
+    fn resonance_averaging_spreads_states_over_contributors() {
+        // Acetaldehyde enolate drawn as CH2=CH-O-.
+        let mut enolate = Molecule::new();
+        let terminal = add_carbon(&mut enolate, 2);
+        let central = add_carbon(&mut enolate, 1);
+        let oxygen = enolate.add_atom(Element::O, -1);
+        enolate
+            .add_bond(terminal, central, BondOrder::Double)
+            .expect("failed to add C=C bond");
+        enolate
+            .add_bond(central, oxygen, BondOrder::Single)
+            .expect("failed to add C-O bond");
+
+        assert_eq!(
+            states(&enolate, OxidationStateModel::Localised),
+            vec![-2.0, 0.0, -2.0]
+        );
+        assert_eq!(
+            states(&enolate, OxidationStateModel::ResonanceAveraged),
+            vec![-2.5, 0.5, -2.0]
+        );
+    }
+
+    #[test]
+    fn metal_aware_mode_treats_ligand_bonds_as_ionic() {
+        // Dimethylzinc.
+        let mut molecule = Molecule::new();
+        let zinc = molecule.add_atom(Element::Zn, 0);
+        for _ in 0..2 {
+            let carbon = add_carbon(&mut molecule, 3);
+            molecule
+                .add_bond(zinc, carbon, BondOrder::Single)
+                .expect("failed to add Zn-C bond");
+        }
+
+        let options = PerceptionOptions {
+            metal_aware: true,
+            ..PerceptionOptions::default()
+        };
+        let perception = ChemicalPerception::from_graph_with_options(&molecule, &options)
+            .expect("perception failed");
+        let values: Vec<f64> = assign(&perception, OxidationStateModel::Localised)
+            .into_iter()
+            .map(|state| state.value)
+            .collect();
+        assert_eq!(values, vec![2.0, -4.0, -4.0]);
+    }
+}
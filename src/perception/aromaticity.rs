@@ -1,14 +1,184 @@
 //! Aromaticity perception driven by ring topology and electron counting rules.
 
-use crate::core::atom::Element;
-use crate::core::bond::BondOrder;
-use crate::perception::ChemicalPerception;
+use crate::core::atom::{AtomId, Element};
+use crate::core::bond::{BondId, BondOrder};
+use crate::errors::PerceptionWarning;
+use crate::perception::ring::RingSystem;
+use crate::perception::{ChemicalPerception, PerceptionOptions};
 use std::collections::{HashMap, HashSet};
 
+/// Largest number of SSSR rings combined when searching aromatic subsets.
+const MAX_FUSED_SUBSET_RINGS: usize = 6;
+/// Upper bound on the ring subsets enumerated for one fused component.
+const MAX_FUSED_SUBSETS: usize = 2048;
+
+/// Aromaticity conventions modelled on common cheminformatics toolkits.
+///
+/// Toolkits disagree about which rings are aromatic. Each model mirrors the
+/// ring sizes, ring combinations, and exocyclic bonds one toolkit is willing
+/// to consider, and overrides the rows of pauling's π-electron table where
+/// that toolkit counts differently: exocyclic double bonds to heteroatoms,
+/// polarised exocyclic alkenes, and carbon ions without a multiple bond. All
+/// models then apply Hückel's `4n + 2` rule, which brings perception close to
+/// data sets produced by each toolkit without reproducing its atom typing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AromaticityModel {
+    /// Fused systems and their ring subsets of any size, with the full
+    /// π-electron table, as in Daylight.
+    #[default]
+    Daylight,
+    /// Fused systems and their ring subsets with rings of up to 24 atoms, as
+    /// in the RDKit default model. Any exocyclic C=C to a non-ring carbon
+    /// breaks aromaticity, whatever its substituents.
+    Rdkit,
+    /// Only all-carbon rings without exocyclic multiple bonds whose π
+    /// electrons all come from ring double bonds, as in MDL; carbanions and
+    /// carbocations such as cyclopentadienide and tropylium are not aromatic.
+    Mdl,
+    /// Ring atoms carrying an exocyclic multiple bond to a heteroatom (as in
+    /// 2-pyridone) or a C=C to a non-ring carbon break aromaticity, as in
+    /// OpenEye.
+    OpenEye,
+    /// Individual five- and six-membered rings only, as in MMFF94. Ring
+    /// atoms with an exocyclic C=O, C=N or C=C and carbon ions without a
+    /// multiple bond break aromaticity, while charged heteroatoms such as
+    /// the pyrrolide nitrogen still donate their lone pair.
+    Mmff94,
+    /// Strict Hückel model: individual rings only, never fused perimeters.
+    Huckel,
+}
+
+/// Aromatic character of a ring or fused ring system.
+///
+/// Classification uses the same π-electron count as aromaticity perception:
+/// admissible rings with `4n + 2` electrons are aromatic, those with `4n`
+/// electrons (`n ≥ 1`) are antiaromatic, and everything else is non-aromatic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RingAromaticity {
+    /// The ring or system satisfies Hückel's `4n + 2` rule.
+    Aromatic,
+    /// A fully conjugated ring or system with `4n` π electrons, such as
+    /// cyclobutadiene or pentalene.
+    Antiaromatic,
+    /// The ring or system is not fully conjugated or has no cyclic π count
+    /// that fits either rule.
+    NonAromatic,
+}
+
+/// Ring-selection rules and π-electron overrides that distinguish the
+/// aromaticity models.
+#[derive(Clone, Copy, Debug)]
+struct ModelRules {
+    /// Whether fused systems and multi-ring subsets are evaluated, rather
+    /// than each SSSR ring in isolation.
+    fused_systems: bool,
+    /// Whether rings may contain atoms other than carbon.
+    heteroatoms: bool,
+    /// Whether a ring atom may carry an exocyclic multiple bond to a heteroatom.
+    exocyclic_heteroatom_bonds: bool,
+    /// Whether a ring atom may carry any exocyclic multiple bond.
+    exocyclic_multiple_bonds: bool,
+    /// Smallest ring, in atoms, that may be aromatic.
+    min_ring_size: usize,
+    /// Largest ring, in atoms, that may be aromatic.
+    max_ring_size: usize,
+    /// Whether multi-ring systems count only the electrons of their
+    /// peripheral circuit, ignoring internal bonds and atoms.
+    peripheral_circuit: bool,
+    /// π electrons of a ring atom with an exocyclic double bond to a non-ring
+    /// heteroatom (C=O, C=N, C=S), or `None` when such an atom breaks
+    /// aromaticity.
+    exocyclic_heteroatom_electrons: Option<u32>,
+    /// Whether an exocyclic C=C whose outer carbon carries a π donor or
+    /// acceptor counts two or zero electrons; otherwise every exocyclic C=C
+    /// to a non-ring carbon breaks aromaticity.
+    polarised_exocyclic_alkenes: bool,
+    /// Whether carbanions and carbocations without a multiple bond (C, Si,
+    /// Ge) contribute a lone pair or an empty p orbital.
+    carbon_ion_orbitals: bool,
+}
+
+impl ModelRules {
+    /// Builds the rules for the selected model and applies caller overrides.
+    fn from_options(options: &PerceptionOptions) -> Self {
+        let mut rules = options.aromaticity_model.rules();
+        if let Some(min_ring_size) = options.min_aromatic_ring_size {
+            rules.min_ring_size = min_ring_size;
+        }
+        if let Some(max_ring_size) = options.max_aromatic_ring_size {
+            rules.max_ring_size = max_ring_size;
+        }
+        rules.peripheral_circuit = options.peripheral_circuit;
+        rules
+    }
+}
+
+impl AromaticityModel {
+    /// Returns the ring-selection rules and π overrides of this model.
+    fn rules(self) -> ModelRules {
+        let base = ModelRules {
+            fused_systems: true,
+            heteroatoms: true,
+            exocyclic_heteroatom_bonds: true,
+            exocyclic_multiple_bonds: true,
+            min_ring_size: 3,
+            max_ring_size: usize::MAX,
+            peripheral_circuit: false,
+            exocyclic_heteroatom_electrons: Some(0),
+            polarised_exocyclic_alkenes: true,
+            carbon_ion_orbitals: true,
+        };
+
+        match self {
+            AromaticityModel::Rdkit => ModelRules {
+                max_ring_size: 24,
+                polarised_exocyclic_alkenes: false,
+                ..base
+            },
+            AromaticityModel::Daylight => base,
+            AromaticityModel::Mdl => ModelRules {
+                heteroatoms: false,
+                exocyclic_heteroatom_bonds: false,
+                exocyclic_multiple_bonds: false,
+                exocyclic_heteroatom_electrons: None,
+                polarised_exocyclic_alkenes: false,
+                carbon_ion_orbitals: false,
+                ..base
+            },
+            AromaticityModel::OpenEye => ModelRules {
+                exocyclic_heteroatom_bonds: false,
+                exocyclic_heteroatom_electrons: None,
+                polarised_exocyclic_alkenes: false,
+                ..base
+            },
+            AromaticityModel::Mmff94 => ModelRules {
+                fused_systems: false,
+                min_ring_size: 5,
+                max_ring_size: 6,
+                exocyclic_heteroatom_electrons: None,
+                polarised_exocyclic_alkenes: false,
+                carbon_ion_orbitals: false,
+                ..base
+            },
+            AromaticityModel::Huckel => ModelRules {
+                fused_systems: false,
+                ..base
+            },
+        }
+    }
+}
+
 /// Marks aromatic atoms and bonds using explicit annotations and Hückel's rule.
-pub fn perceive(perception: &mut ChemicalPerception) {
+///
+/// # Arguments
+///
+/// * `perception` - Perception snapshot with ring information populated.
+/// * `options` - Settings selecting the aromaticity model and ring-size policy.
+pub fn perceive(perception: &mut ChemicalPerception, options: &PerceptionOptions) {
+    let rules = ModelRules::from_options(options);
     apply_explicit_aromaticity(perception);
-    apply_topological_aromaticity(perception);
+    apply_topological_aromaticity(perception, &rules);
+    classify_rings(perception, &rules);
 }
 
 /// Phase 1: Handles bonds explicitly marked as `BondOrder::Aromatic`.
@@ -39,7 +209,7 @@ fn apply_explicit_aromaticity(perception: &mut ChemicalPerception) {
 }
 
 /// Phase 2: Detects aromaticity based on topology and Hückel's rule.
-fn apply_topological_aromaticity(perception: &mut ChemicalPerception) {
+fn apply_topological_aromaticity(perception: &mut ChemicalPerception, rules: &ModelRules) {
     if perception.ring_info.rings.is_empty() {
         return;
     }
@@ -47,79 +217,290 @@ fn apply_topological_aromaticity(perception: &mut ChemicalPerception) {
     // Step 2a: Group rings into fused systems.
     let fused_systems = find_fused_ring_systems(perception);
 
-    // Step 2b: Check each fused system for aromaticity.
+    // Step 2b: Find the aromatic rings or ring subsets of each fused system.
     for system_indices in fused_systems {
-        if is_system_aromatic(perception, &system_indices) {
-            // If aromatic, mark all atoms and bonds in the system.
-            let mut all_atom_indices = HashSet::new();
-            let mut all_bond_indices = HashSet::new();
-            for &ring_idx in &system_indices {
-                let ring = &perception.ring_info.rings[ring_idx];
-                for &atom_id in &ring.atom_ids {
-                    if let Some(&idx) = perception.atom_id_to_index.get(&atom_id) {
-                        all_atom_indices.insert(idx);
-                    }
-                }
-                for &bond_id in &ring.bond_ids {
-                    if let Some(&idx) = perception.bond_id_to_index.get(&bond_id) {
-                        all_bond_indices.insert(idx);
+        let search = find_aromatic_subsets(perception, &system_indices, rules);
+        for subset in &search.subsets {
+            mark_rings_aromatic(perception, subset);
+        }
+        for component in search.truncated {
+            let (atoms, bonds) = ring_set_members(perception, &component);
+            perception
+                .warnings
+                .push(PerceptionWarning::AromaticSubsetsTruncated { atoms, bonds });
+        }
+    }
+}
+
+/// Aromatic ring subsets found in one fused system.
+struct SubsetSearch {
+    /// Ring subsets that satisfy Hückel's rule.
+    subsets: Vec<Vec<usize>>,
+    /// Groups of rings whose subset enumeration hit [`MAX_FUSED_SUBSETS`].
+    truncated: Vec<Vec<usize>>,
+}
+
+/// Returns the ring subsets of a fused system that satisfy Hückel's rule.
+///
+/// The whole system is tried first. When it does not qualify (for example
+/// because a saturated ring is fused to a benzene ring, as in tetralin), each
+/// SSSR ring and each connected combination of aromatic-capable rings is
+/// evaluated on its own, so qualifying rings are flagged even when their
+/// fused neighbours are not. Models that do not consider fused systems only
+/// evaluate individual rings.
+fn find_aromatic_subsets(
+    perception: &ChemicalPerception,
+    system_indices: &[usize],
+    rules: &ModelRules,
+) -> SubsetSearch {
+    let mut search = SubsetSearch {
+        subsets: Vec::new(),
+        truncated: Vec::new(),
+    };
+    if rules.fused_systems && is_system_aromatic(perception, system_indices, rules) {
+        search.subsets.push(system_indices.to_vec());
+        return search;
+    }
+
+    let capable = aromatic_capable_rings(perception, system_indices, rules);
+
+    if !rules.fused_systems {
+        search.subsets = capable
+            .into_iter()
+            .map(|ring_idx| vec![ring_idx])
+            .filter(|subset| is_system_aromatic(perception, subset, rules))
+            .collect();
+        return search;
+    }
+
+    for component in fused_components(perception, &capable) {
+        // A qualifying component flags all of its rings at once. The complete
+        // system has already been rejected above.
+        if component.len() > 1
+            && component.len() < system_indices.len()
+            && is_system_aromatic(perception, &component, rules)
+        {
+            search.subsets.push(component);
+        } else if !search_connected_subsets(perception, &component, rules, &mut search.subsets) {
+            search.truncated.push(component);
+        }
+    }
+    search
+}
+
+/// Returns the rings of a fused system that can take part in an aromatic
+/// subset.
+///
+/// Besides the model's ring-level rules, every atom must have an admissible
+/// π contribution. Both are judged against the bonds of the whole system, and
+/// a smaller subset only turns more bonds exocyclic, so a ring rejected here
+/// cannot be aromatic in any subset.
+fn aromatic_capable_rings(
+    perception: &ChemicalPerception,
+    system_indices: &[usize],
+    rules: &ModelRules,
+) -> Vec<usize> {
+    let (_, system_bond_indices) = ring_set_indices(perception, system_indices);
+
+    system_indices
+        .iter()
+        .copied()
+        .filter(|&ring_idx| is_ring_admissible(perception, ring_idx, rules))
+        .filter(|&ring_idx| {
+            perception.ring_info.rings[ring_idx]
+                .atom_ids
+                .iter()
+                .all(|atom_id| {
+                    let atom_idx = perception.atom_id_to_index[atom_id];
+                    has_admissible_exocyclic_bonds(
+                        perception,
+                        atom_idx,
+                        &system_bond_indices,
+                        rules,
+                    ) && pi_electrons_for_atom(perception, atom_idx, &system_bond_indices, rules)
+                        .is_some()
+                })
+        })
+        .collect()
+}
+
+/// Checks whether a single ring may take part in an aromatic subset.
+fn is_ring_admissible(
+    perception: &ChemicalPerception,
+    ring_idx: usize,
+    rules: &ModelRules,
+) -> bool {
+    let ring = &perception.ring_info.rings[ring_idx];
+    if ring.size() < rules.min_ring_size || ring.size() > rules.max_ring_size {
+        return false;
+    }
+
+    ring.atom_ids.iter().all(|atom_id| {
+        let atom_idx = perception.atom_id_to_index[atom_id];
+        is_potential_sp2_hybrid(perception, atom_idx)
+            && (rules.heteroatoms || perception.atoms[atom_idx].element == Element::C)
+    })
+}
+
+/// Checks whether an atom's exocyclic multiple bonds are allowed by the model.
+///
+/// A bond is exocyclic when it leaves the evaluated set of ring bonds.
+fn has_admissible_exocyclic_bonds(
+    perception: &ChemicalPerception,
+    atom_idx: usize,
+    system_bond_indices: &HashSet<usize>,
+    rules: &ModelRules,
+) -> bool {
+    if rules.exocyclic_multiple_bonds && rules.exocyclic_heteroatom_bonds {
+        return true;
+    }
+
+    perception.adjacency[atom_idx]
+        .iter()
+        .all(|&(neighbor_idx, bond_id)| {
+            let bond_idx = perception.bond_id_to_index[&bond_id];
+            let bond = &perception.bonds[bond_idx];
+            if system_bond_indices.contains(&bond_idx)
+                || !matches!(bond.order, BondOrder::Double | BondOrder::Triple)
+            {
+                return true;
+            }
+            rules.exocyclic_multiple_bonds
+                && (rules.exocyclic_heteroatom_bonds
+                    || perception.atoms[neighbor_idx].element == Element::C)
+        })
+}
+
+/// Evaluates connected subsets of `ring_indices`, where two rings are
+/// connected when they share a bond, and appends those that satisfy Hückel's
+/// rule to `found`.
+///
+/// Subsets are grown one ring at a time, smallest first, and are capped at
+/// `MAX_FUSED_SUBSET_RINGS` rings and `MAX_FUSED_SUBSETS` subsets to keep
+/// large polycycles such as fullerenes tractable. A subset whose rings are all
+/// flagged already is skipped, and the search stops once every ring is
+/// flagged. Returns `false` when the cap cut the search short.
+fn search_connected_subsets(
+    perception: &ChemicalPerception,
+    ring_indices: &[usize],
+    rules: &ModelRules,
+    found: &mut Vec<Vec<usize>>,
+) -> bool {
+    let rings = &perception.ring_info.rings;
+    let shares_bond = |a: usize, b: usize| {
+        rings[a]
+            .bond_ids
+            .iter()
+            .any(|bond_id| rings[b].bond_ids.binary_search(bond_id).is_ok())
+    };
+
+    let mut covered: HashSet<usize> = HashSet::new();
+    let mut seen: HashSet<Vec<usize>> = HashSet::new();
+    let mut frontier: Vec<Vec<usize>> = ring_indices.iter().map(|&idx| vec![idx]).collect();
+
+    while !frontier.is_empty() {
+        if covered.len() == ring_indices.len() {
+            return true;
+        }
+        let mut next = Vec::new();
+        for subset in frontier {
+            if !seen.insert(subset.clone()) {
+                continue;
+            }
+            if seen.len() > MAX_FUSED_SUBSETS {
+                return false;
+            }
+            if subset.iter().any(|ring_idx| !covered.contains(ring_idx))
+                && is_system_aromatic(perception, &subset, rules)
+            {
+                covered.extend(subset.iter().copied());
+                found.push(subset.clone());
+            }
+            if subset.len() < MAX_FUSED_SUBSET_RINGS {
+                for &candidate in ring_indices {
+                    if subset.contains(&candidate)
+                        || !subset.iter().any(|&member| shares_bond(member, candidate))
+                    {
+                        continue;
                     }
+                    let mut grown = subset.clone();
+                    grown.push(candidate);
+                    grown.sort_unstable();
+                    next.push(grown);
                 }
             }
+        }
+        frontier = next;
+    }
+
+    true
+}
 
-            for idx in all_atom_indices {
-                perception.atoms[idx].is_aromatic = true;
+/// Flags every atom and bond of the given rings as aromatic.
+fn mark_rings_aromatic(perception: &mut ChemicalPerception, ring_indices: &[usize]) {
+    let mut all_atom_indices = HashSet::new();
+    let mut all_bond_indices = HashSet::new();
+    for &ring_idx in ring_indices {
+        let ring = &perception.ring_info.rings[ring_idx];
+        for &atom_id in &ring.atom_ids {
+            if let Some(&idx) = perception.atom_id_to_index.get(&atom_id) {
+                all_atom_indices.insert(idx);
             }
-            for idx in all_bond_indices {
-                perception.bonds[idx].is_aromatic = true;
+        }
+        for &bond_id in &ring.bond_ids {
+            if let Some(&idx) = perception.bond_id_to_index.get(&bond_id) {
+                all_bond_indices.insert(idx);
             }
         }
     }
+
+    for idx in all_atom_indices {
+        perception.atoms[idx].is_aromatic = true;
+    }
+    for idx in all_bond_indices {
+        perception.bonds[idx].is_aromatic = true;
+    }
 }
 
 /// Groups rings into connected components based on shared bonds.
 fn find_fused_ring_systems(perception: &ChemicalPerception) -> Vec<Vec<usize>> {
-    let num_rings = perception.ring_info.rings.len();
-    if num_rings == 0 {
-        return Vec::new();
-    }
+    let all_rings: Vec<usize> = (0..perception.ring_info.rings.len()).collect();
+    fused_components(perception, &all_rings)
+}
 
+/// Splits `ring_indices` into groups connected through shared bonds.
+///
+/// Each group is sorted ascending; groups are ordered by their first ring.
+fn fused_components(perception: &ChemicalPerception, ring_indices: &[usize]) -> Vec<Vec<usize>> {
     // Create a map from bond ID to the rings it belongs to.
-    let mut bond_to_rings = HashMap::new();
-    for (ring_idx, ring) in perception.ring_info.rings.iter().enumerate() {
-        for &bond_id in &ring.bond_ids {
-            bond_to_rings
-                .entry(bond_id)
-                .or_insert_with(Vec::new)
-                .push(ring_idx);
+    let mut bond_to_rings: HashMap<BondId, Vec<usize>> = HashMap::new();
+    for (position, &ring_idx) in ring_indices.iter().enumerate() {
+        for &bond_id in &perception.ring_info.rings[ring_idx].bond_ids {
+            bond_to_rings.entry(bond_id).or_default().push(position);
         }
     }
 
     // Build an adjacency graph of rings.
-    let mut ring_adjacency = vec![Vec::new(); num_rings];
+    let mut ring_adjacency = vec![Vec::new(); ring_indices.len()];
     for rings in bond_to_rings.values() {
-        if rings.len() > 1 {
-            for i in 0..rings.len() {
-                for j in (i + 1)..rings.len() {
-                    let r1 = rings[i];
-                    let r2 = rings[j];
-                    ring_adjacency[r1].push(r2);
-                    ring_adjacency[r2].push(r1);
-                }
+        for i in 0..rings.len() {
+            for j in (i + 1)..rings.len() {
+                ring_adjacency[rings[i]].push(rings[j]);
+                ring_adjacency[rings[j]].push(rings[i]);
             }
         }
     }
 
-    // Find connected components in the ring graph (these are the fused systems).
-    let mut visited = vec![false; num_rings];
+    // Find connected components in the ring graph.
+    let mut visited = vec![false; ring_indices.len()];
     let mut components = Vec::new();
-    for i in 0..num_rings {
+    for i in 0..ring_indices.len() {
         if !visited[i] {
             let mut component = Vec::new();
             let mut stack = vec![i];
             visited[i] = true;
             while let Some(current) = stack.pop() {
-                component.push(current);
+                component.push(ring_indices[current]);
                 for &neighbor in &ring_adjacency[current] {
                     if !visited[neighbor] {
                         visited[neighbor] = true;
@@ -127,108 +508,408 @@ fn find_fused_ring_systems(perception: &ChemicalPerception) -> Vec<Vec<usize>> {
                     }
                 }
             }
+            component.sort_unstable();
             components.push(component);
         }
     }
     components
 }
 
-/// Checks if a single fused ring system is aromatic using Hückel's rule.
-fn is_system_aromatic(perception: &ChemicalPerception, system_ring_indices: &[usize]) -> bool {
-    // Collect all unique atom and bond indices in the system.
-    let mut system_atom_indices = HashSet::new();
-    let mut system_bond_indices = HashSet::new();
-
-    for &ring_idx in system_ring_indices {
+/// Collects the atom and bond indices of a set of rings.
+fn ring_set_indices(
+    perception: &ChemicalPerception,
+    ring_indices: &[usize],
+) -> (HashSet<usize>, HashSet<usize>) {
+    let mut atom_indices = HashSet::new();
+    let mut bond_indices = HashSet::new();
+    for &ring_idx in ring_indices {
         let ring = &perception.ring_info.rings[ring_idx];
-        for &atom_id in &ring.atom_ids {
-            system_atom_indices.insert(perception.atom_id_to_index[&atom_id]);
+        for atom_id in &ring.atom_ids {
+            atom_indices.insert(perception.atom_id_to_index[atom_id]);
         }
-        for &bond_id in &ring.bond_ids {
-            system_bond_indices.insert(perception.bond_id_to_index[&bond_id]);
+        for bond_id in &ring.bond_ids {
+            bond_indices.insert(perception.bond_id_to_index[bond_id]);
         }
     }
+    (atom_indices, bond_indices)
+}
 
-    // An atom must be a potential sp2 hybrid to participate in an aromatic system.
-    for &atom_idx in &system_atom_indices {
-        if !is_potential_sp2_hybrid(perception, atom_idx) {
+/// Returns the atoms and bonds of a set of rings, sorted by identifier.
+fn ring_set_members(
+    perception: &ChemicalPerception,
+    ring_indices: &[usize],
+) -> (Vec<AtomId>, Vec<BondId>) {
+    let (atom_indices, bond_indices) = ring_set_indices(perception, ring_indices);
+    let mut atoms: Vec<AtomId> = atom_indices
+        .into_iter()
+        .map(|idx| perception.atoms[idx].id)
+        .collect();
+    let mut bonds: Vec<BondId> = bond_indices
+        .into_iter()
+        .map(|idx| perception.bonds[idx].id)
+        .collect();
+    atoms.sort_unstable();
+    bonds.sort_unstable();
+    (atoms, bonds)
+}
+
+/// Phase 3: Classifies every ring and fused system as aromatic, antiaromatic
+/// or non-aromatic.
+///
+/// A ring or system whose bonds have all been flagged aromatic is aromatic.
+/// Otherwise its π electrons are counted on their own: a `4n` count marks it
+/// antiaromatic when every member atom takes part in one cyclic conjugated
+/// path, and anything else (including `4n + 2` counts rejected by the
+/// aromaticity model) leaves it non-aromatic.
+fn classify_rings(perception: &mut ChemicalPerception, rules: &ModelRules) {
+    let aromaticity = (0..perception.ring_info.rings.len())
+        .map(|ring_idx| classify_ring_set(perception, &[ring_idx], rules))
+        .collect();
+
+    let systems = find_fused_ring_systems(perception)
+        .into_iter()
+        .map(|mut ring_indices| {
+            ring_indices.sort_unstable();
+            let aromaticity = classify_ring_set(perception, &ring_indices, rules);
+            RingSystem {
+                ring_indices,
+                aromaticity,
+            }
+        })
+        .collect();
+
+    perception.ring_info.aromaticity = aromaticity;
+    perception.ring_info.systems = systems;
+}
+
+/// Classifies a set of rings taken together.
+fn classify_ring_set(
+    perception: &ChemicalPerception,
+    ring_indices: &[usize],
+    rules: &ModelRules,
+) -> RingAromaticity {
+    let all_bonds_aromatic = ring_indices.iter().all(|&ring_idx| {
+        perception.ring_info.rings[ring_idx]
+            .bond_ids
+            .iter()
+            .all(|bond_id| perception.bonds[perception.bond_id_to_index[bond_id]].is_aromatic)
+    });
+    if all_bonds_aromatic {
+        return RingAromaticity::Aromatic;
+    }
+
+    match count_pi_electrons(perception, ring_indices, rules) {
+        Some(count)
+            if count > 0
+                && count % 4 == 0
+                && is_cyclically_conjugated(perception, ring_indices) =>
+        {
+            RingAromaticity::Antiaromatic
+        }
+        _ => RingAromaticity::NonAromatic,
+    }
+}
+
+/// Checks that no member atom of a set of rings is cross-conjugated.
+///
+/// An atom whose only π bond is an exocyclic double bond to a heteroatom
+/// (the carbonyl carbons of p-benzoquinone) contributes no electrons and
+/// interrupts the cyclic path, so a `4n` count over such a ring is not
+/// antiaromatic. Empty p orbitals (borole boron, carbocations) do not
+/// interrupt it.
+fn is_cyclically_conjugated(perception: &ChemicalPerception, ring_indices: &[usize]) -> bool {
+    let (system_atom_indices, system_bond_indices) = ring_set_indices(perception, ring_indices);
+
+    !system_atom_indices
+        .iter()
+        .any(|&atom_idx| is_cross_conjugated(perception, atom_idx, &system_bond_indices))
+}
+
+/// Checks whether an atom's only π bond is an exocyclic double bond to a
+/// heteroatom.
+fn is_cross_conjugated(
+    perception: &ChemicalPerception,
+    atom_idx: usize,
+    system_bond_indices: &HashSet<usize>,
+) -> bool {
+    let mut polar_exocyclic = false;
+    for &(neighbor_idx, bond_id) in &perception.adjacency[atom_idx] {
+        let bond_idx = perception.bond_id_to_index[&bond_id];
+        let bond = &perception.bonds[bond_idx];
+        if !matches!(bond.order, BondOrder::Double | BondOrder::Triple) {
+            continue;
+        }
+        if system_bond_indices.contains(&bond_idx) || bond.is_in_ring {
             return false;
         }
+        polar_exocyclic |= perception.atoms[neighbor_idx].element != Element::C;
+    }
+    polar_exocyclic
+}
+
+/// Checks if a set of rings, taken together, is aromatic using Hückel's rule.
+fn is_system_aromatic(
+    perception: &ChemicalPerception,
+    system_ring_indices: &[usize],
+    rules: &ModelRules,
+) -> bool {
+    // Apply Hückel's rule: 4n + 2 π electrons.
+    matches!(
+        count_pi_electrons(perception, system_ring_indices, rules),
+        Some(count) if count > 0 && (count - 2) % 4 == 0
+    )
+}
+
+/// Counts the π electrons of a set of rings taken together.
+///
+/// Returns `None` when a ring is not admissible under the model or an atom
+/// cannot take part in a cyclic π system. With the peripheral-circuit rule,
+/// bonds shared by two member rings and atoms off the perimeter (such as the
+/// central atoms of pyrene) are left out of the count.
+fn count_pi_electrons(
+    perception: &ChemicalPerception,
+    system_ring_indices: &[usize],
+    rules: &ModelRules,
+) -> Option<u32> {
+    if !system_ring_indices
+        .iter()
+        .all(|&ring_idx| is_ring_admissible(perception, ring_idx, rules))
+    {
+        return None;
+    }
+
+    // Collect all unique atom and bond indices in the system.
+    let (mut system_atom_indices, mut system_bond_indices) =
+        ring_set_indices(perception, system_ring_indices);
+
+    // Every atom must satisfy the model's exocyclic bond restrictions.
+    for &atom_idx in &system_atom_indices {
+        if !has_admissible_exocyclic_bonds(perception, atom_idx, &system_bond_indices, rules) {
+            return None;
+        }
+    }
+
+    if rules.peripheral_circuit && system_ring_indices.len() > 1 {
+        (system_atom_indices, system_bond_indices) =
+            peripheral_circuit(perception, system_ring_indices);
     }
 
     // Sum π electrons contributed by each atom in the system.
     let mut pi_electron_count = 0;
     for &atom_idx in &system_atom_indices {
-        pi_electron_count += pi_electrons_for_atom(perception, atom_idx, &system_bond_indices);
+        pi_electron_count +=
+            pi_electrons_for_atom(perception, atom_idx, &system_bond_indices, rules)?;
     }
+    Some(pi_electron_count)
+}
 
-    // Apply Hückel's rule: 4n + 2 π electrons.
-    pi_electron_count > 0 && (pi_electron_count - 2) % 4 == 0
+/// Returns the atom and bond indices on the perimeter of a set of rings.
+///
+/// A bond lies on the perimeter when exactly one member ring contains it;
+/// perimeter atoms are the endpoints of those bonds.
+fn peripheral_circuit(
+    perception: &ChemicalPerception,
+    ring_indices: &[usize],
+) -> (HashSet<usize>, HashSet<usize>) {
+    let mut ring_counts: HashMap<usize, usize> = HashMap::new();
+    for &ring_idx in ring_indices {
+        for bond_id in &perception.ring_info.rings[ring_idx].bond_ids {
+            *ring_counts
+                .entry(perception.bond_id_to_index[bond_id])
+                .or_insert(0) += 1;
+        }
+    }
+
+    let bond_indices: HashSet<usize> = ring_counts
+        .into_iter()
+        .filter(|&(_, count)| count == 1)
+        .map(|(bond_idx, _)| bond_idx)
+        .collect();
+    let atom_indices = bond_indices
+        .iter()
+        .flat_map(|&bond_idx| {
+            let bond = &perception.bonds[bond_idx];
+            [
+                perception.atom_id_to_index[&bond.start_atom_id],
+                perception.atom_id_to_index[&bond.end_atom_id],
+            ]
+        })
+        .collect();
+
+    (atom_indices, bond_indices)
 }
 
 /// A heuristic check if an atom can adopt sp2 hybridization for aromaticity.
-fn is_potential_sp2_hybrid(perception: &ChemicalPerception, atom_idx: usize) -> bool {
+pub fn is_potential_sp2_hybrid(perception: &ChemicalPerception, atom_idx: usize) -> bool {
     let atom = &perception.atoms[atom_idx];
     // This rule covers most common cases in organic chemistry.
     // Transition metals and hypervalent atoms are out of scope.
     atom.total_degree <= 3 && atom.element.is_common_conjugation_element()
 }
 
-/// Encapsulates the chemical rules for counting π electrons contributed by an atom.
+/// Returns the number of π electrons an atom contributes to a ring system, or
+/// `None` when the atom prevents the system from being aromatic.
+///
+/// The rules form a contribution table keyed on the atom's bonding situation:
+///
+/// | Situation | Contribution |
+/// |-----------|--------------|
+/// | Multiple bond inside the evaluated system | 1 |
+/// | Exocyclic multiple bond into another ring (fused neighbour) | 1 |
+/// | Exocyclic multiple bond to a heteroatom (C=O, C=N, C=S) | 0 |
+/// | Exocyclic C=C to a carbon carrying a π donor (6-aminofulvene) | 2 |
+/// | Exocyclic C=C to a carbon carrying a π acceptor (dicyanoheptafulvene) | 0 |
+/// | Any other exocyclic C=C to a non-ring carbon (fulvene) | `None` |
+/// | Neutral 3-connected N, P, As (pyrrole, phosphole) | 2 |
+/// | Negative 2-connected N, P, As | 2 |
+/// | Neutral 2-connected O, S, Se, Te (furan, thiophene, selenophene) | 2 |
+/// | Negative C, Si, Ge (cyclopentadienide, silole anion) | 2 |
+/// | Negative 3-connected B without a multiple bond | 1 |
+/// | Neutral B, positive C, Si, Ge (borole, tropylium) | 0 |
+/// | Anything else (tricoordinate N⁻, charged chalcogens) | `None` |
+///
+/// Positively charged heteroatoms that carry a ring double bond in the Kekulé
+/// form (pyridinium, pyrylium, thiopyrylium) fall under the first row.
+///
+/// The table above is the Daylight and Hückel one. Other models override
+/// the rows for exocyclic heteroatom bonds, polarised exocyclic alkenes and
+/// carbon ions through their [`ModelRules`], turning them into `None`.
 fn pi_electrons_for_atom(
     perception: &ChemicalPerception,
     atom_idx: usize,
     system_bond_indices: &HashSet<usize>,
-) -> u32 {
+    rules: &ModelRules,
+) -> Option<u32> {
     let atom = &perception.atoms[atom_idx];
 
-    // Case 1: Atom is part of a multiple bond within the ring system.
-    // It contributes 1 π electron. This is the most common case (e.g., C in benzene).
-    let is_in_multiple_bond_in_system =
-        perception.adjacency[atom_idx].iter().any(|&(_, bond_id)| {
-            if let Some(&bond_idx) = perception.bond_id_to_index.get(&bond_id) {
-                if system_bond_indices.contains(&bond_idx) {
-                    let bond = &perception.bonds[bond_idx];
-                    matches!(bond.order, BondOrder::Double | BondOrder::Triple)
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        });
+    let mut exocyclic_contribution = None;
+    for &(neighbor_idx, bond_id) in &perception.adjacency[atom_idx] {
+        let bond_idx = perception.bond_id_to_index[&bond_id];
+        let bond = &perception.bonds[bond_idx];
+        match bond.order {
+            BondOrder::Double | BondOrder::Triple => {}
+            _ => continue,
+        }
+
+        // Case 1: the multiple bond lies within the system (e.g. C in benzene).
+        if system_bond_indices.contains(&bond_idx) {
+            return Some(1);
+        }
 
-    if is_in_multiple_bond_in_system {
-        return 1;
+        // Case 2: an exocyclic multiple bond. Its polarity decides whether the
+        // ring atom's p orbital is effectively empty or unavailable.
+        let contribution = if bond.order == BondOrder::Triple {
+            None
+        } else if bond.is_in_ring {
+            // The π bond belongs to a fused neighbour and is shared with it.
+            Some(1)
+        } else if perception.atoms[neighbor_idx].element != Element::C {
+            // A polarised C=O, C=N or C=S bond withdraws the π electrons.
+            rules.exocyclic_heteroatom_electrons
+        } else if rules.polarised_exocyclic_alkenes {
+            exocyclic_alkene_contribution(perception, atom_idx, neighbor_idx)
+        } else {
+            None
+        };
+        exocyclic_contribution = Some(contribution);
+    }
+    if let Some(contribution) = exocyclic_contribution {
+        return contribution;
     }
 
-    // Case 2: Atom is NOT part of a multiple bond, contributes via lone pair or empty orbital.
-    // These rules are based on common patterns in heterocycles.
-    match atom.element {
-        // Pyrrole-like Nitrogen
-        Element::N if atom.total_degree == 3 => {
-            if atom.formal_charge == 1 { 0 } else { 2 } // Positively charged N (e.g., in protonated indole) has no lone pair to donate.
+    // Case 3: no multiple bonds; the atom contributes a lone pair or an empty
+    // p orbital depending on its element, charge and connectivity.
+    match (atom.element, atom.effective_charge(), atom.total_degree) {
+        // Pyrrole-, phosphole- and arsole-type atoms.
+        (Element::N | Element::P | Element::As, 0, 3) => Some(2),
+        (Element::N | Element::P | Element::As, -1, 2) => Some(2),
+        // Furan-, thiophene-, selenophene- and tellurophene-type atoms.
+        (Element::O | Element::S | Element::Se | Element::Te, 0, 2) => Some(2),
+        // Carbanions and their heavier analogues (cyclopentadienide, silole anion).
+        (Element::C | Element::Si | Element::Ge, -1, _) if rules.carbon_ion_orbitals => Some(2),
+        // B⁻ has four valence electrons; three go into σ bonds.
+        (Element::B, -1, 3) => Some(1),
+        // Empty p orbitals: borole boron, carbocations (tropylium) and silylium.
+        (Element::B, 0, _) => Some(0),
+        (Element::C | Element::Si | Element::Ge, 1, _) if rules.carbon_ion_orbitals => Some(0),
+        // No p orbital fits a cyclic π system.
+        _ => None,
+    }
+}
+
+/// Returns the contribution of a ring atom whose exocyclic double bond leads
+/// to a non-ring carbon, judged by the substituents on that carbon.
+///
+/// A π donor on the outer carbon pushes the π pair onto the ring atom, as in
+/// 6-(dimethylamino)fulvene; a π acceptor pulls it away, as in
+/// 8,8-dicyanoheptafulvene. Without a clear polarity (or with both kinds of
+/// substituent) the bond keeps the ring cross-conjugated.
+fn exocyclic_alkene_contribution(
+    perception: &ChemicalPerception,
+    ring_atom_idx: usize,
+    outer_idx: usize,
+) -> Option<u32> {
+    let mut donor = false;
+    let mut acceptor = false;
+    for &(substituent_idx, bond_id) in &perception.adjacency[outer_idx] {
+        let bond = &perception.bonds[perception.bond_id_to_index[&bond_id]];
+        if substituent_idx == ring_atom_idx || bond.order != BondOrder::Single {
+            continue;
         }
-        // Furan-like Oxygen or Thiophene-like Sulfur
-        Element::O | Element::S if atom.total_degree == 2 => 2,
-        // Carbocation or Carbanion in a ring
-        Element::C if atom.total_degree == 3 => match atom.formal_charge {
-            -1 => 2, // Carbanion (e.g., cyclopentadienyl anion)
-            1 => 0,  // Carbocation (e.g., tropylium cation)
-            _ => 0,
-        },
-        // Boron in a ring
-        Element::B if atom.total_degree == 3 => 0, // Has an empty p-orbital, contributes 0 electrons.
-        _ => 0,                                    // Default case: atom does not contribute.
+        if is_pi_donor(perception, substituent_idx) {
+            donor = true;
+        } else if is_pi_acceptor(perception, substituent_idx) {
+            acceptor = true;
+        }
+    }
+
+    match (donor, acceptor) {
+        (true, false) => Some(2),
+        (false, true) => Some(0),
+        _ => None,
     }
 }
 
+/// Checks whether a substituent offers a lone pair to an adjacent π bond
+/// (amino, hydroxy, alkoxy, thio or oxide groups).
+fn is_pi_donor(perception: &ChemicalPerception, atom_idx: usize) -> bool {
+    let atom = &perception.atoms[atom_idx];
+    matches!(atom.element, Element::N | Element::O | Element::S)
+        && atom.effective_charge() <= 0
+        && !has_multiple_bond(perception, atom_idx, |_| true)
+}
+
+/// Checks whether a substituent withdraws π density through a multiple bond
+/// to a heteroatom (carbonyl, nitrile or nitro groups).
+fn is_pi_acceptor(perception: &ChemicalPerception, atom_idx: usize) -> bool {
+    has_multiple_bond(perception, atom_idx, |partner_idx| {
+        perception.atoms[partner_idx].element != Element::C
+    })
+}
+
+/// Checks whether an atom has a double or triple bond to a partner accepted
+/// by `partner_filter`.
+fn has_multiple_bond(
+    perception: &ChemicalPerception,
+    atom_idx: usize,
+    partner_filter: impl Fn(usize) -> bool,
+) -> bool {
+    perception.adjacency[atom_idx]
+        .iter()
+        .any(|&(partner_idx, bond_id)| {
+            let bond = &perception.bonds[perception.bond_id_to_index[&bond_id]];
+            matches!(bond.order, BondOrder::Double | BondOrder::Triple)
+                && partner_filter(partner_idx)
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::atom::{AtomId, Element};
     use crate::core::bond::{BondId, BondOrder};
     use crate::molecule::Molecule;
+    use crate::perception::PerceptionOptions;
 
     fn add_atoms(molecule: &mut Molecule, specs: &[(Element, i8)]) -> Vec<AtomId> {
         specs
@@ -1054,4 +1735,1211 @@ mod tests {
         let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
         assert_aromatic_state(&perception, &ring_atoms, &ring_bonds, true);
     }
+
+    fn build_ring_molecule(
+        atom_specs: &[(Element, i8)],
+        bonds: &[(usize, usize, BondOrder)],
+    ) -> (Molecule, Vec<AtomId>, Vec<BondId>) {
+        let mut molecule = Molecule::new();
+        let atoms = add_atoms(&mut molecule, atom_specs);
+        let mut ring_bonds = Vec::new();
+        for &(start, end, order) in bonds {
+            add_ring_bond(&mut molecule, &atoms, start, end, order, &mut ring_bonds);
+        }
+        (molecule, atoms, ring_bonds)
+    }
+
+    fn attach_hydrogens(molecule: &mut Molecule, atoms: &[AtomId], count: usize) {
+        for &atom in atoms {
+            for _ in 0..count {
+                let hydrogen = molecule.add_atom(Element::H, 0);
+                molecule
+                    .add_bond(atom, hydrogen, BondOrder::Single)
+                    .expect("failed to attach hydrogen");
+            }
+        }
+    }
+
+    #[test]
+    fn tetralin_benzene_ring_is_aromatic_while_saturated_ring_is_not() {
+        // Atoms 0-5 form the benzene ring; 4, 5 and 6-9 form the saturated ring.
+        let (mut molecule, atoms, bonds) = build_ring_molecule(
+            &[(Element::C, 0); 10],
+            &[
+                (0, 1, BondOrder::Double),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 0, BondOrder::Single),
+                (5, 6, BondOrder::Single),
+                (6, 7, BondOrder::Single),
+                (7, 8, BondOrder::Single),
+                (8, 9, BondOrder::Single),
+                (9, 4, BondOrder::Single),
+            ],
+        );
+
+        attach_hydrogens(&mut molecule, &atoms[6..10], 2);
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        assert_aromatic_state(&perception, &atoms[..6], &bonds[..6], true);
+        assert_aromatic_state(&perception, &atoms[6..], &bonds[6..], false);
+    }
+
+    #[test]
+    fn indane_benzene_ring_is_aromatic_while_cyclopentane_ring_is_not() {
+        let (mut molecule, atoms, bonds) = build_ring_molecule(
+            &[(Element::C, 0); 9],
+            &[
+                (0, 1, BondOrder::Double),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 0, BondOrder::Single),
+                (5, 6, BondOrder::Single),
+                (6, 7, BondOrder::Single),
+                (7, 8, BondOrder::Single),
+                (8, 4, BondOrder::Single),
+            ],
+        );
+
+        attach_hydrogens(&mut molecule, &atoms[6..9], 2);
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        assert_aromatic_state(&perception, &atoms[..6], &bonds[..6], true);
+        assert_aromatic_state(&perception, &atoms[6..], &bonds[6..], false);
+    }
+
+    #[test]
+    fn chromane_benzene_ring_is_aromatic_while_pyran_ring_is_not() {
+        let (mut molecule, atoms, bonds) = build_ring_molecule(
+            &[
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::O, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+            ],
+            &[
+                (0, 1, BondOrder::Double),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 0, BondOrder::Single),
+                (5, 6, BondOrder::Single),
+                (6, 7, BondOrder::Single),
+                (7, 8, BondOrder::Single),
+                (8, 9, BondOrder::Single),
+                (9, 4, BondOrder::Single),
+            ],
+        );
+
+        attach_hydrogens(&mut molecule, &atoms[7..10], 2);
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        assert_aromatic_state(&perception, &atoms[..6], &bonds[..6], true);
+        assert_aromatic_state(&perception, &atoms[6..], &bonds[6..], false);
+    }
+
+    #[test]
+    fn benzocyclobutadiene_keeps_only_the_benzene_ring_aromatic() {
+        // The whole system (8 π electrons) fails Hückel's rule, but the
+        // six-membered ring on its own qualifies.
+        let (molecule, atoms, bonds) = build_ring_molecule(
+            &[(Element::C, 0); 8],
+            &[
+                (0, 1, BondOrder::Double),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 0, BondOrder::Single),
+                (5, 6, BondOrder::Single),
+                (6, 7, BondOrder::Double),
+                (7, 4, BondOrder::Single),
+            ],
+        );
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        assert_aromatic_state(&perception, &atoms[..6], &bonds[..6], true);
+        assert_aromatic_state(&perception, &atoms[6..], &bonds[6..], false);
+    }
+
+    #[test]
+    fn subset_search_skips_rings_that_cannot_be_aromatic() {
+        // Tetralin: the saturated ring never enters the subset search.
+        let (mut molecule, atoms, _) = build_ring_molecule(
+            &[(Element::C, 0); 10],
+            &[
+                (0, 1, BondOrder::Double),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 0, BondOrder::Single),
+                (5, 6, BondOrder::Single),
+                (6, 7, BondOrder::Single),
+                (7, 8, BondOrder::Single),
+                (8, 9, BondOrder::Single),
+                (9, 4, BondOrder::Single),
+            ],
+        );
+        attach_hydrogens(&mut molecule, &atoms[6..10], 2);
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        let rules = ModelRules::from_options(&PerceptionOptions::default());
+        let capable = aromatic_capable_rings(&perception, &[0, 1], &rules);
+        assert_eq!(capable.len(), 1);
+        assert!(
+            perception.ring_info.rings[capable[0]]
+                .atom_ids
+                .iter()
+                .all(|atom| atoms[..6].contains(atom))
+        );
+        assert!(perception.warnings.is_empty());
+    }
+
+    #[test]
+    fn truncated_subset_search_is_reported() {
+        // A sheet of fused six-membered rings of neutral boron: every ring is
+        // admissible, but with no π electrons no subset ever qualifies, so the
+        // search runs into the subset limit.
+        let (rows, columns) = (5, 12);
+        let mut molecule = Molecule::new();
+        let grid: Vec<Vec<AtomId>> = (0..rows)
+            .map(|_| {
+                (0..columns)
+                    .map(|_| molecule.add_atom(Element::B, 0))
+                    .collect()
+            })
+            .collect();
+        for row in 0..rows {
+            for column in 0..columns {
+                if column + 1 < columns {
+                    molecule
+                        .add_bond(grid[row][column], grid[row][column + 1], BondOrder::Single)
+                        .expect("failed to add bond");
+                }
+                if row + 1 < rows && (row + column) % 2 == 0 {
+                    molecule
+                        .add_bond(grid[row][column], grid[row + 1][column], BondOrder::Single)
+                        .expect("failed to add bond");
+                }
+            }
+        }
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        assert!(perception.atoms.iter().all(|atom| !atom.is_aromatic));
+        let truncated: Vec<_> = perception
+            .warnings
+            .iter()
+            .filter_map(|warning| match warning {
+                PerceptionWarning::AromaticSubsetsTruncated { atoms, .. } => Some(atoms),
+                _ => None,
+            })
+            .collect();
+        let mut ring_atoms: Vec<AtomId> = perception
+            .ring_info
+            .rings
+            .iter()
+            .flat_map(|ring| ring.atom_ids.iter().copied())
+            .collect();
+        ring_atoms.sort_unstable();
+        ring_atoms.dedup();
+        assert_eq!(truncated, [&ring_atoms]);
+    }
+
+    fn perceive_with_model(molecule: &Molecule, model: AromaticityModel) -> ChemicalPerception {
+        let options = PerceptionOptions {
+            aromaticity_model: model,
+            ..PerceptionOptions::default()
+        };
+        ChemicalPerception::from_graph_with_options(molecule, &options).expect("perception failed")
+    }
+
+    #[test]
+    fn mdl_model_rejects_heteroaromatic_rings() {
+        let (molecule, atoms, bonds) = build_ring_molecule(
+            &[
+                (Element::S, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+            ],
+            &[
+                (0, 1, BondOrder::Single),
+                (1, 2, BondOrder::Double),
+                (2, 3, BondOrder::Single),
+                (3, 4, BondOrder::Double),
+                (4, 0, BondOrder::Single),
+            ],
+        );
+
+        let rdkit = perceive_with_model(&molecule, AromaticityModel::Rdkit);
+        assert_aromatic_state(&rdkit, &atoms, &bonds, true);
+
+        let mdl = perceive_with_model(&molecule, AromaticityModel::Mdl);
+        assert_aromatic_state(&mdl, &atoms, &bonds, false);
+    }
+
+    #[test]
+    fn mmff94_model_limits_ring_size_while_huckel_does_not() {
+        let (mut molecule, atoms, bonds) = build_ring_molecule(
+            &[
+                (Element::C, 1),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+            ],
+            &[
+                (0, 1, BondOrder::Single),
+                (1, 2, BondOrder::Double),
+                (2, 3, BondOrder::Single),
+                (3, 4, BondOrder::Double),
+                (4, 5, BondOrder::Single),
+                (5, 6, BondOrder::Double),
+                (6, 0, BondOrder::Single),
+            ],
+        );
+        attach_hydrogens(&mut molecule, &atoms[..1], 1);
+
+        let mmff94 = perceive_with_model(&molecule, AromaticityModel::Mmff94);
+        assert_aromatic_state(&mmff94, &atoms, &bonds, false);
+
+        let huckel = perceive_with_model(&molecule, AromaticityModel::Huckel);
+        assert_aromatic_state(&huckel, &atoms, &bonds, true);
+    }
+
+    #[test]
+    fn openeye_model_rejects_exocyclic_carbonyl_in_pyridone() {
+        // 2-pyridone: N1(H)-C2(=O)-C3=C4-C5=C6-N1.
+        let (mut molecule, atoms, bonds) = build_ring_molecule(
+            &[
+                (Element::N, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+            ],
+            &[
+                (0, 1, BondOrder::Single),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 0, BondOrder::Single),
+            ],
+        );
+        let oxygen = molecule.add_atom(Element::O, 0);
+        molecule
+            .add_bond(atoms[1], oxygen, BondOrder::Double)
+            .expect("failed to add carbonyl bond");
+        attach_hydrogens(&mut molecule, &atoms[..1], 1);
+
+        let rdkit = perceive_with_model(&molecule, AromaticityModel::Rdkit);
+        assert_aromatic_state(&rdkit, &atoms, &bonds, true);
+
+        let openeye = perceive_with_model(&molecule, AromaticityModel::OpenEye);
+        assert_aromatic_state(&openeye, &atoms, &bonds, false);
+    }
+
+    #[test]
+    fn huckel_model_evaluates_fused_rings_individually() {
+        // Benzocyclobutadiene's benzene ring stays aromatic under every model
+        // that evaluates individual rings.
+        let (molecule, atoms, bonds) = build_ring_molecule(
+            &[(Element::C, 0); 8],
+            &[
+                (0, 1, BondOrder::Double),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 0, BondOrder::Single),
+                (5, 6, BondOrder::Single),
+                (6, 7, BondOrder::Double),
+                (7, 4, BondOrder::Single),
+            ],
+        );
+
+        let huckel = perceive_with_model(&molecule, AromaticityModel::Huckel);
+        assert_aromatic_state(&huckel, &atoms[..6], &bonds[..6], true);
+        assert_aromatic_state(&huckel, &atoms[6..], &bonds[6..], false);
+    }
+
+    fn five_membered_anion(element: Element) -> (Molecule, Vec<AtomId>, Vec<BondId>) {
+        let (mut molecule, atoms, bonds) = five_membered_heterocycle(element);
+        molecule
+            .set_formal_charge(atoms[0], -1)
+            .expect("failed to set charge");
+        (molecule, atoms, bonds)
+    }
+
+    fn two_pyridone() -> (Molecule, Vec<AtomId>, Vec<BondId>) {
+        // N1(H)-C2(=O)-C3=C4-C5=C6-N1.
+        let (mut molecule, atoms, bonds) = build_ring_molecule(
+            &[
+                (Element::N, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+            ],
+            &[
+                (0, 1, BondOrder::Single),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 0, BondOrder::Single),
+            ],
+        );
+        attach_exocyclic(&mut molecule, atoms[1], Element::O, BondOrder::Double);
+        attach_hydrogens(&mut molecule, &atoms[..1], 1);
+        (molecule, atoms, bonds)
+    }
+
+    #[test]
+    fn rdkit_model_ignores_exocyclic_alkene_polarity() {
+        // 8,8-dicyanoheptafulvene is a tropylium-like sextet for Daylight,
+        // but RDKit never lets an exocyclic C=C carbon into an aromatic ring.
+        let (mut molecule, atoms, bonds) = build_ring_molecule(
+            &[(Element::C, 0); 7],
+            &[
+                (0, 1, BondOrder::Single),
+                (1, 2, BondOrder::Double),
+                (2, 3, BondOrder::Single),
+                (3, 4, BondOrder::Double),
+                (4, 5, BondOrder::Single),
+                (5, 6, BondOrder::Double),
+                (6, 0, BondOrder::Single),
+            ],
+        );
+        let outer = attach_exocyclic(&mut molecule, atoms[0], Element::C, BondOrder::Double);
+        for _ in 0..2 {
+            let nitrile = attach_exocyclic(&mut molecule, outer, Element::C, BondOrder::Single);
+            attach_exocyclic(&mut molecule, nitrile, Element::N, BondOrder::Triple);
+        }
+
+        let daylight = perceive_with_model(&molecule, AromaticityModel::Daylight);
+        assert_aromatic_state(&daylight, &atoms, &bonds, true);
+        let rdkit = perceive_with_model(&molecule, AromaticityModel::Rdkit);
+        assert_aromatic_state(&rdkit, &atoms, &bonds, false);
+    }
+
+    #[test]
+    fn openeye_model_rejects_donor_substituted_fulvene() {
+        // 6-(dimethylamino)fulvene counts as a cyclopentadienide ring only
+        // under the Daylight and Hückel tables.
+        let (mut molecule, atoms, bonds) = five_membered_heterocycle(Element::C);
+        let outer = attach_exocyclic(&mut molecule, atoms[0], Element::C, BondOrder::Double);
+        let nitrogen = attach_exocyclic(&mut molecule, outer, Element::N, BondOrder::Single);
+        attach_exocyclic(&mut molecule, nitrogen, Element::C, BondOrder::Single);
+        attach_exocyclic(&mut molecule, nitrogen, Element::C, BondOrder::Single);
+        attach_hydrogens(&mut molecule, &[outer], 1);
+
+        for model in [AromaticityModel::Daylight, AromaticityModel::Huckel] {
+            let perception = perceive_with_model(&molecule, model);
+            assert_aromatic_state(&perception, &atoms, &bonds, true);
+        }
+        let openeye = perceive_with_model(&molecule, AromaticityModel::OpenEye);
+        assert_aromatic_state(&openeye, &atoms, &bonds, false);
+    }
+
+    #[test]
+    fn mdl_model_requires_every_electron_from_a_ring_double_bond() {
+        let (cyclopentadienide, atoms, bonds) = five_membered_anion(Element::C);
+
+        let rdkit = perceive_with_model(&cyclopentadienide, AromaticityModel::Rdkit);
+        assert_aromatic_state(&rdkit, &atoms, &bonds, true);
+        let mdl = perceive_with_model(&cyclopentadienide, AromaticityModel::Mdl);
+        assert_aromatic_state(&mdl, &atoms, &bonds, false);
+    }
+
+    #[test]
+    fn mmff94_model_counts_charged_heteroatoms_but_not_carbon_ions_or_carbonyls() {
+        let (pyrrolide, atoms, bonds) = five_membered_anion(Element::N);
+        let mmff94 = perceive_with_model(&pyrrolide, AromaticityModel::Mmff94);
+        assert_aromatic_state(&mmff94, &atoms, &bonds, true);
+
+        let (cyclopentadienide, atoms, bonds) = five_membered_anion(Element::C);
+        let (pyridone, pyridone_atoms, pyridone_bonds) = two_pyridone();
+        for (molecule, atoms, bonds) in [
+            (&cyclopentadienide, &atoms, &bonds),
+            (&pyridone, &pyridone_atoms, &pyridone_bonds),
+        ] {
+            let daylight = perceive_with_model(molecule, AromaticityModel::Daylight);
+            assert_aromatic_state(&daylight, atoms, bonds, true);
+            let mmff94 = perceive_with_model(molecule, AromaticityModel::Mmff94);
+            assert_aromatic_state(&mmff94, atoms, bonds, false);
+        }
+    }
+
+    fn attach_exocyclic(
+        molecule: &mut Molecule,
+        atom: AtomId,
+        element: Element,
+        order: BondOrder,
+    ) -> AtomId {
+        let partner = molecule.add_atom(element, 0);
+        molecule
+            .add_bond(atom, partner, order)
+            .expect("failed to attach exocyclic atom");
+        partner
+    }
+
+    fn five_membered_heterocycle(heteroatom: Element) -> (Molecule, Vec<AtomId>, Vec<BondId>) {
+        build_ring_molecule(
+            &[
+                (heteroatom, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+            ],
+            &[
+                (0, 1, BondOrder::Single),
+                (1, 2, BondOrder::Double),
+                (2, 3, BondOrder::Single),
+                (3, 4, BondOrder::Double),
+                (4, 0, BondOrder::Single),
+            ],
+        )
+    }
+
+    #[test]
+    fn para_benzoquinone_is_non_aromatic() {
+        let (mut molecule, atoms, bonds) = build_ring_molecule(
+            &[(Element::C, 0); 6],
+            &[
+                (0, 1, BondOrder::Single),
+                (1, 2, BondOrder::Double),
+                (2, 3, BondOrder::Single),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 0, BondOrder::Single),
+            ],
+        );
+        attach_exocyclic(&mut molecule, atoms[0], Element::O, BondOrder::Double);
+        attach_exocyclic(&mut molecule, atoms[3], Element::O, BondOrder::Double);
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        assert_aromatic_state(&perception, &atoms, &bonds, false);
+        // Four π electrons, but the carbonyl carbons break the cyclic path.
+        assert_eq!(
+            ring_aromaticity(&perception, &atoms),
+            RingAromaticity::NonAromatic
+        );
+        assert_eq!(
+            perception.ring_info.systems[0].aromaticity,
+            RingAromaticity::NonAromatic
+        );
+    }
+
+    #[test]
+    fn anthraquinone_keeps_only_outer_rings_aromatic() {
+        // Outer rings 0-5 and 7, 8, 10-13; the central ring 4-9 holds the
+        // carbonyl carbons 6 and 9.
+        let (mut molecule, atoms, bonds) = build_ring_molecule(
+            &[(Element::C, 0); 14],
+            &[
+                (0, 1, BondOrder::Double),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 0, BondOrder::Single),
+                (5, 6, BondOrder::Single),
+                (6, 7, BondOrder::Single),
+                (7, 8, BondOrder::Double),
+                (8, 9, BondOrder::Single),
+                (9, 4, BondOrder::Single),
+                (7, 10, BondOrder::Single),
+                (10, 11, BondOrder::Double),
+                (11, 12, BondOrder::Single),
+                (12, 13, BondOrder::Double),
+                (13, 8, BondOrder::Single),
+            ],
+        );
+        attach_exocyclic(&mut molecule, atoms[6], Element::O, BondOrder::Double);
+        attach_exocyclic(&mut molecule, atoms[9], Element::O, BondOrder::Double);
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        assert_aromatic_state(&perception, &atoms[..6], &bonds[..6], true);
+        assert_aromatic_state(&perception, &atoms[10..], &bonds[11..], true);
+        assert_aromatic_state(&perception, &[atoms[6], atoms[9]], &bonds[6..8], false);
+        assert_eq!(
+            ring_aromaticity(&perception, &atoms[..6]),
+            RingAromaticity::Aromatic
+        );
+        assert_eq!(
+            ring_aromaticity(&perception, &atoms[4..10]),
+            RingAromaticity::NonAromatic
+        );
+        assert_eq!(
+            ring_aromaticity(
+                &perception,
+                &[
+                    atoms[7], atoms[8], atoms[10], atoms[11], atoms[12], atoms[13]
+                ]
+            ),
+            RingAromaticity::Aromatic
+        );
+        assert_eq!(perception.ring_info.systems.len(), 1);
+        assert_eq!(
+            perception.ring_info.systems[0].aromaticity,
+            RingAromaticity::NonAromatic
+        );
+    }
+
+    fn ring_contribution(
+        perception: &ChemicalPerception,
+        bonds: &[BondId],
+        atom: AtomId,
+    ) -> Option<u32> {
+        let system: HashSet<usize> = bonds
+            .iter()
+            .map(|bond_id| perception.bond_id_to_index[bond_id])
+            .collect();
+        pi_electrons_for_atom(
+            perception,
+            perception.atom_id_to_index[&atom],
+            &system,
+            &AromaticityModel::default().rules(),
+        )
+    }
+
+    #[test]
+    fn pyridones_and_tropone_are_aromatic_with_a_zero_electron_carbonyl_carbon() {
+        let pyridone_specs = [
+            (Element::N, 0),
+            (Element::C, 0),
+            (Element::C, 0),
+            (Element::C, 0),
+            (Element::C, 0),
+            (Element::C, 0),
+        ];
+        // 2-pyridone: N1(H)-C2(=O)-C3=C4-C5=C6-N1.
+        let (mut two_pyridone, two_atoms, two_bonds) = build_ring_molecule(
+            &pyridone_specs,
+            &[
+                (0, 1, BondOrder::Single),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 0, BondOrder::Single),
+            ],
+        );
+        attach_exocyclic(
+            &mut two_pyridone,
+            two_atoms[1],
+            Element::O,
+            BondOrder::Double,
+        );
+        attach_hydrogens(&mut two_pyridone, &two_atoms[..1], 1);
+
+        // 4-pyridone: N1(H)-C2=C3-C4(=O)-C5=C6-N1.
+        let (mut four_pyridone, four_atoms, four_bonds) = build_ring_molecule(
+            &pyridone_specs,
+            &[
+                (0, 1, BondOrder::Single),
+                (1, 2, BondOrder::Double),
+                (2, 3, BondOrder::Single),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 0, BondOrder::Single),
+            ],
+        );
+        attach_exocyclic(
+            &mut four_pyridone,
+            four_atoms[3],
+            Element::O,
+            BondOrder::Double,
+        );
+        attach_hydrogens(&mut four_pyridone, &four_atoms[..1], 1);
+
+        let (mut tropone, tropone_atoms, tropone_bonds) = build_ring_molecule(
+            &[(Element::C, 0); 7],
+            &[
+                (0, 1, BondOrder::Single),
+                (1, 2, BondOrder::Double),
+                (2, 3, BondOrder::Single),
+                (3, 4, BondOrder::Double),
+                (4, 5, BondOrder::Single),
+                (5, 6, BondOrder::Double),
+                (6, 0, BondOrder::Single),
+            ],
+        );
+        attach_exocyclic(
+            &mut tropone,
+            tropone_atoms[0],
+            Element::O,
+            BondOrder::Double,
+        );
+
+        for (molecule, atoms, bonds, carbonyl) in [
+            (&two_pyridone, &two_atoms, &two_bonds, two_atoms[1]),
+            (&four_pyridone, &four_atoms, &four_bonds, four_atoms[3]),
+            (&tropone, &tropone_atoms, &tropone_bonds, tropone_atoms[0]),
+        ] {
+            let perception = ChemicalPerception::from_graph(molecule).expect("perception failed");
+            assert_eq!(ring_contribution(&perception, bonds, carbonyl), Some(0));
+            assert_aromatic_state(&perception, atoms, bonds, true);
+            assert_eq!(
+                ring_aromaticity(&perception, atoms),
+                RingAromaticity::Aromatic
+            );
+        }
+    }
+
+    #[test]
+    fn fulvene_exocyclic_carbon_double_bond_breaks_aromaticity() {
+        let (mut molecule, atoms, bonds) = build_ring_molecule(
+            &[(Element::C, 0); 5],
+            &[
+                (0, 1, BondOrder::Single),
+                (1, 2, BondOrder::Double),
+                (2, 3, BondOrder::Single),
+                (3, 4, BondOrder::Double),
+                (4, 0, BondOrder::Single),
+            ],
+        );
+        attach_exocyclic(&mut molecule, atoms[0], Element::C, BondOrder::Double);
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        assert_aromatic_state(&perception, &atoms, &bonds, false);
+    }
+
+    #[test]
+    fn exocyclic_alkene_polarity_decides_the_ring_contribution() {
+        // 6-(dimethylamino)fulvene: the donor pushes the π pair onto C1,
+        // completing a cyclopentadienide sextet.
+        let (mut aminofulvene, atoms, bonds) = build_ring_molecule(
+            &[(Element::C, 0); 5],
+            &[
+                (0, 1, BondOrder::Single),
+                (1, 2, BondOrder::Double),
+                (2, 3, BondOrder::Single),
+                (3, 4, BondOrder::Double),
+                (4, 0, BondOrder::Single),
+            ],
+        );
+        let outer = attach_exocyclic(&mut aminofulvene, atoms[0], Element::C, BondOrder::Double);
+        let nitrogen = attach_exocyclic(&mut aminofulvene, outer, Element::N, BondOrder::Single);
+        attach_exocyclic(&mut aminofulvene, nitrogen, Element::C, BondOrder::Single);
+        attach_exocyclic(&mut aminofulvene, nitrogen, Element::C, BondOrder::Single);
+        attach_hydrogens(&mut aminofulvene, &[outer], 1);
+
+        let perception = ChemicalPerception::from_graph(&aminofulvene).expect("perception failed");
+        assert_eq!(ring_contribution(&perception, &bonds, atoms[0]), Some(2));
+        assert_aromatic_state(&perception, &atoms, &bonds, true);
+
+        // 8,8-dicyanoheptafulvene: the acceptors leave C1 with an empty p
+        // orbital, as in tropylium.
+        let (mut heptafulvene, atoms, bonds) = build_ring_molecule(
+            &[(Element::C, 0); 7],
+            &[
+                (0, 1, BondOrder::Single),
+                (1, 2, BondOrder::Double),
+                (2, 3, BondOrder::Single),
+                (3, 4, BondOrder::Double),
+                (4, 5, BondOrder::Single),
+                (5, 6, BondOrder::Double),
+                (6, 0, BondOrder::Single),
+            ],
+        );
+        let outer = attach_exocyclic(&mut heptafulvene, atoms[0], Element::C, BondOrder::Double);
+        for _ in 0..2 {
+            let nitrile = attach_exocyclic(&mut heptafulvene, outer, Element::C, BondOrder::Single);
+            attach_exocyclic(&mut heptafulvene, nitrile, Element::N, BondOrder::Triple);
+        }
+
+        let perception = ChemicalPerception::from_graph(&heptafulvene).expect("perception failed");
+        assert_eq!(ring_contribution(&perception, &bonds, atoms[0]), Some(0));
+        assert_aromatic_state(&perception, &atoms, &bonds, true);
+    }
+
+    #[test]
+    fn atoms_outside_the_contribution_table_block_aromaticity() {
+        // A tricoordinate N⁻ has no fitting p orbital, so the would-be
+        // pyrrolide sextet is rejected rather than counted as four electrons.
+        let (mut molecule, atoms, bonds) = build_ring_molecule(
+            &[
+                (Element::N, -1),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+            ],
+            &[
+                (0, 1, BondOrder::Single),
+                (1, 2, BondOrder::Double),
+                (2, 3, BondOrder::Single),
+                (3, 4, BondOrder::Double),
+                (4, 0, BondOrder::Single),
+            ],
+        );
+        attach_hydrogens(&mut molecule, &atoms[..1], 1);
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        assert_eq!(ring_contribution(&perception, &bonds, atoms[0]), None);
+        assert_aromatic_state(&perception, &atoms, &bonds, false);
+        assert_eq!(
+            ring_aromaticity(&perception, &atoms),
+            RingAromaticity::NonAromatic
+        );
+    }
+
+    #[test]
+    fn chalcogenophenes_are_aromatic() {
+        for element in [Element::Se, Element::Te] {
+            let (molecule, atoms, bonds) = five_membered_heterocycle(element);
+            let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+            assert_aromatic_state(&perception, &atoms, &bonds, true);
+        }
+    }
+
+    #[test]
+    fn phosphole_is_aromatic_and_borole_is_not() {
+        let (mut phosphole, atoms, bonds) = five_membered_heterocycle(Element::P);
+        attach_hydrogens(&mut phosphole, &atoms[..1], 1);
+        let perception = ChemicalPerception::from_graph(&phosphole).expect("perception failed");
+        assert_aromatic_state(&perception, &atoms, &bonds, true);
+
+        let (mut borole, atoms, bonds) = five_membered_heterocycle(Element::B);
+        attach_hydrogens(&mut borole, &atoms[..1], 1);
+        let perception = ChemicalPerception::from_graph(&borole).expect("perception failed");
+        assert_aromatic_state(&perception, &atoms, &bonds, false);
+    }
+
+    #[test]
+    fn tricoordinate_borate_contributes_one_electron() {
+        // 1-H-borole anion: four electrons from the C=C bonds and one from B⁻.
+        let (mut borole, atoms, bonds) = build_ring_molecule(
+            &[
+                (Element::B, -1),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+            ],
+            &[
+                (0, 1, BondOrder::Single),
+                (1, 2, BondOrder::Double),
+                (2, 3, BondOrder::Single),
+                (3, 4, BondOrder::Double),
+                (4, 0, BondOrder::Single),
+            ],
+        );
+        attach_hydrogens(&mut borole, &atoms[..1], 1);
+        let perception = ChemicalPerception::from_graph(&borole).expect("perception failed");
+        assert_eq!(ring_contribution(&perception, &bonds, atoms[0]), Some(1));
+        assert_aromatic_state(&perception, &atoms, &bonds, false);
+
+        // Boratabenzene carries its π bond into the ring like a carbon.
+        let (mut boratabenzene, atoms, bonds) = build_ring_molecule(
+            &[
+                (Element::B, -1),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+            ],
+            &[
+                (0, 1, BondOrder::Double),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 0, BondOrder::Single),
+            ],
+        );
+        attach_hydrogens(&mut boratabenzene, &atoms[..1], 1);
+        let perception = ChemicalPerception::from_graph(&boratabenzene).expect("perception failed");
+        assert_aromatic_state(&perception, &atoms, &bonds, true);
+    }
+
+    #[test]
+    fn pyrylium_cation_is_aromatic() {
+        let (molecule, atoms, bonds) = build_ring_molecule(
+            &[
+                (Element::O, 1),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+            ],
+            &[
+                (0, 1, BondOrder::Double),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 0, BondOrder::Single),
+            ],
+        );
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        assert_aromatic_state(&perception, &atoms, &bonds, true);
+    }
+
+    #[test]
+    fn charge_separated_azulene_rings_are_aromatic() {
+        // Five-membered ring 0-4 carries the anion, seven-membered ring
+        // 0, 4, 5-9 carries the cation; the fused bond is 0-4.
+        let (mut molecule, atoms, bonds) = build_ring_molecule(
+            &[
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, -1),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 1),
+                (Element::C, 0),
+                (Element::C, 0),
+            ],
+            &[
+                (0, 1, BondOrder::Double),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Single),
+                (3, 4, BondOrder::Double),
+                (4, 0, BondOrder::Single),
+                (4, 5, BondOrder::Single),
+                (5, 6, BondOrder::Double),
+                (6, 7, BondOrder::Single),
+                (7, 8, BondOrder::Single),
+                (8, 9, BondOrder::Double),
+                (9, 0, BondOrder::Single),
+            ],
+        );
+        attach_hydrogens(&mut molecule, &atoms[2..3], 1);
+        attach_hydrogens(&mut molecule, &atoms[7..8], 1);
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        assert_aromatic_state(&perception, &atoms, &bonds, true);
+    }
+
+    fn ring_aromaticity(perception: &ChemicalPerception, atoms: &[AtomId]) -> RingAromaticity {
+        let mut atoms = atoms.to_vec();
+        atoms.sort_unstable();
+        let ring_idx = perception
+            .ring_info
+            .rings
+            .iter()
+            .position(|ring| ring.atom_ids == atoms)
+            .expect("ring should be perceived");
+        perception.ring_info.aromaticity[ring_idx]
+    }
+
+    #[test]
+    fn cyclobutadiene_is_antiaromatic() {
+        let (molecule, atoms, _) = build_ring_molecule(
+            &[(Element::C, 0); 4],
+            &[
+                (0, 1, BondOrder::Double),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 0, BondOrder::Single),
+            ],
+        );
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        assert_eq!(
+            ring_aromaticity(&perception, &atoms),
+            RingAromaticity::Antiaromatic
+        );
+        assert_eq!(perception.ring_info.systems.len(), 1);
+        assert_eq!(
+            perception.ring_info.systems[0].aromaticity,
+            RingAromaticity::Antiaromatic
+        );
+    }
+
+    #[test]
+    fn cyclooctatetraene_dianion_is_aromatic_while_neutral_ring_is_antiaromatic() {
+        let dianion_bonds = [
+            (0, 1, BondOrder::Double),
+            (1, 2, BondOrder::Single),
+            (2, 3, BondOrder::Double),
+            (3, 4, BondOrder::Single),
+            (4, 5, BondOrder::Single),
+            (5, 6, BondOrder::Double),
+            (6, 7, BondOrder::Single),
+            (7, 0, BondOrder::Single),
+        ];
+        let mut specs = [(Element::C, 0); 8];
+
+        let (neutral, atoms, _) = build_ring_molecule(
+            &specs,
+            &[
+                (0, 1, BondOrder::Double),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 6, BondOrder::Single),
+                (6, 7, BondOrder::Double),
+                (7, 0, BondOrder::Single),
+            ],
+        );
+        let perception = ChemicalPerception::from_graph(&neutral).expect("perception failed");
+        assert_eq!(
+            ring_aromaticity(&perception, &atoms),
+            RingAromaticity::Antiaromatic
+        );
+
+        specs[4] = (Element::C, -1);
+        specs[7] = (Element::C, -1);
+        let (dianion, atoms, _) = build_ring_molecule(&specs, &dianion_bonds);
+        let perception = ChemicalPerception::from_graph(&dianion).expect("perception failed");
+        assert_eq!(
+            ring_aromaticity(&perception, &atoms),
+            RingAromaticity::Aromatic
+        );
+    }
+
+    #[test]
+    fn pentalene_system_is_antiaromatic() {
+        // Two five-membered rings sharing the 0-4 bond.
+        let (molecule, _, _) = build_ring_molecule(
+            &[(Element::C, 0); 8],
+            &[
+                (0, 1, BondOrder::Double),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 4, BondOrder::Single),
+                (4, 0, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 6, BondOrder::Single),
+                (6, 7, BondOrder::Double),
+                (7, 0, BondOrder::Single),
+            ],
+        );
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        assert_eq!(perception.ring_info.systems.len(), 1);
+        assert_eq!(
+            perception.ring_info.systems[0].aromaticity,
+            RingAromaticity::Antiaromatic
+        );
+        assert!(perception.bonds.iter().all(|bond| !bond.is_aromatic));
+    }
+
+    #[test]
+    fn benzocyclobutadiene_rings_are_classified_separately() {
+        let (molecule, atoms, _) = build_ring_molecule(
+            &[(Element::C, 0); 8],
+            &[
+                (0, 1, BondOrder::Double),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 0, BondOrder::Single),
+                (5, 6, BondOrder::Single),
+                (6, 7, BondOrder::Double),
+                (7, 4, BondOrder::Single),
+            ],
+        );
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        assert_eq!(
+            ring_aromaticity(&perception, &atoms[..6]),
+            RingAromaticity::Aromatic
+        );
+        assert_eq!(
+            ring_aromaticity(&perception, &[atoms[4], atoms[5], atoms[6], atoms[7]]),
+            RingAromaticity::Antiaromatic
+        );
+    }
+
+    #[test]
+    fn saturated_ring_is_non_aromatic() {
+        let (mut molecule, atoms, _) = build_ring_molecule(
+            &[(Element::C, 0); 6],
+            &[
+                (0, 1, BondOrder::Single),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Single),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Single),
+                (5, 0, BondOrder::Single),
+            ],
+        );
+        attach_hydrogens(&mut molecule, &atoms, 2);
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        assert_eq!(
+            ring_aromaticity(&perception, &atoms),
+            RingAromaticity::NonAromatic
+        );
+        assert_eq!(
+            perception.ring_info.systems[0].aromaticity,
+            RingAromaticity::NonAromatic
+        );
+    }
+
+    fn alternating_annulene(size: usize) -> (Molecule, Vec<AtomId>, Vec<BondId>) {
+        let bonds: Vec<_> = (0..size)
+            .map(|i| {
+                let order = if i % 2 == 0 {
+                    BondOrder::Double
+                } else {
+                    BondOrder::Single
+                };
+                (i, (i + 1) % size, order)
+            })
+            .collect();
+        build_ring_molecule(&vec![(Element::C, 0); size], &bonds)
+    }
+
+    fn perceive_with_options(
+        molecule: &Molecule,
+        options: &PerceptionOptions,
+    ) -> ChemicalPerception {
+        ChemicalPerception::from_graph_with_options(molecule, options).expect("perception failed")
+    }
+
+    #[test]
+    fn max_ring_size_rejects_annulene_but_not_anthracene() {
+        let options = PerceptionOptions {
+            max_aromatic_ring_size: Some(12),
+            ..PerceptionOptions::default()
+        };
+
+        let (annulene, atoms, bonds) = alternating_annulene(14);
+        let perception = ChemicalPerception::from_graph(&annulene).expect("perception failed");
+        assert_aromatic_state(&perception, &atoms, &bonds, true);
+        let perception = perceive_with_options(&annulene, &options);
+        assert_aromatic_state(&perception, &atoms, &bonds, false);
+
+        // Anthracene: three linearly fused six-membered rings, 14-atom perimeter.
+        let (anthracene, atoms, bonds) = build_ring_molecule(
+            &[(Element::C, 0); 14],
+            &[
+                (0, 1, BondOrder::Double),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Double),
+                (3, 4, BondOrder::Single),
+                (4, 5, BondOrder::Double),
+                (5, 0, BondOrder::Single),
+                (4, 6, BondOrder::Single),
+                (6, 7, BondOrder::Double),
+                (7, 8, BondOrder::Single),
+                (8, 9, BondOrder::Double),
+                (9, 5, BondOrder::Single),
+                (7, 10, BondOrder::Single),
+                (10, 11, BondOrder::Double),
+                (11, 12, BondOrder::Single),
+                (12, 13, BondOrder::Double),
+                (13, 8, BondOrder::Single),
+            ],
+        );
+        let perception = perceive_with_options(&anthracene, &options);
+        assert_aromatic_state(&perception, &atoms, &bonds, true);
+    }
+
+    #[test]
+    fn default_model_treats_kekule_and_aromatic_macrocycles_alike() {
+        let (kekule, atoms, bonds) = alternating_annulene(26);
+        let aromatic_bonds: Vec<_> = (0..26)
+            .map(|i| (i, (i + 1) % 26, BondOrder::Aromatic))
+            .collect();
+        let (aromatic, _, _) = build_ring_molecule(&[(Element::C, 0); 26], &aromatic_bonds);
+
+        let perception = ChemicalPerception::from_graph(&kekule).expect("perception failed");
+        assert_aromatic_state(&perception, &atoms, &bonds, true);
+        let perception = ChemicalPerception::from_graph(&aromatic).expect("perception failed");
+        assert_aromatic_state(&perception, &atoms, &bonds, true);
+
+        let rdkit = perceive_with_model(&kekule, AromaticityModel::Rdkit);
+        assert_aromatic_state(&rdkit, &atoms, &bonds, false);
+    }
+
+    #[test]
+    fn min_ring_size_overrides_model_default() {
+        let (benzene, atoms, bonds) = alternating_annulene(6);
+        let options = PerceptionOptions {
+            min_aromatic_ring_size: Some(7),
+            ..PerceptionOptions::default()
+        };
+
+        let perception = perceive_with_options(&benzene, &options);
+        assert_aromatic_state(&perception, &atoms, &bonds, false);
+    }
+
+    #[test]
+    fn peripheral_circuit_ignores_internal_atoms_of_pyrene() {
+        // Perimeter atoms 0-13; internal atoms 14 and 15.
+        let mut bonds: Vec<_> = (0..14)
+            .map(|i| {
+                let order = if i % 2 == 0 {
+                    BondOrder::Double
+                } else {
+                    BondOrder::Single
+                };
+                (i, (i + 1) % 14, order)
+            })
+            .collect();
+        bonds.extend([
+            (3, 14, BondOrder::Single),
+            (13, 14, BondOrder::Single),
+            (6, 15, BondOrder::Single),
+            (10, 15, BondOrder::Single),
+            (14, 15, BondOrder::Double),
+        ]);
+        let (pyrene, atoms, ring_bonds) = build_ring_molecule(&[(Element::C, 0); 16], &bonds);
+
+        let default_options = PerceptionOptions::default();
+        let peripheral_options = PerceptionOptions {
+            peripheral_circuit: true,
+            ..PerceptionOptions::default()
+        };
+
+        let perception = perceive_with_options(&pyrene, &default_options);
+        let all_rings: Vec<usize> = (0..perception.ring_info.rings.len()).collect();
+        assert_eq!(all_rings.len(), 4);
+        assert_eq!(
+            count_pi_electrons(
+                &perception,
+                &all_rings,
+                &ModelRules::from_options(&default_options)
+            ),
+            Some(16)
+        );
+        assert_eq!(
+            count_pi_electrons(
+                &perception,
+                &all_rings,
+                &ModelRules::from_options(&peripheral_options)
+            ),
+            Some(14)
+        );
+
+        let perception = perceive_with_options(&pyrene, &peripheral_options);
+        assert_aromatic_state(&perception, &atoms, &ring_bonds, true);
+        assert_eq!(
+            perception.ring_info.systems[0].aromaticity,
+            RingAromaticity::Aromatic
+        );
+    }
 }
@@ -1,20 +1,33 @@
 //! Assigns Kekulé resonance orders to aromatic components.
 
-use crate::core::atom::AtomId;
-use crate::core::bond::BondOrder;
-use crate::errors::PerceptionError;
-use crate::perception::ChemicalPerception;
-use std::collections::{HashMap, VecDeque};
+use crate::core::atom::{AtomId, Element};
+use crate::core::bond::{BondId, BondOrder};
+use crate::errors::{PerceptionError, PerceptionWarning};
+use crate::perception::{ChemicalPerception, PerceptionOptions};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 const KEKULIZATION_ATTEMPT_LIMIT: usize = 1000;
+/// Upper bound on the search steps spent on one valence-aware matching.
+const MATCHING_STEP_LIMIT: usize = 10_000;
+/// Upper bound on the tautomer combinations tried for one component.
+const TAUTOMER_GUESS_LIMIT: usize = 256;
 
 /// Assigns alternating single/double orders to every aromatic component.
 ///
-/// The algorithm partitions aromatic bonds into connected components and uses a
-/// bounded backtracking search to find a valid assignment that respects atomic
-/// valences. Bonds that remain unassigned after the search default to `Single`
-/// because the resonance delocalisation handles their aromatic character.
-pub fn kekulize(perception: &mut ChemicalPerception) -> Result<(), PerceptionError> {
+/// Each component is solved as a matching problem: atoms that need a π bond
+/// (see [`PiBondDemand`]) are paired along aromatic bonds, paired bonds become
+/// double and all others single. Hydrogen counts and charges decide whether a
+/// nitrogen is pyrrole-type or pyridine-type. When the input leaves this
+/// open and `options.guess_tautomers` is set, nitrogens are promoted to
+/// pyrrole-type in a deterministic order and flagged as guessed. Components
+/// that still admit no structure fall back to an unconstrained search, unless
+/// `options.lenient_kekulization` is set, in which case components proven to
+/// have no structure, or on which the fallback gives up, are left without
+/// Kekulé orders and reported as warnings.
+pub fn kekulize(
+    perception: &mut ChemicalPerception,
+    options: &PerceptionOptions,
+) -> Result<(), PerceptionError> {
     let mut visited_bonds = vec![false; perception.bonds.len()];
     let mut total_attempts = 0;
 
@@ -23,12 +36,333 @@ pub fn kekulize(perception: &mut ChemicalPerception) -> Result<(), PerceptionErr
             let component_bond_indices =
                 collect_aromatic_component(perception, bond_idx, &mut visited_bonds);
 
-            assign_kekule_orders(perception, &component_bond_indices, &mut total_attempts)?;
+            match find_kekule_structure(
+                perception,
+                &component_bond_indices,
+                options.guess_tautomers,
+            ) {
+                KekuleSearch::Found(structure) => {
+                    apply_kekule_structure(perception, &component_bond_indices, &structure);
+                }
+                KekuleSearch::NotFound if options.lenient_kekulization => {
+                    record_unkekulized_component(perception, &component_bond_indices);
+                }
+                KekuleSearch::NotFound | KekuleSearch::Inconclusive => {
+                    match assign_unconstrained_orders(
+                        perception,
+                        &component_bond_indices,
+                        &mut total_attempts,
+                    ) {
+                        Ok(()) => record_dropped_pi_bonds(perception, &component_bond_indices),
+                        Err(_) if options.lenient_kekulization => {
+                            record_unkekulized_component(perception, &component_bond_indices);
+                        }
+                        Err(error) => return Err(error),
+                    }
+                }
+            }
         }
     }
     Ok(())
 }
 
+/// Whether an aromatic atom takes a double bond in a Kekulé structure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PiBondDemand {
+    /// The atom must take exactly one aromatic double bond (benzene carbon,
+    /// pyridine-type nitrogen).
+    Required,
+    /// The annotation is ambiguous and the atom may take a double bond or
+    /// donate a lone pair, as for a neutral nitrogen with three aromatic bonds.
+    Optional,
+    /// The atom never takes an aromatic double bond.
+    Excluded,
+}
+
+/// Decides whether an atom of an aromatic component must take a double bond.
+///
+/// Atoms that carry a localised multiple bond outside the component, donate a
+/// lone pair (pyrrole-type N with a hydrogen or substituent, furan-type O,
+/// carbanions) or offer an empty orbital (boron, carbocations) are excluded.
+pub fn pi_bond_demand(
+    perception: &ChemicalPerception,
+    atom_idx: usize,
+    component: &HashSet<usize>,
+) -> PiBondDemand {
+    let mut component_bonds = 0;
+    for (_, bond_id) in &perception.adjacency[atom_idx] {
+        let bond_idx = perception.bond_id_to_index[bond_id];
+        if component.contains(&bond_idx) {
+            component_bonds += 1;
+        } else if matches!(
+            perception.bonds[bond_idx].order,
+            BondOrder::Double | BondOrder::Triple
+        ) {
+            return PiBondDemand::Excluded;
+        }
+    }
+
+    let atom = &perception.atoms[atom_idx];
+    match (atom.element, atom.effective_charge(), atom.total_degree) {
+        (Element::C | Element::Si | Element::Ge, 0, _) => PiBondDemand::Required,
+        (Element::N | Element::P | Element::As, 0, 2) => PiBondDemand::Required,
+        (Element::N | Element::P | Element::As, 0, 3) if component_bonds == 3 => {
+            PiBondDemand::Optional
+        }
+        (Element::N | Element::P | Element::As, 1, _) => PiBondDemand::Required,
+        (Element::O | Element::S | Element::Se | Element::Te, 1, _) => PiBondDemand::Required,
+        (Element::B, -1, _) => PiBondDemand::Required,
+        _ => PiBondDemand::Excluded,
+    }
+}
+
+/// A valence-consistent Kekulé structure for one aromatic component.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KekuleStructure {
+    /// Component bonds that become double bonds.
+    pub double_bonds: Vec<usize>,
+    /// Atoms whose pyrrole-type hydrogen had to be guessed.
+    pub guessed_atoms: Vec<usize>,
+}
+
+/// Result of the valence-aware Kekulé search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KekuleSearch {
+    /// A structure exists.
+    Found(KekuleStructure),
+    /// No structure exists, even after any permitted tautomer guesses.
+    NotFound,
+    /// The search budget ran out before an answer was proven.
+    Inconclusive,
+}
+
+/// Searches for a valence-consistent Kekulé structure of an aromatic component.
+///
+/// # Arguments
+///
+/// * `perception` - Perception snapshot containing the component.
+/// * `component_bond_indices` - Indices of the bonds forming the component.
+/// * `guess_tautomers` - Whether pyridine-type nitrogens may be promoted to
+///   pyrrole-type when no structure exists otherwise.
+pub fn find_kekule_structure(
+    perception: &ChemicalPerception,
+    component_bond_indices: &[usize],
+    guess_tautomers: bool,
+) -> KekuleSearch {
+    let component: HashSet<usize> = component_bond_indices.iter().copied().collect();
+    let mut atom_indices: Vec<usize> = component_bond_indices
+        .iter()
+        .flat_map(|&bond_idx| {
+            let bond = &perception.bonds[bond_idx];
+            [
+                perception.atom_id_to_index[&bond.start_atom_id],
+                perception.atom_id_to_index[&bond.end_atom_id],
+            ]
+        })
+        .collect();
+    atom_indices.sort_unstable();
+    atom_indices.dedup();
+
+    let mut required = Vec::new();
+    let mut pairable = HashSet::new();
+    for &atom_idx in &atom_indices {
+        match pi_bond_demand(perception, atom_idx, &component) {
+            PiBondDemand::Required => {
+                required.push(atom_idx);
+                pairable.insert(atom_idx);
+            }
+            PiBondDemand::Optional => {
+                pairable.insert(atom_idx);
+            }
+            PiBondDemand::Excluded => {}
+        }
+    }
+
+    // Pairs `required` atoms along component bonds; `guessed` atoms are
+    // pyrrole-type and take no double bond at all.
+    let search = |required: &[usize], guessed: &[usize]| -> Option<Option<Vec<usize>>> {
+        let allowed = |atom_idx: &usize| pairable.contains(atom_idx) && !guessed.contains(atom_idx);
+        if required.len() % 2 == 1 && required.len() == pairable.len() - guessed.len() {
+            return Some(None);
+        }
+
+        let mut edges: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
+        for &bond_idx in component_bond_indices {
+            let bond = &perception.bonds[bond_idx];
+            let start = perception.atom_id_to_index[&bond.start_atom_id];
+            let end = perception.atom_id_to_index[&bond.end_atom_id];
+            if allowed(&start) && allowed(&end) {
+                edges.entry(start).or_default().push((end, bond_idx));
+                edges.entry(end).or_default().push((start, bond_idx));
+            }
+        }
+
+        let mut matched = HashMap::new();
+        let mut steps = 0;
+        match find_matching(required, &edges, &mut matched, &mut steps) {
+            Some(true) => {
+                let mut double_bonds: Vec<usize> =
+                    matched.values().map(|&(_, bond_idx)| bond_idx).collect();
+                double_bonds.sort_unstable();
+                double_bonds.dedup();
+                Some(Some(double_bonds))
+            }
+            Some(false) => Some(None),
+            None => None,
+        }
+    };
+
+    let mut inconclusive = false;
+    match search(&required, &[]) {
+        Some(Some(double_bonds)) => {
+            return KekuleSearch::Found(KekuleStructure {
+                double_bonds,
+                guessed_atoms: Vec::new(),
+            });
+        }
+        Some(None) => {}
+        None => inconclusive = true,
+    }
+
+    if guess_tautomers {
+        let candidates: Vec<usize> = required
+            .iter()
+            .copied()
+            .filter(|&atom_idx| is_tautomer_candidate(perception, atom_idx))
+            .collect();
+
+        let mut tried = 0;
+        for size in 1..=candidates.len() {
+            for guessed in combinations(&candidates, size) {
+                tried += 1;
+                if tried > TAUTOMER_GUESS_LIMIT {
+                    return KekuleSearch::Inconclusive;
+                }
+                let remaining: Vec<usize> = required
+                    .iter()
+                    .copied()
+                    .filter(|atom_idx| !guessed.contains(atom_idx))
+                    .collect();
+                match search(&remaining, &guessed) {
+                    Some(Some(double_bonds)) => {
+                        return KekuleSearch::Found(KekuleStructure {
+                            double_bonds,
+                            guessed_atoms: guessed,
+                        });
+                    }
+                    Some(None) => {}
+                    None => inconclusive = true,
+                }
+            }
+        }
+    }
+
+    if inconclusive {
+        KekuleSearch::Inconclusive
+    } else {
+        KekuleSearch::NotFound
+    }
+}
+
+/// Checks whether a pyridine-type atom could instead carry a hydrogen.
+fn is_tautomer_candidate(perception: &ChemicalPerception, atom_idx: usize) -> bool {
+    let atom = &perception.atoms[atom_idx];
+    matches!(atom.element, Element::N | Element::P)
+        && atom.effective_charge() == 0
+        && atom.total_degree == 2
+}
+
+/// Lists the `size`-element combinations of `items` in lexicographic order.
+fn combinations(items: &[usize], size: usize) -> Vec<Vec<usize>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+    let mut result = Vec::new();
+    for (idx, &item) in items.iter().enumerate() {
+        for mut rest in combinations(&items[idx + 1..], size - 1) {
+            rest.insert(0, item);
+            result.push(rest);
+        }
+    }
+    result
+}
+
+/// Searches for a matching over `edges` that covers every `required` atom.
+///
+/// Atoms that appear only as partners in `edges` may be matched but do not
+/// have to be. Returns `Some(true)` when such a matching exists, `Some(false)`
+/// when none exists, and `None` when the step budget ran out first. On
+/// success `matched` maps each matched atom to its partner and the bond used.
+fn find_matching(
+    required: &[usize],
+    edges: &HashMap<usize, Vec<(usize, usize)>>,
+    matched: &mut HashMap<usize, (usize, usize)>,
+    steps: &mut usize,
+) -> Option<bool> {
+    // Branch on the unmatched atom with the fewest free partners.
+    let next = required
+        .iter()
+        .filter(|node| !matched.contains_key(node))
+        .min_by_key(|node| {
+            edges.get(node).map_or(0, |partners| {
+                partners
+                    .iter()
+                    .filter(|(partner, _)| !matched.contains_key(partner))
+                    .count()
+            })
+        });
+    let Some(&node) = next else {
+        return Some(true);
+    };
+
+    *steps += 1;
+    if *steps > MATCHING_STEP_LIMIT {
+        return None;
+    }
+
+    let partners = edges.get(&node).map(Vec::as_slice).unwrap_or_default();
+    let mut exhausted = false;
+    for &(partner, bond_idx) in partners {
+        if matched.contains_key(&partner) {
+            continue;
+        }
+        matched.insert(node, (partner, bond_idx));
+        matched.insert(partner, (node, bond_idx));
+        match find_matching(required, edges, matched, steps) {
+            Some(true) => return Some(true),
+            Some(false) => {}
+            None => exhausted = true,
+        }
+        matched.remove(&node);
+        matched.remove(&partner);
+    }
+
+    if exhausted { None } else { Some(false) }
+}
+
+/// Writes a Kekulé structure onto the component and records guessed tautomers.
+fn apply_kekule_structure(
+    perception: &mut ChemicalPerception,
+    component_bond_indices: &[usize],
+    structure: &KekuleStructure,
+) {
+    for &bond_idx in component_bond_indices {
+        let order = if structure.double_bonds.binary_search(&bond_idx).is_ok() {
+            BondOrder::Double
+        } else {
+            BondOrder::Single
+        };
+        perception.bonds[bond_idx].kekule_order = Some(order);
+    }
+
+    for &atom_idx in &structure.guessed_atoms {
+        let atom = &mut perception.atoms[atom_idx];
+        atom.tautomer_guessed = true;
+        atom.implicit_hydrogens = atom.implicit_hydrogens.saturating_add(1);
+        atom.total_degree = atom.total_degree.saturating_add(1);
+    }
+}
+
 /// Collects every aromatic bond reachable from a seed via aromatic-only edges.
 fn collect_aromatic_component(
     perception: &ChemicalPerception,
@@ -67,7 +401,10 @@ fn collect_aromatic_component(
 }
 
 /// Assigns Kekulé orders for a single aromatic component using backtracking.
-fn assign_kekule_orders(
+///
+/// This unconstrained search ignores valences and is only used as a last
+/// resort when no valence-consistent structure exists.
+fn assign_unconstrained_orders(
     perception: &mut ChemicalPerception,
     component_bond_indices: &[usize],
     total_attempts: &mut usize,
@@ -91,7 +428,12 @@ fn assign_kekule_orders(
         &mut atom_double_bond_counts,
         total_attempts,
     ) {
-        return Err(PerceptionError::KekulizationFailed(*total_attempts));
+        let (atoms, bonds) = component_ids(perception, component_bond_indices);
+        return Err(PerceptionError::KekulizationFailed {
+            atoms,
+            bonds,
+            attempts: *total_attempts,
+        });
     }
 
     for &bond_idx in component_bond_indices {
@@ -103,6 +445,79 @@ fn assign_kekule_orders(
     Ok(())
 }
 
+/// Warns about atoms that need a π bond but were left without a double bond
+/// by the unconstrained search.
+fn record_dropped_pi_bonds(perception: &mut ChemicalPerception, component_bond_indices: &[usize]) {
+    let component: HashSet<usize> = component_bond_indices.iter().copied().collect();
+    let mut atom_indices: Vec<usize> = component_bond_indices
+        .iter()
+        .flat_map(|&bond_idx| {
+            let bond = &perception.bonds[bond_idx];
+            [
+                perception.atom_id_to_index[&bond.start_atom_id],
+                perception.atom_id_to_index[&bond.end_atom_id],
+            ]
+        })
+        .collect();
+    atom_indices.sort_unstable();
+    atom_indices.dedup();
+
+    let mut atoms: Vec<AtomId> = atom_indices
+        .into_iter()
+        .filter(|&atom_idx| {
+            pi_bond_demand(perception, atom_idx, &component) == PiBondDemand::Required
+                && !perception.adjacency[atom_idx].iter().any(|(_, bond_id)| {
+                    let bond_idx = perception.bond_id_to_index[bond_id];
+                    component.contains(&bond_idx)
+                        && perception.bonds[bond_idx].kekule_order == Some(BondOrder::Double)
+                })
+        })
+        .map(|atom_idx| perception.atoms[atom_idx].id)
+        .collect();
+    if atoms.is_empty() {
+        return;
+    }
+    atoms.sort_unstable();
+
+    let (_, bonds) = component_ids(perception, component_bond_indices);
+    perception
+        .warnings
+        .push(PerceptionWarning::AromaticPiBondDropped { atoms, bonds });
+}
+
+/// Clears the Kekulé orders of a component that could not be kekulized and
+/// warns about it.
+fn record_unkekulized_component(
+    perception: &mut ChemicalPerception,
+    component_bond_indices: &[usize],
+) {
+    for &bond_idx in component_bond_indices {
+        perception.bonds[bond_idx].kekule_order = None;
+    }
+    let (atoms, bonds) = component_ids(perception, component_bond_indices);
+    perception
+        .warnings
+        .push(PerceptionWarning::UnkekulizedComponent { atoms, bonds });
+}
+
+/// Returns the sorted atom and bond identifiers of a component.
+fn component_ids(
+    perception: &ChemicalPerception,
+    component_bond_indices: &[usize],
+) -> (Vec<AtomId>, Vec<BondId>) {
+    let mut atoms = Vec::new();
+    let mut bonds = Vec::new();
+    for &bond_idx in component_bond_indices {
+        let bond = &perception.bonds[bond_idx];
+        atoms.extend([bond.start_atom_id, bond.end_atom_id]);
+        bonds.push(bond.id);
+    }
+    atoms.sort_unstable();
+    atoms.dedup();
+    bonds.sort_unstable();
+    (atoms, bonds)
+}
+
 /// Recursive search that enumerates valid alternating assignments.
 fn kekule_backtrack(
     perception: &mut ChemicalPerception,
@@ -197,7 +612,7 @@ mod tests {
 
     fn perceive_and_kekulize(molecule: &Molecule) -> ChemicalPerception {
         let mut perception = ChemicalPerception::from_graph(molecule).expect("perception failed");
-        kekulize(&mut perception).expect("kekulization failed");
+        kekulize(&mut perception, &PerceptionOptions::default()).expect("kekulization failed");
         perception
     }
 
@@ -248,7 +663,7 @@ mod tests {
     #[test]
     fn benzene_kekulization_assigns_alternating_bonds() {
         let mut molecule = Molecule::new();
-        let atoms = add_atoms(&mut molecule, &vec![(Element::C, 0); 6]);
+        let atoms = add_atoms(&mut molecule, &[(Element::C, 0); 6]);
         let mut ring_bonds = Vec::new();
 
         add_ring_bond(
@@ -307,7 +722,7 @@ mod tests {
     #[test]
     fn naphthalene_kekulization_assigns_valid_pattern() {
         let mut molecule = Molecule::new();
-        let atoms = add_atoms(&mut molecule, &vec![(Element::C, 0); 10]);
+        let atoms = add_atoms(&mut molecule, &[(Element::C, 0); 10]);
         let mut ring_bonds = Vec::new();
 
         add_ring_bond(
@@ -409,7 +824,7 @@ mod tests {
         let mut molecule = Molecule::new();
         let atoms = add_atoms(
             &mut molecule,
-            &vec![
+            &[
                 (Element::C, 0),
                 (Element::C, 0),
                 (Element::C, 0),
@@ -476,7 +891,7 @@ mod tests {
     #[test]
     fn biphenyl_kekulization_handles_multiple_components() {
         let mut molecule = Molecule::new();
-        let atoms = add_atoms(&mut molecule, &vec![(Element::C, 0); 12]);
+        let atoms = add_atoms(&mut molecule, &[(Element::C, 0); 12]);
         let mut ring_bonds = Vec::new();
 
         add_ring_bond(
@@ -684,13 +1099,10 @@ mod tests {
             &mut ring_bonds,
         );
 
-        let h1 = molecule.add_atom(Element::H, 0);
-        molecule
-            .add_bond(atoms[6], h1, BondOrder::Single)
-            .expect("failed to attach H to N7");
-        let h2 = molecule.add_atom(Element::H, 0);
+        // 9H-purine: only N9 carries a hydrogen; N7 is pyridine-type.
+        let h = molecule.add_atom(Element::H, 0);
         molecule
-            .add_bond(atoms[8], h2, BondOrder::Single)
+            .add_bond(atoms[8], h, BondOrder::Single)
             .expect("failed to attach H to N9");
 
         let perception = perceive_and_kekulize(&molecule);
@@ -725,4 +1137,175 @@ mod tests {
             );
         }
     }
+
+    fn aromatic_imidazole() -> (Molecule, Vec<AtomId>, Vec<BondId>) {
+        // N1-C2-N3-C4-C5, all bonds aromatic, hydrogens implicit.
+        let mut molecule = Molecule::new();
+        let atoms = add_atoms(
+            &mut molecule,
+            &[
+                (Element::N, 0),
+                (Element::C, 0),
+                (Element::N, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+            ],
+        );
+        let mut ring_bonds = Vec::new();
+        for i in 0..atoms.len() {
+            add_ring_bond(
+                &mut molecule,
+                &atoms,
+                i,
+                (i + 1) % atoms.len(),
+                BondOrder::Aromatic,
+                &mut ring_bonds,
+            );
+        }
+        for &carbon in &[atoms[1], atoms[3], atoms[4]] {
+            molecule
+                .set_implicit_hydrogens(carbon, 1)
+                .expect("failed to set implicit hydrogens");
+        }
+        (molecule, atoms, ring_bonds)
+    }
+
+    fn has_double_bond(perception: &ChemicalPerception, atom_id: AtomId) -> bool {
+        let idx = perception.atom_id_to_index[&atom_id];
+        perception.adjacency[idx].iter().any(|(_, bond_id)| {
+            let bond_idx = perception.bond_id_to_index[bond_id];
+            perception.bonds[bond_idx].kekule_order == Some(BondOrder::Double)
+        })
+    }
+
+    #[test]
+    fn implicit_hydrogen_marks_pyrrole_type_nitrogen() {
+        let (mut molecule, atoms, ring_bonds) = aromatic_imidazole();
+        molecule
+            .set_implicit_hydrogens(atoms[2], 1)
+            .expect("failed to set implicit hydrogens");
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        let paired = [atoms[0], atoms[1], atoms[3], atoms[4]];
+        verify_kekule_assignments(&perception, &paired, &ring_bonds, 2);
+
+        assert!(has_double_bond(&perception, atoms[0]));
+        assert!(!has_double_bond(&perception, atoms[2]));
+        assert!(perception.atoms.iter().all(|atom| !atom.tautomer_guessed));
+    }
+
+    #[test]
+    fn missing_hydrogen_position_is_guessed_consistently() {
+        let (molecule, atoms, ring_bonds) = aromatic_imidazole();
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        verify_kekule_assignments(&perception, &atoms[1..], &ring_bonds, 2);
+
+        // The lowest-numbered nitrogen is chosen as the pyrrole-type one.
+        let guessed = &perception.atoms[perception.atom_id_to_index[&atoms[0]]];
+        assert!(guessed.tautomer_guessed);
+        assert_eq!(guessed.implicit_hydrogens, 1);
+        assert_eq!(guessed.total_degree, 3);
+        assert!(!has_double_bond(&perception, atoms[0]));
+        assert!(has_double_bond(&perception, atoms[2]));
+
+        let repeated = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        for bond in &perception.bonds {
+            let idx = repeated.bond_id_to_index[&bond.id];
+            assert_eq!(repeated.bonds[idx].kekule_order, bond.kekule_order);
+        }
+    }
+
+    #[test]
+    fn lenient_mode_leaves_unkekulizable_component_delocalised() {
+        let (molecule, atoms, ring_bonds) = aromatic_imidazole();
+        let strict = PerceptionOptions {
+            guess_tautomers: false,
+            ..PerceptionOptions::default()
+        };
+        assert!(ChemicalPerception::from_graph_with_options(&molecule, &strict).is_err());
+
+        let lenient = PerceptionOptions {
+            lenient_kekulization: true,
+            ..strict
+        };
+        let perception = ChemicalPerception::from_graph_with_options(&molecule, &lenient)
+            .expect("lenient perception failed");
+
+        let mut expected_atoms = atoms;
+        expected_atoms.sort_unstable();
+        let mut expected_bonds = ring_bonds;
+        expected_bonds.sort_unstable();
+        assert_eq!(
+            perception.warnings,
+            vec![PerceptionWarning::UnkekulizedComponent {
+                atoms: expected_atoms,
+                bonds: expected_bonds.clone(),
+            }]
+        );
+        for bond_id in expected_bonds {
+            let bond = &perception.bonds[perception.bond_id_to_index[&bond_id]];
+            assert!(bond.is_aromatic);
+            assert!(bond.kekule_order.is_none());
+        }
+    }
+
+    #[test]
+    fn purine_without_hydrogen_positions_guesses_a_single_tautomer() {
+        // Purine skeleton: six-membered ring 0-5, five-membered ring 3, 4, 6, 7, 8.
+        let mut molecule = Molecule::new();
+        let atoms = add_atoms(
+            &mut molecule,
+            &[
+                (Element::N, 0),
+                (Element::C, 0),
+                (Element::N, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::C, 0),
+                (Element::N, 0),
+                (Element::C, 0),
+                (Element::N, 0),
+            ],
+        );
+        let mut ring_bonds = Vec::new();
+        for (start, end) in [
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 0),
+            (4, 6),
+            (6, 7),
+            (7, 8),
+            (8, 3),
+        ] {
+            add_ring_bond(
+                &mut molecule,
+                &atoms,
+                start,
+                end,
+                BondOrder::Aromatic,
+                &mut ring_bonds,
+            );
+        }
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        let guessed: Vec<AtomId> = perception
+            .atoms
+            .iter()
+            .filter(|atom| atom.tautomer_guessed)
+            .map(|atom| atom.id)
+            .collect();
+        assert_eq!(guessed.len(), 1);
+
+        let paired: Vec<AtomId> = atoms
+            .iter()
+            .copied()
+            .filter(|atom_id| *atom_id != guessed[0])
+            .collect();
+        verify_kekule_assignments(&perception, &paired, &ring_bonds, 4);
+        assert!(!has_double_bond(&perception, guessed[0]));
+    }
 }
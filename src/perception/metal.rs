@@ -0,0 +1,282 @@
+//! Metal-aware handling of coordination bonds.
+//!
+//! Drawn organometallics connect a metal to every atom it coordinates, which
+//! inflates ligand degrees and creates metallacycles that hide ligand
+//! aromaticity. This stage detaches such bonds so that the remaining stages
+//! only see the ligands, and assigns each ligand the charge implied by the
+//! bonding pairs it keeps.
+
+use crate::core::atom::Element;
+use crate::core::bond::BondOrder;
+use crate::perception::ChemicalPerception;
+
+/// Detaches every bond between a metal and a non-metal from the topology.
+///
+/// Detached bonds are flagged [`is_metal_ligand`](crate::perception::PerceivedBond::is_metal_ligand),
+/// removed from the adjacency list and subtracted from the degree of both
+/// ends. A ligand atom whose remaining bonds fall short of its normal valence
+/// was σ-bonded to the metal and keeps the bonding pair: its
+/// [`ligand_charge`](crate::perception::PerceivedAtom::ligand_charge) drops by
+/// the shortfall, up to the order of its metal bonds. Ligands that still reach
+/// their normal valence, such as pyridine or phosphines, are dative donors
+/// and stay neutral.
+pub fn detach_ligands(perception: &mut ChemicalPerception) {
+    let mut link_orders = vec![0u8; perception.atoms.len()];
+
+    for bond in &mut perception.bonds {
+        let start_idx = perception.atom_id_to_index[&bond.start_atom_id];
+        let end_idx = perception.atom_id_to_index[&bond.end_atom_id];
+        let start_is_metal = perception.atoms[start_idx].element.is_metal();
+        let end_is_metal = perception.atoms[end_idx].element.is_metal();
+        if start_is_metal == end_is_metal || !bond.order.is_covalent() {
+            continue;
+        }
+
+        bond.is_metal_ligand = true;
+        let ligand_idx = if start_is_metal { end_idx } else { start_idx };
+        // A ligand that donates through a dative bond contributes nothing.
+        link_orders[ligand_idx] = link_orders[ligand_idx]
+            .saturating_add(bond.order.valence_contribution(!start_is_metal));
+    }
+
+    let bonds = &perception.bonds;
+    let bond_id_to_index = &perception.bond_id_to_index;
+    for (atom_idx, neighbors) in perception.adjacency.iter_mut().enumerate() {
+        let before = neighbors.len();
+        neighbors.retain(|(_, bond_id)| !bonds[bond_id_to_index[bond_id]].is_metal_ligand);
+        let detached = (before - neighbors.len()) as u8;
+        let atom = &mut perception.atoms[atom_idx];
+        atom.total_degree = atom.total_degree.saturating_sub(detached);
+    }
+
+    for (atom_idx, &link_order) in link_orders.iter().enumerate() {
+        if link_order == 0 {
+            continue;
+        }
+        let Some(expected) = normal_valence(perception, atom_idx) else {
+            continue;
+        };
+        let shortfall = expected
+            .saturating_sub(bonded_valence(perception, atom_idx))
+            .min(link_order);
+        perception.atoms[atom_idx].ligand_charge = -(shortfall as i8);
+    }
+}
+
+/// Returns the valence an atom reaches in a closed-shell Lewis structure,
+/// e.g. four for neutral carbon, three for N or C⁻ and one for Cl.
+fn normal_valence(perception: &ChemicalPerception, atom_idx: usize) -> Option<u8> {
+    let atom = &perception.atoms[atom_idx];
+    if atom.element == Element::H {
+        return Some(1);
+    }
+    let electrons = i16::from(atom.element.valence_electrons()?) - i16::from(atom.formal_charge);
+    let valence = if electrons >= 4 {
+        8 - electrons
+    } else {
+        electrons
+    };
+    u8::try_from(valence).ok()
+}
+
+/// Sums the bond orders and hydrogens left on an atom after detachment.
+///
+/// An atom with explicitly aromatic bonds is credited with one π bond, since
+/// the aromatic annotation does not say how its valence is split.
+fn bonded_valence(perception: &ChemicalPerception, atom_idx: usize) -> u8 {
+    let atom = &perception.atoms[atom_idx];
+    let mut valence = atom.implicit_hydrogens;
+    let mut has_aromatic_bond = false;
+    for (_, bond_id) in &perception.adjacency[atom_idx] {
+        let bond = &perception.bonds[perception.bond_id_to_index[bond_id]];
+        has_aromatic_bond |= bond.order == BondOrder::Aromatic;
+        valence = valence.saturating_add(
+            bond.order
+                .valence_contribution(bond.start_atom_id == atom.id),
+        );
+    }
+    if has_aromatic_bond {
+        valence = valence.saturating_add(1);
+    }
+    valence
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::atom::AtomId;
+    use crate::molecule::Molecule;
+    use crate::perception::{PerceptionOptions, RingAromaticity};
+
+    fn metal_aware() -> PerceptionOptions {
+        PerceptionOptions {
+            metal_aware: true,
+            ..PerceptionOptions::default()
+        }
+    }
+
+    /// Adds a neutral cyclopentadienyl ring with two double bonds, every
+    /// carbon bonded to `metal`.
+    fn add_cyclopentadienyl(molecule: &mut Molecule, metal: AtomId) -> Vec<AtomId> {
+        let carbons: Vec<AtomId> = (0..5).map(|_| molecule.add_atom(Element::C, 0)).collect();
+        let orders = [
+            BondOrder::Double,
+            BondOrder::Single,
+            BondOrder::Double,
+            BondOrder::Single,
+            BondOrder::Single,
+        ];
+        for (i, order) in orders.into_iter().enumerate() {
+            molecule
+                .add_bond(carbons[i], carbons[(i + 1) % 5], order)
+                .expect("failed to add ring bond");
+        }
+        for &carbon in &carbons {
+            let hydrogen = molecule.add_atom(Element::H, 0);
+            molecule
+                .add_bond(carbon, hydrogen, BondOrder::Single)
+                .expect("failed to add hydrogen");
+            molecule
+                .add_bond(metal, carbon, BondOrder::Single)
+                .expect("failed to add metal bond");
+        }
+        carbons
+    }
+
+    fn ferrocene() -> (Molecule, AtomId, Vec<AtomId>) {
+        let mut molecule = Molecule::new();
+        let iron = molecule.add_atom(Element::Fe, 0);
+        let mut carbons = add_cyclopentadienyl(&mut molecule, iron);
+        carbons.extend(add_cyclopentadienyl(&mut molecule, iron));
+        (molecule, iron, carbons)
+    }
+
+    #[test]
+    fn ferrocene_cp_rings_are_aromatic_anions() {
+        let (molecule, iron, carbons) = ferrocene();
+        let perception = ChemicalPerception::from_graph_with_options(&molecule, &metal_aware())
+            .expect("perception should succeed");
+
+        assert_eq!(perception.ring_info.rings.len(), 2);
+        assert!(
+            perception
+                .ring_info
+                .aromaticity
+                .iter()
+                .all(|&aromaticity| aromaticity == RingAromaticity::Aromatic)
+        );
+
+        for &carbon in &carbons {
+            let atom = &perception.atoms[perception.atom_id_to_index[&carbon]];
+            assert!(atom.is_aromatic);
+            assert_eq!(atom.total_degree, 3);
+        }
+        let anionic = carbons
+            .iter()
+            .filter(|carbon| {
+                perception.atoms[perception.atom_id_to_index[carbon]].ligand_charge == -1
+            })
+            .count();
+        assert_eq!(anionic, 2, "each Cp ring should carry one negative charge");
+
+        let iron = &perception.atoms[perception.atom_id_to_index[&iron]];
+        assert_eq!(iron.total_degree, 0);
+        assert_eq!(
+            perception
+                .bonds
+                .iter()
+                .filter(|bond| bond.is_metal_ligand)
+                .count(),
+            10
+        );
+    }
+
+    #[test]
+    fn ferrocene_without_metal_awareness_keeps_metallacycles() {
+        let (molecule, _, carbons) = ferrocene();
+        let perception =
+            ChemicalPerception::from_graph(&molecule).expect("perception should succeed");
+
+        assert!(perception.ring_info.rings.len() > 2);
+        assert!(
+            carbons.iter().all(|carbon| {
+                !perception.atoms[perception.atom_id_to_index[carbon]].is_aromatic
+            })
+        );
+    }
+
+    #[test]
+    fn dative_pyridine_ligand_stays_neutral() {
+        let mut molecule = Molecule::new();
+        let zinc = molecule.add_atom(Element::Zn, 0);
+        let ring: Vec<AtomId> = [
+            Element::N,
+            Element::C,
+            Element::C,
+            Element::C,
+            Element::C,
+            Element::C,
+        ]
+        .into_iter()
+        .map(|element| molecule.add_atom(element, 0))
+        .collect();
+        for i in 0..6 {
+            let order = if i % 2 == 0 {
+                BondOrder::Double
+            } else {
+                BondOrder::Single
+            };
+            molecule
+                .add_bond(ring[i], ring[(i + 1) % 6], order)
+                .expect("failed to add ring bond");
+        }
+        molecule
+            .add_bond(zinc, ring[0], BondOrder::Single)
+            .expect("failed to add metal bond");
+
+        let perception = ChemicalPerception::from_graph_with_options(&molecule, &metal_aware())
+            .expect("perception should succeed");
+        let nitrogen = &perception.atoms[perception.atom_id_to_index[&ring[0]]];
+        assert_eq!(nitrogen.ligand_charge, 0);
+        assert_eq!(nitrogen.total_degree, 2);
+        assert!(nitrogen.is_aromatic);
+    }
+
+    #[test]
+    fn dative_ligand_donor_keeps_no_charge() {
+        let mut molecule = Molecule::new();
+        let platinum = molecule.add_atom(Element::Pt, 0);
+        let nitrogen = molecule.add_atom(Element::N, 0);
+        for _ in 0..3 {
+            let hydrogen = molecule.add_atom(Element::H, 0);
+            molecule
+                .add_bond(nitrogen, hydrogen, BondOrder::Single)
+                .expect("failed to add hydrogen");
+        }
+        molecule
+            .add_bond(nitrogen, platinum, BondOrder::Dative)
+            .expect("failed to add dative bond");
+
+        let perception = ChemicalPerception::from_graph_with_options(&molecule, &metal_aware())
+            .expect("perception should succeed");
+        let ammine = &perception.atoms[perception.atom_id_to_index[&nitrogen]];
+        assert_eq!(ammine.ligand_charge, 0);
+        assert_eq!(ammine.total_degree, 3);
+    }
+
+    #[test]
+    fn halide_ligand_takes_the_bonding_pair() {
+        let mut molecule = Molecule::new();
+        let sodium = molecule.add_atom(Element::Na, 0);
+        let chlorine = molecule.add_atom(Element::Cl, 0);
+        molecule
+            .add_bond(sodium, chlorine, BondOrder::Single)
+            .expect("failed to add bond");
+
+        let perception = ChemicalPerception::from_graph_with_options(&molecule, &metal_aware())
+            .expect("perception should succeed");
+        let chloride = &perception.atoms[perception.atom_id_to_index[&chlorine]];
+        assert_eq!(chloride.effective_charge(), -1);
+        assert_eq!(chloride.lone_pairs, 4);
+    }
+}
@@ -5,18 +5,33 @@
 
 use crate::core::atom::{AtomId, Element};
 use crate::core::bond::{BondId, BondOrder};
-use crate::errors::PerceptionError;
+use crate::errors::{PerceptionError, PerceptionWarning, ValenceError};
 use crate::graph::traits::{AtomView, BondView, MoleculeGraph};
 use crate::perception::ring::RingInfo;
 use crate::resonance;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::ops::{BitOr, BitOrAssign};
 
 mod aromaticity;
 mod kekulize;
+mod metal;
+mod options;
 mod ring;
 mod state;
-
+mod validate;
+
+/// Aromaticity conventions selectable through [`PerceptionOptions`].
+pub use aromaticity::AromaticityModel;
+/// Aromatic, antiaromatic or non-aromatic classification of rings.
+pub use aromaticity::RingAromaticity;
+/// Caller-selectable settings for the perception pipeline.
+pub use options::PerceptionOptions;
+/// Ring detected by ring perception or cycle enumeration.
+pub use ring::Ring;
+/// Enumerates every simple cycle up to a size bound.
+pub use ring::find_cycles;
+/// VSEPR geometry assigned to perceived atoms.
+pub use state::Geometry;
 /// Hybridization states assigned to perceived atoms.
 pub use state::Hybridization;
 
@@ -35,6 +50,12 @@ impl ConjugationRole {
     pub const CHARGE_MEDIATOR: Self = Self(1 << 2);
     /// Hypervalent centre capable of bridging multiple π partners.
     pub const HYPERVALENT_BRIDGE: Self = Self(1 << 3);
+    /// Unpaired electron that delocalises into neighbouring π systems (allyl,
+    /// benzyl, phenoxyl radicals).
+    pub const RADICAL: Self = Self(1 << 4);
+    /// Vacant p orbital that accepts π density from neighbouring donors
+    /// (tricoordinate boron, carbocations, silylium).
+    pub const EMPTY_ORBITAL_ACCEPTOR: Self = Self(1 << 5);
 
     /// Returns `true` when no roles are recorded.
     pub fn is_empty(self) -> bool {
@@ -81,8 +102,19 @@ pub struct PerceivedAtom {
     pub element: Element,
     /// Formal charge supplied by the source molecule.
     pub formal_charge: i8,
-    /// Number of adjacent bonds in the original graph.
+    /// Number of adjacent bonds in the original graph plus implicit hydrogens.
     pub total_degree: u8,
+    /// Hydrogens reported by [`AtomView::implicit_hydrogens`], plus one when
+    /// Kekulization guessed a pyrrole-type tautomer for the atom.
+    pub implicit_hydrogens: u8,
+    /// Set when Kekulization had to guess that this aromatic atom carries the
+    /// hydrogen of its tautomer because the input did not say.
+    pub tautomer_guessed: bool,
+    /// Unpaired electrons reported by [`AtomView::radical_electrons`].
+    pub radical_electrons: u8,
+    /// Charge taken over from detached metal–ligand bonds in metal-aware mode,
+    /// e.g. `-1` for each carbon of a Cp ring bound through a σ bond.
+    pub ligand_charge: i8,
     /// Sum of bond multiplicities including Kekulé adjustments.
     pub total_valence: u8,
     /// Indicates whether the atom belongs to at least one ring in the SSSR set.
@@ -91,6 +123,10 @@ pub struct PerceivedAtom {
     pub is_aromatic: bool,
     /// Hybridization state inferred during the perception pipeline.
     pub hybridization: Hybridization,
+    /// Bonded neighbours plus lone pairs that occupy hybrid orbitals.
+    pub steric_number: u8,
+    /// VSEPR shape implied by the steric number and neighbour count.
+    pub geometry: Geometry,
     /// Flag denoting participation eligibility in conjugation/resonance searches.
     pub is_conjugation_candidate: bool,
     /// Estimated number of lone pairs according to valence heuristics.
@@ -107,15 +143,27 @@ impl PerceivedAtom {
             element,
             formal_charge,
             total_degree,
+            implicit_hydrogens: 0,
+            tautomer_guessed: false,
+            radical_electrons: 0,
+            ligand_charge: 0,
             total_valence: 0,
             is_in_ring: false,
             is_aromatic: false,
             hybridization: Hybridization::Unknown,
+            steric_number: 0,
+            geometry: Geometry::Unknown,
             is_conjugation_candidate: false,
             lone_pairs: 0,
             conjugation_roles: ConjugationRole::NONE,
         }
     }
+
+    /// Returns the formal charge plus any charge taken over from metal
+    /// centres, which is the charge the perception heuristics reason about.
+    pub fn effective_charge(&self) -> i8 {
+        self.formal_charge.saturating_add(self.ligand_charge)
+    }
 }
 
 /// Bond annotated with metadata derived from the perception pipeline.
@@ -135,6 +183,9 @@ pub struct PerceivedBond {
     pub is_aromatic: bool,
     /// Kekulé order assigned during Kekulization, when applicable.
     pub kekule_order: Option<BondOrder>,
+    /// Indicates a metal–ligand bond detached from the topology in
+    /// metal-aware mode.
+    pub is_metal_ligand: bool,
 }
 
 impl PerceivedBond {
@@ -148,9 +199,18 @@ impl PerceivedBond {
             is_in_ring: false,
             is_aromatic: false,
             kekule_order: None,
+            is_metal_ligand: false,
         }
     }
 
+    /// Checks whether the bond is left out of the adjacency list.
+    ///
+    /// Non-covalent contacts are never part of the topology, and metal–ligand
+    /// bonds are detached from it in metal-aware mode.
+    pub fn is_detached(&self) -> bool {
+        self.is_metal_ligand || !self.order.is_covalent()
+    }
+
     /// Returns the opposite atom identifier given one endpoint.
     ///
     /// # Arguments
@@ -186,6 +246,9 @@ pub struct ChemicalPerception {
 
     /// Ring data detected during the perception pipeline.
     pub ring_info: RingInfo,
+
+    /// Soft anomalies recorded by the stages, in pipeline order.
+    pub warnings: Vec<PerceptionWarning>,
 }
 
 impl ChemicalPerception {
@@ -209,6 +272,118 @@ impl ChemicalPerception {
     /// Propagates [`PerceptionError`] variants when the input graph contains
     /// structural inconsistencies or when intermediate perception stages fail.
     pub fn from_graph<G>(graph: &G) -> Result<Self, PerceptionError>
+    where
+        G: MoleculeGraph,
+    {
+        Self::from_graph_with_options(graph, &PerceptionOptions::default())
+    }
+
+    /// Builds a `ChemicalPerception` using caller-supplied [`PerceptionOptions`].
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - An implementation of [`MoleculeGraph`].
+    /// * `options` - Settings that tune individual perception stages.
+    ///
+    /// # Returns
+    ///
+    /// A fully populated `ChemicalPerception` ready for downstream resonance
+    /// identification.
+    ///
+    /// # Errors
+    ///
+    /// Propagates [`PerceptionError`] variants when the input graph contains
+    /// structural inconsistencies or when intermediate perception stages fail.
+    pub fn from_graph_with_options<G>(
+        graph: &G,
+        options: &PerceptionOptions,
+    ) -> Result<Self, PerceptionError>
+    where
+        G: MoleculeGraph,
+    {
+        let mut perception = Self::from_topology(graph)?;
+
+        if options.metal_aware {
+            metal::detach_ligands(&mut perception);
+        }
+
+        let ring_info = ring::find_sssr(&perception);
+
+        for ring in &ring_info.rings {
+            for &atom_id in &ring.atom_ids {
+                if let Some(&idx) = perception.atom_id_to_index.get(&atom_id) {
+                    perception.atoms[idx].is_in_ring = true;
+                }
+            }
+            for &bond_id in &ring.bond_ids {
+                if let Some(&idx) = perception.bond_id_to_index.get(&bond_id) {
+                    perception.bonds[idx].is_in_ring = true;
+                }
+            }
+        }
+        perception
+            .warnings
+            .extend(ring_info.ambiguities.iter().map(|ambiguity| {
+                PerceptionWarning::AmbiguousSssr {
+                    ring_size: ambiguity.ring_size,
+                    atoms: ambiguity.atoms.clone(),
+                }
+            }));
+        perception
+            .warnings
+            .extend(ring_info.shortfalls.iter().map(|shortfall| {
+                PerceptionWarning::IncompleteSssr {
+                    atoms: shortfall.atoms.clone(),
+                    bonds: shortfall.bonds.clone(),
+                    expected_rings: shortfall.expected_rings,
+                    found_rings: shortfall.found_rings,
+                }
+            }));
+        perception.ring_info = ring_info;
+
+        validate::validate_explicit_aromaticity(&perception, options)?;
+
+        aromaticity::perceive(&mut perception, options);
+
+        kekulize::kekulize(&mut perception, options)?;
+
+        state::perceive(&mut perception);
+
+        if options.strict_valence {
+            let errors = validate::check_valences(&perception);
+            if !errors.is_empty() {
+                return Err(PerceptionError::InvalidValence { errors });
+            }
+        }
+
+        resonance::candidate::determine(&mut perception);
+
+        Ok(perception)
+    }
+
+    /// Lists the atoms whose perceived valence is not allowed for their
+    /// element and charge, ordered by atom identifier.
+    pub fn valence_errors(&self) -> Vec<ValenceError> {
+        validate::check_valences(self)
+    }
+
+    /// Copies the atoms, bonds, and adjacency of a [`MoleculeGraph`] without
+    /// running any perception stage.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - An implementation of [`MoleculeGraph`].
+    ///
+    /// # Returns
+    ///
+    /// A `ChemicalPerception` whose perception metadata is still at its
+    /// defaults, suitable for purely topological queries.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PerceptionError`] when the graph contains duplicate bonds,
+    /// self-loops, or bonds that reference missing atoms.
+    pub fn from_topology<G>(graph: &G) -> Result<Self, PerceptionError>
     where
         G: MoleculeGraph,
     {
@@ -222,31 +397,48 @@ impl ChemicalPerception {
         let mut adjacency: Vec<Vec<(usize, BondId)>> = vec![Vec::new(); num_atoms];
         let mut bonds = Vec::new();
         let mut bond_id_to_index = HashMap::new();
-        let mut seen_bonds = HashSet::new();
+        let mut seen_bonds = HashMap::new();
 
         for bond_view in graph.bonds() {
             let start_id = bond_view.start_atom_id();
             let end_id = bond_view.end_atom_id();
 
+            if start_id == end_id {
+                return Err(PerceptionError::SelfLoop {
+                    bond: bond_view.id(),
+                    atom: start_id,
+                });
+            }
+
             let canonical_start = start_id.min(end_id);
             let canonical_end = start_id.max(end_id);
 
-            if !seen_bonds.insert((canonical_start, canonical_end)) {
+            if let Some(&first) = seen_bonds.get(&(canonical_start, canonical_end)) {
                 return Err(PerceptionError::DuplicateBond {
                     start: start_id,
                     end: end_id,
+                    bonds: vec![first, bond_view.id()],
                 });
             }
+            seen_bonds.insert((canonical_start, canonical_end), bond_view.id());
 
+            let dangling = |atom| PerceptionError::InconsistentGraph {
+                bond: bond_view.id(),
+                atom,
+            };
             let start_idx = *atom_id_to_index
                 .get(&start_id)
-                .ok_or(PerceptionError::InconsistentGraph(start_id))?;
+                .ok_or_else(|| dangling(start_id))?;
             let end_idx = *atom_id_to_index
                 .get(&end_id)
-                .ok_or(PerceptionError::InconsistentGraph(end_id))?;
+                .ok_or_else(|| dangling(end_id))?;
 
-            adjacency[start_idx].push((end_idx, bond_view.id()));
-            adjacency[end_idx].push((start_idx, bond_view.id()));
+            // Contacts such as ionic or hydrogen bonds stay out of the
+            // topology, so they add no degree and close no rings.
+            if bond_view.order().is_covalent() {
+                adjacency[start_idx].push((end_idx, bond_view.id()));
+                adjacency[end_idx].push((start_idx, bond_view.id()));
+            }
 
             bond_id_to_index.insert(bond_view.id(), bonds.len());
             bonds.push(PerceivedBond::new(
@@ -260,48 +452,27 @@ impl ChemicalPerception {
         let mut perceived_atoms = Vec::with_capacity(num_atoms);
         for atom_view in source_atoms {
             let idx = atom_id_to_index[&atom_view.id()];
-            perceived_atoms.push(PerceivedAtom::new(
+            let implicit_hydrogens = atom_view.implicit_hydrogens();
+            let mut atom = PerceivedAtom::new(
                 atom_view.id(),
                 atom_view.element(),
                 atom_view.formal_charge(),
-                adjacency[idx].len() as u8,
-            ));
+                (adjacency[idx].len() as u8).saturating_add(implicit_hydrogens),
+            );
+            atom.implicit_hydrogens = implicit_hydrogens;
+            atom.radical_electrons = atom_view.radical_electrons();
+            perceived_atoms.push(atom);
         }
 
-        let mut perception = Self {
+        Ok(Self {
             atoms: perceived_atoms,
             bonds,
             adjacency,
             atom_id_to_index,
             bond_id_to_index,
             ring_info: RingInfo::default(),
-        };
-
-        let ring_info = ring::find_sssr(&perception);
-
-        for ring in &ring_info.rings {
-            for &atom_id in &ring.atom_ids {
-                if let Some(&idx) = perception.atom_id_to_index.get(&atom_id) {
-                    perception.atoms[idx].is_in_ring = true;
-                }
-            }
-            for &bond_id in &ring.bond_ids {
-                if let Some(&idx) = perception.bond_id_to_index.get(&bond_id) {
-                    perception.bonds[idx].is_in_ring = true;
-                }
-            }
-        }
-        perception.ring_info = ring_info;
-
-        aromaticity::perceive(&mut perception);
-
-        kekulize::kekulize(&mut perception)?;
-
-        state::perceive(&mut perception);
-
-        resonance::candidate::determine(&mut perception);
-
-        Ok(perception)
+            warnings: Vec::new(),
+        })
     }
 }
 
@@ -554,10 +725,9 @@ mod tests {
                 && bond.start_atom_id != bond.end_atom_id
                 && perception.atoms[perception.atom_id_to_index[&bond.start_atom_id]].is_aromatic
                 && perception.atoms[perception.atom_id_to_index[&bond.end_atom_id]].is_aromatic
+                && let Some(kekule) = bond.kekule_order
             {
-                if let Some(kekule) = bond.kekule_order {
-                    assert_ne!(kekule, BondOrder::Double);
-                }
+                assert_ne!(kekule, BondOrder::Double);
             }
         }
     }
@@ -585,4 +755,52 @@ mod tests {
             assert!(bond.kekule_order.is_none());
         }
     }
+
+    #[test]
+    fn hydrogen_bond_contact_closes_no_ring_and_adds_no_degree() {
+        // Four-carbon chain whose ends are linked only by a hydrogen bond.
+        let mut molecule = Molecule::new();
+        let chain: Vec<AtomId> = (0..4).map(|_| molecule.add_atom(Element::C, 0)).collect();
+        for pair in chain.windows(2) {
+            molecule
+                .add_bond(pair[0], pair[1], BondOrder::Single)
+                .expect("chain bond");
+        }
+        let contact = molecule
+            .add_bond(chain[3], chain[0], BondOrder::Hydrogen)
+            .expect("contact bond");
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception");
+
+        assert!(perception.ring_info.rings.is_empty());
+        let first = &perception.atoms[perception.atom_id_to_index[&chain[0]]];
+        assert_eq!(first.total_degree, 1);
+        assert_eq!(first.total_valence, 1);
+        assert!(perception.bonds[perception.bond_id_to_index[&contact]].is_detached());
+    }
+
+    #[test]
+    fn dative_bond_counts_towards_the_acceptor_valence() {
+        // Ammonia borane, H3N->BH3.
+        let mut molecule = Molecule::new();
+        let nitrogen = molecule.add_atom(Element::N, 0);
+        let boron = molecule.add_atom(Element::B, 0);
+        for _ in 0..3 {
+            attach_hydrogen(&mut molecule, nitrogen);
+            attach_hydrogen(&mut molecule, boron);
+        }
+        molecule
+            .add_bond(nitrogen, boron, BondOrder::Dative)
+            .expect("dative bond");
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception");
+
+        let nitrogen = &perception.atoms[perception.atom_id_to_index[&nitrogen]];
+        assert_eq!(nitrogen.total_degree, 4);
+        assert_eq!(nitrogen.total_valence, 3);
+        assert_eq!(nitrogen.lone_pairs, 0);
+        assert_eq!(nitrogen.hybridization, Hybridization::SP3);
+        let boron = &perception.atoms[perception.atom_id_to_index[&boron]];
+        assert_eq!(boron.total_valence, 4);
+    }
 }
@@ -0,0 +1,97 @@
+//! Caller-selectable settings that tune the perception pipeline.
+
+use crate::perception::aromaticity::AromaticityModel;
+
+/// Settings that control how the perception pipeline interprets a graph.
+///
+/// The [`Default`] value reproduces the behaviour of
+/// [`find_resonance_systems`](crate::find_resonance_systems).
+///
+/// # Examples
+///
+/// ```
+/// use pauling::{AromaticityModel, PerceptionOptions};
+///
+/// let options = PerceptionOptions {
+///     aromaticity_model: AromaticityModel::Mdl,
+///     ..PerceptionOptions::default()
+/// };
+/// assert_eq!(options.aromaticity_model, AromaticityModel::Mdl);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PerceptionOptions {
+    /// Aromaticity model used when detecting aromatic rings from topology.
+    pub aromaticity_model: AromaticityModel,
+    /// Smallest ring, in atoms, that may be aromatic.
+    ///
+    /// `None` keeps the limit of the selected [`AromaticityModel`]. The limit
+    /// applies to each SSSR ring rather than to fused perimeters.
+    pub min_aromatic_ring_size: Option<usize>,
+    /// Largest ring, in atoms, that may be aromatic.
+    ///
+    /// `None` keeps the limit of the selected [`AromaticityModel`]. Because the
+    /// limit applies to each SSSR ring, a limit of 12 rejects [14]annulene
+    /// while anthracene, whose 14-atom perimeter is made of six-membered
+    /// rings, stays aromatic.
+    pub max_aromatic_ring_size: Option<usize>,
+    /// Count only the peripheral circuit of fused polycycles.
+    ///
+    /// When enabled, bonds shared by two rings and atoms off the perimeter are
+    /// left out of the π-electron count of multi-ring systems, so pyrene is
+    /// judged by its 14-electron periphery rather than all 16 electrons.
+    pub peripheral_circuit: bool,
+    /// Guess the hydrogen position of aromatic input that omits it.
+    ///
+    /// In aromatic input such as imidazole or purine without hydrogen counts,
+    /// Kekulization cannot tell pyrrole-type from pyridine-type nitrogens.
+    /// When enabled (the default), the lowest-numbered nitrogens that yield a
+    /// valid Kekulé structure are treated as pyrrole-type and flagged as
+    /// guessed. When disabled, such input is rejected with
+    /// [`PerceptionError::InvalidAromaticInput`](crate::PerceptionError::InvalidAromaticInput).
+    pub guess_tautomers: bool,
+    /// Treat bonds between metals and non-metals as coordination links.
+    ///
+    /// When enabled, metal–ligand bonds are detached before ring perception,
+    /// so they add nothing to the degree or valence of either end and create
+    /// no metallacycles. A ligand atom left short of its normal valence keeps
+    /// the bonding pair as a negative charge, which lets Cp rings in ferrocene
+    /// or the core of a metalloporphyrin be perceived as the aromatic anions
+    /// they are. Metal centres are listed separately in
+    /// [`PerceptionReport::metal_centres`](crate::PerceptionReport::metal_centres).
+    pub metal_aware: bool,
+    /// Reject atoms whose valence is not allowed for their element and charge.
+    ///
+    /// Every atom is checked against [`Element::allowed_valences`](crate::Element::allowed_valences)
+    /// once its valence is known. Violations, such as a five-bonded carbon or
+    /// a three-bonded neutral oxygen, are always listed in
+    /// [`PerceptionReport::valence_errors`](crate::PerceptionReport::valence_errors);
+    /// when enabled they also abort the pipeline with
+    /// [`PerceptionError::InvalidValence`](crate::PerceptionError::InvalidValence).
+    pub strict_valence: bool,
+    /// Leave aromatic components without a Kekulé structure delocalised.
+    ///
+    /// By default a single aromatic component that admits no Kekulé
+    /// structure aborts the pipeline with
+    /// [`PerceptionError::InvalidAromaticInput`](crate::PerceptionError::InvalidAromaticInput)
+    /// or [`PerceptionError::KekulizationFailed`](crate::PerceptionError::KekulizationFailed).
+    /// When enabled, such components keep their aromatic bonds without
+    /// Kekulé orders and are reported as
+    /// [`PerceptionWarning::UnkekulizedComponent`](crate::PerceptionWarning::UnkekulizedComponent),
+    /// while the rest of the graph is perceived as usual.
+    pub lenient_kekulization: bool,
+}
+
+impl Default for PerceptionOptions {
+    fn default() -> Self {
+        Self {
+            aromaticity_model: AromaticityModel::default(),
+            min_aromatic_ring_size: None,
+            max_aromatic_ring_size: None,
+            peripheral_circuit: false,
+            guess_tautomers: true,
+            metal_aware: false,
+            strict_valence: false,
+            lenient_kekulization: false,
+        }
+    }
+}
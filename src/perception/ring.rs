@@ -3,26 +3,70 @@
 use crate::core::atom::AtomId;
 use crate::core::bond::BondId;
 use crate::perception::ChemicalPerception;
+use crate::perception::aromaticity::RingAromaticity;
 use std::collections::{HashMap, HashSet, VecDeque};
 
+/// Largest ring, in atoms, checked for interchangeable SSSR members.
+const MAX_AMBIGUITY_RING_SIZE: usize = 12;
+
 /// Canonical representation of a detected ring.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Ring {
-    /// Atoms that belong to the ring.
+    /// Atoms that belong to the ring, sorted by identifier.
     pub atom_ids: Vec<AtomId>,
-    /// Bonds that belong to the ring.
+    /// Bonds that belong to the ring, sorted by identifier.
     pub bond_ids: Vec<BondId>,
+    /// Atoms in the order they are visited when walking around the ring.
+    ///
+    /// The walk starts at the smallest atom identifier and proceeds towards
+    /// the smaller of its two ring neighbours.
+    pub path: Vec<AtomId>,
 }
 
 impl Ring {
-    /// Constructs a ring and normalises the atom/bond order.
-    pub fn new(mut atom_ids: Vec<AtomId>, mut bond_ids: Vec<BondId>) -> Self {
+    /// Constructs a ring from atoms listed in path order and normalises it.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Ring atoms in traversal order; the closing bond between the
+    ///   last and first atom is implied.
+    /// * `bond_ids` - Bonds that belong to the ring, in any order.
+    pub fn new(path: Vec<AtomId>, mut bond_ids: Vec<BondId>) -> Self {
+        let path = canonical_path(path);
+        let mut atom_ids = path.clone();
         atom_ids.sort_unstable();
         atom_ids.dedup();
         bond_ids.sort_unstable();
         bond_ids.dedup();
-        Self { atom_ids, bond_ids }
+        Self {
+            atom_ids,
+            bond_ids,
+            path,
+        }
+    }
+
+    /// Returns the number of atoms in the ring.
+    pub fn size(&self) -> usize {
+        self.atom_ids.len()
+    }
+}
+
+/// Rotates and orients a cyclic atom path so equal rings compare equal.
+fn canonical_path(mut path: Vec<AtomId>) -> Vec<AtomId> {
+    let Some(start) = path
+        .iter()
+        .enumerate()
+        .min_by_key(|&(_, id)| id)
+        .map(|(idx, _)| idx)
+    else {
+        return path;
+    };
+
+    path.rotate_left(start);
+    if path.len() > 2 && path[path.len() - 1] < path[1] {
+        path[1..].reverse();
     }
+    path
 }
 
 /// Aggregated ring metadata.
@@ -30,24 +74,264 @@ impl Ring {
 pub struct RingInfo {
     /// Rings forming the Smallest Set of Smallest Rings (SSSR).
     pub rings: Vec<Ring>,
+    /// Aromatic character of each ring, parallel to `rings`.
+    ///
+    /// Populated by aromaticity perception; empty before that stage runs.
+    pub aromaticity: Vec<RingAromaticity>,
+    /// Fused ring systems, i.e. groups of rings connected by shared bonds.
+    ///
+    /// Populated by aromaticity perception; empty before that stage runs.
+    pub systems: Vec<RingSystem>,
+    /// Atoms of rings that could replace an SSSR ring of the same size, one
+    /// entry per ring size, e.g. the sixth face of cubane. Empty when the
+    /// SSSR is unique.
+    pub ambiguities: Vec<SssrAmbiguity>,
+    /// Connected components whose independent rings could not all be found.
+    /// Empty when the SSSR is complete.
+    pub shortfalls: Vec<SssrShortfall>,
+}
+
+/// Rings of one size that are interchangeable in the SSSR.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SssrAmbiguity {
+    /// Number of atoms in each interchangeable ring.
+    pub ring_size: usize,
+    /// Atoms of the rings left out of the SSSR, sorted and de-duplicated.
+    pub atoms: Vec<AtomId>,
+}
+
+/// A connected component with fewer SSSR rings than its cyclomatic number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SssrShortfall {
+    /// Atoms of the component, sorted by identifier.
+    pub atoms: Vec<AtomId>,
+    /// Bonds of the component, sorted by identifier.
+    pub bonds: Vec<BondId>,
+    /// Independent rings implied by the component's topology.
+    pub expected_rings: usize,
+    /// Independent rings actually found.
+    pub found_rings: usize,
+}
+
+/// A group of SSSR rings connected through shared bonds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RingSystem {
+    /// Indices into [`RingInfo::rings`], sorted ascending.
+    pub ring_indices: Vec<usize>,
+    /// Aromatic character of the system taken as a whole.
+    pub aromaticity: RingAromaticity,
 }
 
 /// Computes the smallest set of smallest rings for a perceived molecule.
+///
+/// The shortest cycle through each bond is tried first. A component left
+/// short of its cyclomatic number by those candidates, as in the prismanes,
+/// is searched again with Horton's candidates. Components that are still
+/// short are listed in [`RingInfo::shortfalls`] with the rings found so far.
 pub fn find_sssr(perception: &ChemicalPerception) -> RingInfo {
     let num_components = count_components(perception);
+    let num_bonds = perception
+        .bonds
+        .iter()
+        .filter(|bond| !bond.is_detached())
+        .count();
     let cyclomatic_number =
-        perception.bonds.len() as isize - perception.atoms.len() as isize + num_components as isize;
+        num_bonds as isize - perception.atoms.len() as isize + num_components as isize;
 
     if cyclomatic_number <= 0 {
         return RingInfo::default();
     }
 
-    let candidates = enumerate_cycle_candidates(perception);
-    let selected_rings =
-        select_minimal_cycle_basis(perception, candidates, cyclomatic_number as usize);
+    let (mut candidates, ring_bonds) = enumerate_cycle_candidates(perception);
+    let mut selected_rings =
+        select_minimal_cycle_basis(perception, candidates.clone(), cyclomatic_number as usize);
+    let mut shortfalls = find_shortfalls(perception, &selected_rings);
+
+    if !shortfalls.is_empty() {
+        let short_bonds: HashSet<BondId> = shortfalls
+            .iter()
+            .flat_map(|shortfall| shortfall.bonds.iter().copied())
+            .collect();
+        let short_ring_bonds: Vec<BondId> = ring_bonds
+            .into_iter()
+            .filter(|bond_id| short_bonds.contains(bond_id))
+            .collect();
+        let seen_signatures: HashSet<Vec<BondId>> = candidates
+            .iter()
+            .map(|ring| ring.bond_ids.clone())
+            .collect();
+        candidates.extend(
+            horton_candidates(perception, &short_ring_bonds)
+                .into_iter()
+                .filter(|ring| !seen_signatures.contains(&ring.bond_ids)),
+        );
+        selected_rings =
+            select_minimal_cycle_basis(perception, candidates, cyclomatic_number as usize);
+        shortfalls = find_shortfalls(perception, &selected_rings);
+    }
+    let ambiguities = find_ambiguities(perception, &selected_rings);
 
     RingInfo {
         rings: selected_rings,
+        ambiguities,
+        shortfalls,
+        ..RingInfo::default()
+    }
+}
+
+/// Lists the components with fewer rings than their cyclomatic number.
+fn find_shortfalls(perception: &ChemicalPerception, rings: &[Ring]) -> Vec<SssrShortfall> {
+    let labels = component_labels(perception);
+    let component_count = labels.iter().max().map_or(0, |&label| label + 1);
+    let mut atoms = vec![Vec::new(); component_count];
+    let mut bonds = vec![Vec::new(); component_count];
+    let mut found = vec![0; component_count];
+
+    for (idx, atom) in perception.atoms.iter().enumerate() {
+        atoms[labels[idx]].push(atom.id);
+    }
+    for bond in perception.bonds.iter().filter(|bond| !bond.is_detached()) {
+        bonds[labels[perception.atom_id_to_index[&bond.start_atom_id]]].push(bond.id);
+    }
+    for ring in rings {
+        found[labels[perception.atom_id_to_index[&ring.atom_ids[0]]]] += 1;
+    }
+
+    atoms
+        .into_iter()
+        .zip(bonds)
+        .zip(found)
+        .filter(|((atoms, bonds), found)| found + atoms.len() < bonds.len() + 1)
+        .map(|((mut atoms, mut bonds), found_rings)| {
+            atoms.sort_unstable();
+            bonds.sort_unstable();
+            SssrShortfall {
+                expected_rings: bonds.len() + 1 - atoms.len(),
+                found_rings,
+                atoms,
+                bonds,
+            }
+        })
+        .collect()
+}
+
+/// Enumerates every simple cycle containing at most `max_size` atoms.
+///
+/// Unlike [`find_sssr`], which returns a cycle basis, this walks every simple
+/// cycle of the graph. The search is restricted to ring bonds (bonds that are
+/// not bridges), so acyclic substituents never widen the search space. The
+/// enumeration is bounded by `max_size`, which keeps it tractable for fused
+/// polycycles whose total cycle count grows exponentially.
+///
+/// # Arguments
+///
+/// * `perception` - Perception snapshot providing the molecular topology.
+/// * `max_size` - Largest cycle, in atoms, that should be reported.
+///
+/// # Returns
+///
+/// All simple cycles with between three and `max_size` atoms, ordered by size
+/// and then by their sorted atom identifiers.
+pub fn find_cycles(perception: &ChemicalPerception, max_size: usize) -> Vec<Ring> {
+    if max_size < 3 {
+        return Vec::new();
+    }
+
+    let ring_bonds: HashSet<BondId> = perception
+        .bonds
+        .iter()
+        .filter(|bond| {
+            shortest_path_excluding_bond(perception, bond.start_atom_id, bond.end_atom_id, bond.id)
+                .is_some()
+        })
+        .map(|bond| bond.id)
+        .collect();
+
+    if ring_bonds.is_empty() {
+        return Vec::new();
+    }
+
+    let mut search = CycleSearch {
+        perception,
+        ring_bonds: &ring_bonds,
+        max_size,
+        on_path: vec![false; perception.atoms.len()],
+        atom_path: Vec::new(),
+        bond_path: Vec::new(),
+        cycles: Vec::new(),
+    };
+
+    for start_idx in 0..perception.atoms.len() {
+        search.on_path[start_idx] = true;
+        search.atom_path.push(start_idx);
+        search.extend(start_idx, start_idx);
+        search.atom_path.pop();
+        search.on_path[start_idx] = false;
+    }
+
+    let mut cycles = search.cycles;
+    cycles.sort_by(|a, b| {
+        a.size()
+            .cmp(&b.size())
+            .then_with(|| a.atom_ids.cmp(&b.atom_ids))
+            .then_with(|| a.bond_ids.cmp(&b.bond_ids))
+    });
+    cycles
+}
+
+/// Depth-first state used by [`find_cycles`].
+///
+/// Each cycle is rooted at its lowest atom index and only extended through
+/// higher indices, and the two traversal directions are collapsed by
+/// requiring the second atom to have a lower index than the last one.
+struct CycleSearch<'a> {
+    perception: &'a ChemicalPerception,
+    ring_bonds: &'a HashSet<BondId>,
+    max_size: usize,
+    on_path: Vec<bool>,
+    atom_path: Vec<usize>,
+    bond_path: Vec<BondId>,
+    cycles: Vec<Ring>,
+}
+
+impl CycleSearch<'_> {
+    /// Extends the current path from `current_idx`, recording closed cycles.
+    fn extend(&mut self, start_idx: usize, current_idx: usize) {
+        for &(neighbor_idx, bond_id) in &self.perception.adjacency[current_idx] {
+            if !self.ring_bonds.contains(&bond_id) {
+                continue;
+            }
+
+            if neighbor_idx == start_idx {
+                let len = self.atom_path.len();
+                if len >= 3 && self.atom_path[1] < self.atom_path[len - 1] {
+                    let path = self
+                        .atom_path
+                        .iter()
+                        .map(|&idx| self.perception.atoms[idx].id)
+                        .collect();
+                    let mut bond_ids = self.bond_path.clone();
+                    bond_ids.push(bond_id);
+                    self.cycles.push(Ring::new(path, bond_ids));
+                }
+                continue;
+            }
+
+            if neighbor_idx < start_idx
+                || self.on_path[neighbor_idx]
+                || self.atom_path.len() >= self.max_size
+            {
+                continue;
+            }
+
+            self.on_path[neighbor_idx] = true;
+            self.atom_path.push(neighbor_idx);
+            self.bond_path.push(bond_id);
+            self.extend(start_idx, neighbor_idx);
+            self.bond_path.pop();
+            self.atom_path.pop();
+            self.on_path[neighbor_idx] = false;
+        }
     }
 }
 
@@ -57,15 +341,20 @@ struct PathData {
     bond_ids: Vec<BondId>,
 }
 
-/// Enumerates simple cycle candidates by removing each bond in turn.
-fn enumerate_cycle_candidates(perception: &ChemicalPerception) -> Vec<Ring> {
+/// Enumerates the shortest cycle through each bond as a basis candidate.
+///
+/// Also returns the ring bonds, i.e. the bonds that lie on some cycle.
+fn enumerate_cycle_candidates(perception: &ChemicalPerception) -> (Vec<Ring>, Vec<BondId>) {
     let mut candidates = Vec::new();
     let mut seen_signatures: HashSet<Vec<BondId>> = HashSet::new();
+    let mut ring_bonds = Vec::new();
 
-    for bond in &perception.bonds {
+    for bond in perception.bonds.iter().filter(|bond| !bond.is_detached()) {
         if let Some(path) =
             shortest_path_excluding_bond(perception, bond.start_atom_id, bond.end_atom_id, bond.id)
         {
+            ring_bonds.push(bond.id);
+
             let mut all_bond_ids = path.bond_ids;
             all_bond_ids.push(bond.id);
 
@@ -75,9 +364,137 @@ fn enumerate_cycle_candidates(perception: &ChemicalPerception) -> Vec<Ring> {
             }
         }
     }
+    (candidates, ring_bonds)
+}
+
+/// Builds Horton's candidate cycles, which contain a minimum cycle basis.
+///
+/// The shortest cycle through each bond can miss an independent ring: every
+/// bond of pentaprismane lies on a four-membered face, so neither
+/// five-membered end is found. For every ring atom `v` and ring bond `x-y`,
+/// the shortest paths from `v` to `x` and from `v` to `y`, closed by the
+/// bond, form a candidate when the two paths meet only at `v`. Paths follow
+/// the given ring bonds only, so acyclic substituents and complete
+/// components never widen the search.
+fn horton_candidates(perception: &ChemicalPerception, ring_bonds: &[BondId]) -> Vec<Ring> {
+    let ring_bond_set: HashSet<BondId> = ring_bonds.iter().copied().collect();
+    let mut roots: Vec<usize> = ring_bonds
+        .iter()
+        .flat_map(|bond_id| {
+            let bond = &perception.bonds[perception.bond_id_to_index[bond_id]];
+            [
+                perception.atom_id_to_index[&bond.start_atom_id],
+                perception.atom_id_to_index[&bond.end_atom_id],
+            ]
+        })
+        .collect();
+    roots.sort_unstable();
+    roots.dedup();
+
+    let mut candidates = Vec::new();
+    let mut on_first_path = vec![false; perception.atoms.len()];
+
+    for root_idx in roots {
+        let parent = shortest_path_tree(perception, root_idx, &ring_bond_set);
+
+        for bond_id in ring_bonds {
+            let bond = &perception.bonds[perception.bond_id_to_index[bond_id]];
+            let x_idx = perception.atom_id_to_index[&bond.start_atom_id];
+            let y_idx = perception.atom_id_to_index[&bond.end_atom_id];
+            let (Some(x_path), Some(y_path)) = (
+                path_to_root(&parent, root_idx, x_idx),
+                path_to_root(&parent, root_idx, y_idx),
+            ) else {
+                continue;
+            };
+            // A tree bond would close a degenerate cycle of length two.
+            if x_path.bonds.contains(bond_id) || y_path.bonds.contains(bond_id) {
+                continue;
+            }
+
+            for &idx in &x_path.atoms {
+                on_first_path[idx] = true;
+            }
+            let disjoint = y_path.atoms[..y_path.atoms.len() - 1]
+                .iter()
+                .all(|&idx| !on_first_path[idx]);
+            for &idx in &x_path.atoms {
+                on_first_path[idx] = false;
+            }
+            if !disjoint {
+                continue;
+            }
+
+            // Walk root -> x, cross the bond, then y -> root.
+            let path = x_path
+                .atoms
+                .iter()
+                .rev()
+                .chain(&y_path.atoms[..y_path.atoms.len() - 1])
+                .map(|&idx| perception.atoms[idx].id)
+                .collect();
+            let mut bond_ids = x_path.bonds;
+            bond_ids.extend(y_path.bonds);
+            bond_ids.push(*bond_id);
+            candidates.push(Ring::new(path, bond_ids));
+        }
+    }
     candidates
 }
 
+/// Atoms and bonds walked from an atom back to the root of a shortest-path
+/// tree. `atoms` starts at the atom and ends at the root.
+struct TreePath {
+    atoms: Vec<usize>,
+    bonds: Vec<BondId>,
+}
+
+/// Records the breadth-first parent of every atom reachable from `root_idx`
+/// through `allowed_bonds`.
+fn shortest_path_tree(
+    perception: &ChemicalPerception,
+    root_idx: usize,
+    allowed_bonds: &HashSet<BondId>,
+) -> Vec<Option<(usize, BondId)>> {
+    let mut parent: Vec<Option<(usize, BondId)>> = vec![None; perception.atoms.len()];
+    let mut visited = vec![false; perception.atoms.len()];
+    let mut queue = VecDeque::new();
+
+    visited[root_idx] = true;
+    queue.push_back(root_idx);
+    while let Some(current_idx) = queue.pop_front() {
+        for &(neighbor_idx, bond_id) in &perception.adjacency[current_idx] {
+            if !visited[neighbor_idx] && allowed_bonds.contains(&bond_id) {
+                visited[neighbor_idx] = true;
+                parent[neighbor_idx] = Some((current_idx, bond_id));
+                queue.push_back(neighbor_idx);
+            }
+        }
+    }
+    parent
+}
+
+/// Follows `parent` links from `atom_idx` to `root_idx`, or returns `None`
+/// when the atom is not in the root's tree.
+fn path_to_root(
+    parent: &[Option<(usize, BondId)>],
+    root_idx: usize,
+    atom_idx: usize,
+) -> Option<TreePath> {
+    let mut path = TreePath {
+        atoms: vec![atom_idx],
+        bonds: Vec::new(),
+    };
+    let mut cursor = atom_idx;
+    while cursor != root_idx {
+        let (prev_idx, bond_id) = parent[cursor]?;
+        path.atoms.push(prev_idx);
+        path.bonds.push(bond_id);
+        cursor = prev_idx;
+    }
+    Some(path)
+}
+
 /// Computes the shortest path between two atoms while omitting a bond.
 fn shortest_path_excluding_bond(
     perception: &ChemicalPerception,
@@ -144,13 +561,8 @@ fn select_minimal_cycle_basis(
     let mut basis: Vec<(BitVec, usize)> = Vec::new();
 
     for ring in candidates {
-        let mut bitvec = BitVec::from_bond_ids(&ring.bond_ids, &perception.bond_id_to_index);
-
-        for (basis_vec, pivot) in &basis {
-            if bitvec.test(*pivot) {
-                bitvec.xor(basis_vec);
-            }
-        }
+        let bitvec =
+            BitVec::from_bond_ids(&ring.bond_ids, &perception.bond_id_to_index).reduce(&basis);
 
         if !bitvec.is_zero() {
             let pivot = bitvec
@@ -171,27 +583,103 @@ fn select_minimal_cycle_basis(
     selected_rings
 }
 
+/// Finds rings that could replace a selected SSSR ring of the same size.
+///
+/// Every simple cycle up to the largest selected ring is compared against the
+/// selected rings that are strictly shorter than it. A cycle left out of the
+/// SSSR yet independent of those shorter rings can be exchanged for a selected
+/// ring of its own size. Only rings of up to [`MAX_AMBIGUITY_RING_SIZE`] atoms
+/// are examined, which keeps the cycle enumeration tractable.
+fn find_ambiguities(perception: &ChemicalPerception, selected: &[Ring]) -> Vec<SssrAmbiguity> {
+    let Some(largest) = selected.iter().map(Ring::size).max() else {
+        return Vec::new();
+    };
+
+    let selected_bonds: HashSet<&[BondId]> = selected
+        .iter()
+        .map(|ring| ring.bond_ids.as_slice())
+        .collect();
+    let mut ambiguities: Vec<SssrAmbiguity> = Vec::new();
+    let mut shorter_basis: Vec<(BitVec, usize)> = Vec::new();
+    let mut current_size = 0;
+
+    for cycle in find_cycles(perception, largest.min(MAX_AMBIGUITY_RING_SIZE)) {
+        if cycle.size() != current_size {
+            current_size = cycle.size();
+            shorter_basis = reduced_basis(perception, selected, current_size);
+        }
+        if selected_bonds.contains(cycle.bond_ids.as_slice()) {
+            continue;
+        }
+
+        let bitvec = BitVec::from_bond_ids(&cycle.bond_ids, &perception.bond_id_to_index);
+        if bitvec.reduce(&shorter_basis).is_zero() {
+            continue;
+        }
+
+        match ambiguities.last_mut() {
+            Some(ambiguity) if ambiguity.ring_size == cycle.size() => {
+                ambiguity.atoms.extend_from_slice(&cycle.atom_ids);
+                ambiguity.atoms.sort_unstable();
+                ambiguity.atoms.dedup();
+            }
+            _ => ambiguities.push(SssrAmbiguity {
+                ring_size: cycle.size(),
+                atoms: cycle.atom_ids,
+            }),
+        }
+    }
+
+    ambiguities
+}
+
+/// Builds an echelon basis from the selected rings shorter than `size`.
+fn reduced_basis(
+    perception: &ChemicalPerception,
+    selected: &[Ring],
+    size: usize,
+) -> Vec<(BitVec, usize)> {
+    let mut basis: Vec<(BitVec, usize)> = Vec::new();
+    for ring in selected.iter().filter(|ring| ring.size() < size) {
+        let bitvec =
+            BitVec::from_bond_ids(&ring.bond_ids, &perception.bond_id_to_index).reduce(&basis);
+        if let Some(pivot) = bitvec.leading_one() {
+            basis.push((bitvec, pivot));
+            basis.sort_by_key(|&(_, p)| p);
+        }
+    }
+    basis
+}
+
 /// Counts connected components in the perception adjacency list.
 fn count_components(perception: &ChemicalPerception) -> usize {
-    let mut visited = vec![false; perception.atoms.len()];
+    component_labels(perception)
+        .into_iter()
+        .max()
+        .map_or(0, |label| label + 1)
+}
+
+/// Labels every atom index with its connected component, numbered from zero.
+fn component_labels(perception: &ChemicalPerception) -> Vec<usize> {
+    let mut labels = vec![usize::MAX; perception.atoms.len()];
     let mut components = 0;
 
     for i in 0..perception.atoms.len() {
-        if !visited[i] {
-            components += 1;
+        if labels[i] == usize::MAX {
+            labels[i] = components;
             let mut stack = vec![i];
-            visited[i] = true;
             while let Some(current) = stack.pop() {
                 for &(neighbor_idx, _) in &perception.adjacency[current] {
-                    if !visited[neighbor_idx] {
-                        visited[neighbor_idx] = true;
+                    if labels[neighbor_idx] == usize::MAX {
+                        labels[neighbor_idx] = components;
                         stack.push(neighbor_idx);
                     }
                 }
             }
+            components += 1;
         }
     }
-    components
+    labels
 }
 
 /// Bit-set used for parity calculations during minimal cycle selection.
@@ -236,6 +724,21 @@ impl BitVec {
         }
     }
 
+    /// Eliminates the basis vectors from a copy of `self`.
+    ///
+    /// `basis` must be sorted by pivot, each pivot being its vector's leading
+    /// one. Vectors are applied from the highest pivot down, so a later XOR
+    /// never sets a pivot bit that was already cleared.
+    fn reduce(&self, basis: &[(Self, usize)]) -> Self {
+        let mut reduced = self.clone();
+        for (basis_vec, pivot) in basis.iter().rev() {
+            if reduced.test(*pivot) {
+                reduced.xor(basis_vec);
+            }
+        }
+        reduced
+    }
+
     /// Returns `true` when no bits are set.
     fn is_zero(&self) -> bool {
         self.data.iter().all(|&word| word == 0)
@@ -324,6 +827,7 @@ mod tests {
             atom_id_to_index,
             bond_id_to_index,
             ring_info: RingInfo::default(),
+            warnings: Vec::new(),
         }
     }
 
@@ -344,6 +848,7 @@ mod tests {
         let ring = &ring_info.rings[0];
         assert_eq!(ring.atom_ids, vec![0, 1, 2, 3]);
         assert_eq!(ring.bond_ids, vec![0, 1, 2, 3]);
+        assert_eq!(ring.path, vec![0, 1, 2, 3]);
     }
 
     #[test]
@@ -409,4 +914,190 @@ mod tests {
         bond_sets.sort();
         assert_eq!(bond_sets, vec![vec![0, 1, 2], vec![3, 4, 5]]);
     }
+
+    #[test]
+    fn find_sssr_reports_interchangeable_faces_of_a_cube() {
+        let perception = build_perception(&[
+            (0, 0, 1),
+            (1, 1, 2),
+            (2, 2, 3),
+            (3, 3, 0),
+            (4, 4, 5),
+            (5, 5, 6),
+            (6, 6, 7),
+            (7, 7, 4),
+            (8, 0, 4),
+            (9, 1, 5),
+            (10, 2, 6),
+            (11, 3, 7),
+        ]);
+
+        let ring_info = find_sssr(&perception);
+        assert_eq!(ring_info.rings.len(), 5);
+        assert_eq!(ring_info.ambiguities.len(), 1);
+
+        let ambiguity = &ring_info.ambiguities[0];
+        assert_eq!(ambiguity.ring_size, 4);
+        assert!(
+            ring_info
+                .rings
+                .iter()
+                .all(|ring| ring.atom_ids != ambiguity.atoms)
+        );
+    }
+
+    #[test]
+    fn find_sssr_separates_unique_fused_rings_from_interchangeable_bridges() {
+        // Naphthalene skeleton: the ten-membered perimeter is a larger ring.
+        let naphthalene = build_perception(&[
+            (0, 0, 1),
+            (1, 1, 2),
+            (2, 2, 3),
+            (3, 3, 4),
+            (4, 4, 5),
+            (5, 5, 0),
+            (6, 4, 6),
+            (7, 6, 7),
+            (8, 7, 8),
+            (9, 8, 9),
+            (10, 9, 5),
+        ]);
+        assert!(find_sssr(&naphthalene).ambiguities.is_empty());
+
+        // Bicyclo[2.2.2]octane: any two of the three six-membered rings.
+        let bicyclooctane = build_perception(&[
+            (0, 0, 1),
+            (1, 1, 2),
+            (2, 2, 3),
+            (3, 0, 4),
+            (4, 4, 5),
+            (5, 5, 3),
+            (6, 0, 6),
+            (7, 6, 7),
+            (8, 7, 3),
+        ]);
+        let ambiguities = find_sssr(&bicyclooctane).ambiguities;
+        assert_eq!(ambiguities.len(), 1);
+        assert_eq!(ambiguities[0].ring_size, 6);
+    }
+
+    #[test]
+    fn find_sssr_finds_the_polygonal_ends_of_prismanes() {
+        // Every bond of a prismane lies on a four-membered face, so the
+        // shortest cycle through each bond never includes an end polygon.
+        for sides in [5, 6] {
+            let mut edges = Vec::new();
+            for i in 0..sides {
+                let next = (i + 1) % sides;
+                edges.push((edges.len(), i, next));
+                edges.push((edges.len(), sides + i, sides + next));
+                edges.push((edges.len(), i, sides + i));
+            }
+            let perception = build_perception(&edges);
+
+            let ring_info = find_sssr(&perception);
+            assert!(ring_info.shortfalls.is_empty(), "{sides}-prismane");
+            let mut sizes: Vec<usize> = ring_info.rings.iter().map(Ring::size).collect();
+            sizes.sort_unstable();
+            let mut expected = vec![4; sides];
+            expected.push(sides);
+            assert_eq!(sizes, expected, "{sides}-prismane");
+        }
+    }
+
+    #[test]
+    fn shortfall_names_the_component_missing_rings() {
+        let perception = build_perception(&[
+            (0, 0, 1),
+            (1, 1, 2),
+            (2, 2, 0),
+            (3, 3, 4),
+            (4, 4, 5),
+            (5, 5, 3),
+        ]);
+        let ring_info = find_sssr(&perception);
+        assert!(ring_info.shortfalls.is_empty());
+        let first = ring_info
+            .rings
+            .into_iter()
+            .find(|ring| ring.atom_ids == vec![0, 1, 2])
+            .expect("first triangle missing");
+
+        assert_eq!(
+            find_shortfalls(&perception, &[first]),
+            vec![SssrShortfall {
+                atoms: vec![3, 4, 5],
+                bonds: vec![3, 4, 5],
+                expected_rings: 1,
+                found_rings: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn ring_new_orders_path_from_smallest_atom() {
+        let ring = Ring::new(vec![7, 3, 9, 5], vec![4, 1, 2, 3]);
+        assert_eq!(ring.atom_ids, vec![3, 5, 7, 9]);
+        assert_eq!(ring.bond_ids, vec![1, 2, 3, 4]);
+        assert_eq!(ring.path, vec![3, 7, 5, 9]);
+        assert_eq!(ring.size(), 4);
+    }
+
+    #[test]
+    fn find_cycles_returns_nothing_for_acyclic_graph() {
+        let perception = build_perception(&[(0, 0, 1), (1, 1, 2), (2, 1, 3)]);
+        assert!(find_cycles(&perception, 10).is_empty());
+    }
+
+    #[test]
+    fn find_cycles_enumerates_all_cycles_of_fused_bicycle() {
+        // Bicyclo[2.2.0]hexane-like graph: two squares sharing bond 0-3.
+        let perception = build_perception(&[
+            (0, 0, 1),
+            (1, 1, 2),
+            (2, 2, 3),
+            (3, 3, 0),
+            (4, 3, 4),
+            (5, 4, 5),
+            (6, 5, 0),
+        ]);
+
+        let cycles = find_cycles(&perception, 10);
+        let atom_sets: Vec<Vec<AtomId>> = cycles.iter().map(|c| c.atom_ids.clone()).collect();
+        assert_eq!(
+            atom_sets,
+            vec![vec![0, 1, 2, 3], vec![0, 3, 4, 5], vec![0, 1, 2, 3, 4, 5]]
+        );
+
+        let envelope = &cycles[2];
+        assert_eq!(envelope.bond_ids, vec![0, 1, 2, 4, 5, 6]);
+        assert_eq!(envelope.path, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn find_cycles_respects_size_bound_and_ignores_bridges() {
+        let perception = build_perception(&[
+            (0, 0, 1),
+            (1, 1, 2),
+            (2, 2, 3),
+            (3, 3, 0),
+            (4, 3, 4),
+            (5, 4, 5),
+            (6, 5, 0),
+            (7, 5, 6),
+            (8, 6, 7),
+            (9, 7, 8),
+            (10, 8, 6),
+        ]);
+
+        let small = find_cycles(&perception, 4);
+        let atom_sets: Vec<Vec<AtomId>> = small.iter().map(|c| c.atom_ids.clone()).collect();
+        assert_eq!(
+            atom_sets,
+            vec![vec![6, 7, 8], vec![0, 1, 2, 3], vec![0, 3, 4, 5]]
+        );
+
+        assert!(find_cycles(&perception, 2).is_empty());
+        assert_eq!(find_cycles(&perception, 6).len(), 4);
+    }
 }
@@ -1,5 +1,7 @@
 //! Atomic state perception including valence, lone pairs, and hybridization.
 
+use crate::core::bond::BondOrder;
+use crate::errors::PerceptionWarning;
 use crate::perception::{ChemicalPerception, PerceivedAtom};
 
 /// Hybridization states assigned to atoms during perception.
@@ -11,42 +13,133 @@ pub enum Hybridization {
     SP2,
     /// Tetrahedral `sp3` hybridization (steric number 4).
     SP3,
+    /// Trigonal bipyramidal `sp3d` hybridization (steric number 5).
+    SP3D,
+    /// Octahedral `sp3d2` hybridization (steric number 6).
+    SP3D2,
     /// Hybridization is unknown or outside the supported heuristics.
     Unknown,
 }
 
-/// Computes valence, lone pairs, and hybridization for each perceived atom.
+impl Hybridization {
+    /// Returns the steric number the hybridization accommodates, or `None`
+    /// for [`Hybridization::Unknown`].
+    pub fn steric_number(self) -> Option<u8> {
+        match self {
+            Self::SP => Some(2),
+            Self::SP2 => Some(3),
+            Self::SP3 => Some(4),
+            Self::SP3D => Some(5),
+            Self::SP3D2 => Some(6),
+            Self::Unknown => None,
+        }
+    }
+
+    fn from_steric_number(steric_number: u8) -> Self {
+        match steric_number {
+            2 => Self::SP,
+            3 => Self::SP2,
+            4 => Self::SP3,
+            5 => Self::SP3D,
+            6 => Self::SP3D2,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Molecular geometry around an atom predicted by VSEPR theory.
+///
+/// The shape follows from the steric number and how many of its electron
+/// domains are bonded neighbours rather than lone pairs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Geometry {
+    /// Two neighbours at 180°, e.g. CO₂ or XeF₂.
+    Linear,
+    /// Two neighbours with one or two lone pairs, e.g. water.
+    Bent,
+    /// Three neighbours in a plane, e.g. BF₃.
+    TrigonalPlanar,
+    /// Three neighbours with one lone pair, e.g. ammonia.
+    TrigonalPyramidal,
+    /// Three neighbours with two lone pairs, e.g. ClF₃.
+    TShaped,
+    /// Four neighbours, e.g. methane.
+    Tetrahedral,
+    /// Four neighbours with one lone pair, e.g. SF₄.
+    Seesaw,
+    /// Four neighbours with two lone pairs, e.g. XeF₄.
+    SquarePlanar,
+    /// Five neighbours, e.g. PCl₅.
+    TrigonalBipyramidal,
+    /// Five neighbours with one lone pair, e.g. IF₅.
+    SquarePyramidal,
+    /// Six neighbours, e.g. SF₆.
+    Octahedral,
+    /// Fewer than two neighbours, or a steric number outside the VSEPR table.
+    Unknown,
+}
+
+impl Geometry {
+    /// Looks up the VSEPR shape for a steric number and neighbour count.
+    fn from_vsepr(steric_number: u8, neighbors: u8) -> Self {
+        match (steric_number, neighbors) {
+            (2, 2) | (5, 2) | (6, 2) => Self::Linear,
+            (3, 2) | (4, 2) => Self::Bent,
+            (3, 3) => Self::TrigonalPlanar,
+            (4, 3) => Self::TrigonalPyramidal,
+            (5, 3) | (6, 3) => Self::TShaped,
+            (4, 4) => Self::Tetrahedral,
+            (5, 4) => Self::Seesaw,
+            (6, 4) => Self::SquarePlanar,
+            (5, 5) => Self::TrigonalBipyramidal,
+            (6, 5) => Self::SquarePyramidal,
+            (6, 6) => Self::Octahedral,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Computes valence, lone pairs, hybridization, and geometry for each
+/// perceived atom.
 pub fn perceive(perception: &mut ChemicalPerception) {
     compute_valence(perception);
     perceive_hybridization(perception);
+    perceive_geometry(perception);
+    record_warnings(perception);
 }
 
 /// Updates `total_valence` on every perceived atom.
 fn compute_valence(perception: &mut ChemicalPerception) {
     for atom in &mut perception.atoms {
-        atom.total_valence = 0;
+        atom.total_valence = atom.implicit_hydrogens;
     }
 
-    for bond in &perception.bonds {
+    for bond in perception.bonds.iter().filter(|bond| !bond.is_detached()) {
         let effective_order = bond.kekule_order.unwrap_or(bond.order);
-        let multiplicity = effective_order.multiplicity();
 
         if let Some(&start_idx) = perception.atom_id_to_index.get(&bond.start_atom_id) {
             perception.atoms[start_idx].total_valence = perception.atoms[start_idx]
                 .total_valence
-                .saturating_add(multiplicity);
+                .saturating_add(effective_order.valence_contribution(true));
         }
         if let Some(&end_idx) = perception.atom_id_to_index.get(&bond.end_atom_id) {
             perception.atoms[end_idx].total_valence = perception.atoms[end_idx]
                 .total_valence
-                .saturating_add(multiplicity);
+                .saturating_add(effective_order.valence_contribution(false));
         }
     }
 }
 
 /// Determines hybridization states and lone pair counts using heuristic rules.
 fn perceive_hybridization(perception: &mut ChemicalPerception) {
-    let lone_pairs: Vec<u8> = perception.atoms.iter().map(estimate_lone_pairs).collect();
+    let mut lone_pairs: Vec<u8> = perception.atoms.iter().map(estimate_lone_pairs).collect();
+    // A donor's lone pair is spent on its dative bonds.
+    for bond in &perception.bonds {
+        if bond.order == BondOrder::Dative && !bond.is_detached() {
+            let donor_idx = perception.atom_id_to_index[&bond.start_atom_id];
+            lone_pairs[donor_idx] = lone_pairs[donor_idx].saturating_sub(1);
+        }
+    }
 
     for (idx, atom) in perception.atoms.iter_mut().enumerate() {
         atom.lone_pairs = lone_pairs[idx];
@@ -57,12 +150,7 @@ fn perceive_hybridization(perception: &mut ChemicalPerception) {
         }
 
         let steric_number = atom.total_degree.saturating_add(atom.lone_pairs);
-        atom.hybridization = match steric_number {
-            2 => Hybridization::SP,
-            3 => Hybridization::SP2,
-            4 => Hybridization::SP3,
-            _ => Hybridization::Unknown,
-        };
+        atom.hybridization = Hybridization::from_steric_number(steric_number);
     }
 
     let snapshot: Vec<Hybridization> = perception
@@ -88,19 +176,72 @@ fn perceive_hybridization(perception: &mut ChemicalPerception) {
     }
 }
 
-/// Estimates lone pair count from valence electron bookkeeping.
-fn estimate_lone_pairs(atom: &PerceivedAtom) -> u8 {
-    let valence_electrons = match atom.element.valence_electrons() {
-        Some(e) => e as i16,
-        None => return 0,
-    };
+/// Sets the steric number and VSEPR geometry of every atom.
+///
+/// Atoms whose hybridization was lowered because a lone pair joined the π
+/// system count only the domains of their final hybridization, so pyrrole and
+/// amide nitrogens come out trigonal planar rather than pyramidal.
+fn perceive_geometry(perception: &mut ChemicalPerception) {
+    for atom in &mut perception.atoms {
+        atom.steric_number = atom
+            .hybridization
+            .steric_number()
+            .unwrap_or_else(|| atom.total_degree.saturating_add(atom.lone_pairs));
+        atom.geometry = Geometry::from_vsepr(atom.steric_number, atom.total_degree);
+    }
+}
 
-    let non_bonding_electrons =
-        valence_electrons - (atom.formal_charge as i16) - (atom.total_valence as i16);
+/// Warns about atoms whose state rests on a fallback rather than on the
+/// electron bookkeeping: missing valence electron counts, clamped lone-pair
+/// estimates, and central atoms without a known hybridization.
+fn record_warnings(perception: &mut ChemicalPerception) {
+    let mut warnings = Vec::new();
+    for atom in &perception.atoms {
+        match non_bonding_electrons(atom) {
+            None => warnings.push(PerceptionWarning::MissingValenceElectrons {
+                atom: atom.id,
+                element: atom.element,
+            }),
+            Some(electrons) if electrons < 0 => {
+                warnings.push(PerceptionWarning::ClampedLonePairs {
+                    atom: atom.id,
+                    deficit: electrons.unsigned_abs().min(u16::from(u8::MAX)) as u8,
+                });
+            }
+            Some(_) => {}
+        }
+        // Terminal atoms such as hydrogen have no hybridization to speak of.
+        if atom.hybridization == Hybridization::Unknown && atom.total_degree >= 2 {
+            warnings.push(PerceptionWarning::UnknownHybridization {
+                atom: atom.id,
+                steric_number: atom.steric_number,
+            });
+        }
+    }
+    perception.warnings.extend(warnings);
+}
 
+/// Estimates lone pair count from valence electron bookkeeping.
+///
+/// Unpaired electrons are set aside before the remaining non-bonding
+/// electrons are paired up.
+fn estimate_lone_pairs(atom: &PerceivedAtom) -> u8 {
+    let non_bonding_electrons = non_bonding_electrons(atom).unwrap_or(0);
     (non_bonding_electrons.max(0) / 2) as u8
 }
 
+/// Counts the paired non-bonding electrons left after bonds, charge and
+/// radicals; negative when those claim more electrons than the atom has.
+fn non_bonding_electrons(atom: &PerceivedAtom) -> Option<i16> {
+    let valence_electrons = i16::from(atom.element.valence_electrons()?);
+    Some(
+        valence_electrons
+            - i16::from(atom.effective_charge())
+            - i16::from(atom.total_valence)
+            - i16::from(atom.radical_electrons),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -268,6 +409,26 @@ mod tests {
         (perception, o)
     }
 
+    /// Builds a binary halide such as SF₆, with every halogen singly bonded
+    /// to the central atom.
+    fn build_halide(
+        centre: Element,
+        halogen: Element,
+        count: usize,
+    ) -> (ChemicalPerception, AtomId) {
+        let mut molecule = Molecule::new();
+        let c = molecule.add_atom(centre, 0);
+        for _ in 0..count {
+            let x = molecule.add_atom(halogen, 0);
+            molecule
+                .add_bond(c, x, BondOrder::Single)
+                .expect("failed to add halogen bond");
+        }
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception failed");
+        (perception, c)
+    }
+
     fn add_benzene_core(molecule: &mut Molecule) -> Vec<AtomId> {
         let carbons: Vec<AtomId> = (0..6).map(|_| molecule.add_atom(Element::C, 0)).collect();
         let mut bonds = Vec::new();
@@ -517,4 +678,184 @@ mod tests {
             "pyrrole nitrogen should be aromatic"
         );
     }
+
+    #[test]
+    fn radical_electrons_are_not_paired_into_lone_pairs() {
+        // Triplet methylene, CH2 with two unpaired electrons.
+        let mut molecule = Molecule::new();
+        let carbon = molecule.add_atom(Element::C, 0);
+        for _ in 0..2 {
+            let h = molecule.add_atom(Element::H, 0);
+            molecule
+                .add_bond(carbon, h, BondOrder::Single)
+                .expect("add C-H");
+        }
+        molecule
+            .set_radical_electrons(carbon, 2)
+            .expect("set radical electrons");
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception");
+        let idx = atom_index(&perception, carbon);
+        assert_eq!(perception.atoms[idx].lone_pairs, 0);
+        assert_eq!(perception.atoms[idx].hybridization, Hybridization::SP);
+
+        // Hydroxyl radical: one unpaired electron and two lone pairs.
+        let mut molecule = Molecule::new();
+        let oxygen = molecule.add_atom(Element::O, 0);
+        let h = molecule.add_atom(Element::H, 0);
+        molecule
+            .add_bond(oxygen, h, BondOrder::Single)
+            .expect("add O-H");
+        molecule
+            .set_radical_electrons(oxygen, 1)
+            .expect("set radical electrons");
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception");
+        let idx = atom_index(&perception, oxygen);
+        assert_eq!(perception.atoms[idx].lone_pairs, 2);
+    }
+
+    #[test]
+    fn hypervalent_centres_use_d_orbital_hybridization() {
+        let cases = [
+            (
+                Element::P,
+                Element::Cl,
+                5,
+                Hybridization::SP3D,
+                5,
+                Geometry::TrigonalBipyramidal,
+            ),
+            (
+                Element::S,
+                Element::F,
+                4,
+                Hybridization::SP3D,
+                5,
+                Geometry::Seesaw,
+            ),
+            (
+                Element::Cl,
+                Element::F,
+                3,
+                Hybridization::SP3D,
+                5,
+                Geometry::TShaped,
+            ),
+            (
+                Element::Xe,
+                Element::F,
+                2,
+                Hybridization::SP3D,
+                5,
+                Geometry::Linear,
+            ),
+            (
+                Element::S,
+                Element::F,
+                6,
+                Hybridization::SP3D2,
+                6,
+                Geometry::Octahedral,
+            ),
+            (
+                Element::I,
+                Element::F,
+                5,
+                Hybridization::SP3D2,
+                6,
+                Geometry::SquarePyramidal,
+            ),
+            (
+                Element::Xe,
+                Element::F,
+                4,
+                Hybridization::SP3D2,
+                6,
+                Geometry::SquarePlanar,
+            ),
+        ];
+        for (centre, halogen, count, hybridization, steric_number, geometry) in cases {
+            let (perception, atom) = build_halide(centre, halogen, count);
+            let atom = &perception.atoms[atom_index(&perception, atom)];
+            assert_eq!(
+                atom.hybridization, hybridization,
+                "{centre:?}{halogen:?}{count}"
+            );
+            assert_eq!(
+                atom.steric_number, steric_number,
+                "{centre:?}{halogen:?}{count}"
+            );
+            assert_eq!(atom.geometry, geometry, "{centre:?}{halogen:?}{count}");
+        }
+    }
+
+    #[test]
+    fn soft_anomalies_are_recorded_as_warnings() {
+        let (perception, _) = build_methane();
+        assert!(perception.warnings.is_empty());
+
+        // Pentavalent carbon claims one electron more than it has.
+        let (perception, carbon) = build_halide(Element::C, Element::F, 5);
+        assert!(
+            perception
+                .warnings
+                .contains(&PerceptionWarning::ClampedLonePairs {
+                    atom: carbon,
+                    deficit: 1,
+                })
+        );
+
+        // Seven domains fall outside the hybridization table.
+        let (perception, iodine) = build_halide(Element::I, Element::F, 7);
+        assert_eq!(
+            perception.warnings,
+            vec![PerceptionWarning::UnknownHybridization {
+                atom: iodine,
+                steric_number: 7,
+            }]
+        );
+
+        let (perception, zinc) = build_halide(Element::Zn, Element::Cl, 2);
+        assert!(
+            perception
+                .warnings
+                .contains(&PerceptionWarning::MissingValenceElectrons {
+                    atom: zinc,
+                    element: Element::Zn,
+                })
+        );
+    }
+
+    #[test]
+    fn geometry_follows_lone_pairs_and_conjugation() {
+        let (perception, carbon) = build_methane();
+        let atom = &perception.atoms[atom_index(&perception, carbon)];
+        assert_eq!(atom.geometry, Geometry::Tetrahedral);
+
+        let (perception, nitrogen) = build_ammonia();
+        let atom = &perception.atoms[atom_index(&perception, nitrogen)];
+        assert_eq!(atom.geometry, Geometry::TrigonalPyramidal);
+
+        let (perception, oxygen) = build_water();
+        let atom = &perception.atoms[atom_index(&perception, oxygen)];
+        assert_eq!(atom.geometry, Geometry::Bent);
+
+        let (perception, carbons) = build_ethene();
+        let atom = &perception.atoms[atom_index(&perception, carbons[0])];
+        assert_eq!(atom.geometry, Geometry::TrigonalPlanar);
+
+        let (perception, carbons) = build_ethyne();
+        let atom = &perception.atoms[atom_index(&perception, carbons[0])];
+        assert_eq!(atom.geometry, Geometry::Linear);
+
+        // The lone pair of pyrrole's nitrogen sits in the π system.
+        let (perception, nitrogen) = build_pyrrole();
+        let atom = &perception.atoms[atom_index(&perception, nitrogen)];
+        assert_eq!(atom.steric_number, 3);
+        assert_eq!(atom.geometry, Geometry::TrigonalPlanar);
+
+        // Terminal atoms have no shape of their own.
+        let (perception, oxygen) = build_hydroxide();
+        let atom = &perception.atoms[atom_index(&perception, oxygen)];
+        assert_eq!(atom.geometry, Geometry::Unknown);
+    }
 }
@@ -0,0 +1,430 @@
+//! Validation of explicit aromatic annotations and of atom valences.
+//!
+//! Aromaticity perception trusts `BondOrder::Aromatic` annotations. The first
+//! pass checks them up front so that malformed input is reported with the
+//! atoms and bonds at fault instead of surfacing later as an opaque Kekulé
+//! failure. The second pass runs once valences are known and flags atoms whose
+//! valence is impossible for their element and charge.
+
+use crate::core::atom::{AtomId, Element};
+use crate::core::bond::{BondId, BondOrder};
+use crate::errors::{AromaticFix, AromaticInputIssue, PerceptionError, ValenceError};
+use crate::perception::aromaticity::is_potential_sp2_hybrid;
+use crate::perception::kekulize::{
+    KekuleSearch, PiBondDemand, find_kekule_structure, pi_bond_demand,
+};
+use crate::perception::{ChemicalPerception, PerceptionOptions};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+/// Checks every explicitly aromatic bond and atom of the input.
+///
+/// # Errors
+///
+/// Returns [`PerceptionError::InvalidAromaticInput`] for the first problem
+/// found: an aromatic bond outside of a ring, an aromatic atom that cannot be
+/// sp2 hybridised, or an aromatic component whose π-bond-requiring atoms
+/// cannot be paired into a Kekulé structure even after the tautomer guesses
+/// permitted by `options`. The last check is skipped when
+/// `options.lenient_kekulization` is set, leaving such components to
+/// Kekulization.
+pub fn validate_explicit_aromaticity(
+    perception: &ChemicalPerception,
+    options: &PerceptionOptions,
+) -> Result<(), PerceptionError> {
+    let aromatic_bonds: Vec<usize> = perception
+        .bonds
+        .iter()
+        .enumerate()
+        .filter(|(_, bond)| bond.order == BondOrder::Aromatic)
+        .map(|(idx, _)| idx)
+        .collect();
+
+    for &bond_idx in &aromatic_bonds {
+        let bond = &perception.bonds[bond_idx];
+        if !bond.is_in_ring {
+            return Err(PerceptionError::InvalidAromaticInput {
+                issue: AromaticInputIssue::AcyclicBond,
+                atoms: sorted(vec![bond.start_atom_id, bond.end_atom_id]),
+                bonds: vec![bond.id],
+                suggested_fixes: vec![AromaticFix::MarkNonAromatic(bond.id)],
+            });
+        }
+    }
+
+    for (atom_idx, atom) in perception.atoms.iter().enumerate() {
+        let atom_aromatic_bonds = aromatic_bond_ids(perception, atom_idx);
+        if atom_aromatic_bonds.is_empty() || is_potential_sp2_hybrid(perception, atom_idx) {
+            continue;
+        }
+        return Err(PerceptionError::InvalidAromaticInput {
+            issue: AromaticInputIssue::NotSp2Capable,
+            atoms: vec![atom.id],
+            suggested_fixes: atom_aromatic_bonds
+                .iter()
+                .map(|&bond_id| AromaticFix::MarkNonAromatic(bond_id))
+                .collect(),
+            bonds: atom_aromatic_bonds,
+        });
+    }
+
+    if !options.lenient_kekulization {
+        for component in aromatic_components(perception, &aromatic_bonds) {
+            check_kekule_structure(perception, &component, options)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Lists every atom whose valence is not allowed for its element and charge.
+///
+/// Must run after atom state perception, which computes `total_valence`.
+/// Unpaired electrons occupy valence slots, so a methyl radical passes with
+/// three bonds. Elements without [`Element::allowed_valences`] are skipped.
+pub fn check_valences(perception: &ChemicalPerception) -> Vec<ValenceError> {
+    let mut errors: Vec<ValenceError> = perception
+        .atoms
+        .iter()
+        .filter_map(|atom| {
+            let charge = atom.effective_charge();
+            let allowed = atom.element.allowed_valences(charge)?;
+            let expected: Vec<u8> = allowed
+                .iter()
+                .filter_map(|valence| valence.checked_sub(atom.radical_electrons))
+                .collect();
+            (!expected.contains(&atom.total_valence)).then_some(ValenceError {
+                atom: atom.id,
+                element: atom.element,
+                charge,
+                actual: atom.total_valence,
+                expected,
+            })
+        })
+        .collect();
+    errors.sort_unstable_by_key(|error| error.atom);
+    errors
+}
+
+/// Returns the identifiers of the explicitly aromatic bonds at an atom, sorted.
+fn aromatic_bond_ids(perception: &ChemicalPerception, atom_idx: usize) -> Vec<BondId> {
+    let mut bond_ids: Vec<BondId> = perception.adjacency[atom_idx]
+        .iter()
+        .map(|&(_, bond_id)| bond_id)
+        .filter(|bond_id| {
+            perception.bonds[perception.bond_id_to_index[bond_id]].order == BondOrder::Aromatic
+        })
+        .collect();
+    bond_ids.sort_unstable();
+    bond_ids
+}
+
+/// Groups explicitly aromatic bonds into connected components.
+fn aromatic_components(
+    perception: &ChemicalPerception,
+    aromatic_bonds: &[usize],
+) -> Vec<Vec<usize>> {
+    let mut visited = vec![false; perception.bonds.len()];
+    let mut components = Vec::new();
+
+    for &seed in aromatic_bonds {
+        if visited[seed] {
+            continue;
+        }
+        visited[seed] = true;
+        let mut component = Vec::new();
+        let mut queue = VecDeque::from([seed]);
+
+        while let Some(bond_idx) = queue.pop_front() {
+            component.push(bond_idx);
+            let bond = &perception.bonds[bond_idx];
+            for atom_id in [bond.start_atom_id, bond.end_atom_id] {
+                let atom_idx = perception.atom_id_to_index[&atom_id];
+                for (_, neighbor_bond_id) in &perception.adjacency[atom_idx] {
+                    let neighbor_idx = perception.bond_id_to_index[neighbor_bond_id];
+                    if perception.bonds[neighbor_idx].order == BondOrder::Aromatic
+                        && !visited[neighbor_idx]
+                    {
+                        visited[neighbor_idx] = true;
+                        queue.push_back(neighbor_idx);
+                    }
+                }
+            }
+        }
+        components.push(component);
+    }
+    components
+}
+
+/// Checks that the atoms of a component that need a π bond can be paired up.
+fn check_kekule_structure(
+    perception: &ChemicalPerception,
+    component: &[usize],
+    options: &PerceptionOptions,
+) -> Result<(), PerceptionError> {
+    // An exhausted search is inconclusive; only report proven failures.
+    if find_kekule_structure(perception, component, options.guess_tautomers)
+        != KekuleSearch::NotFound
+    {
+        return Ok(());
+    }
+
+    let component_set: HashSet<usize> = component.iter().copied().collect();
+    let mut atom_ids = BTreeSet::new();
+    let mut required = BTreeSet::new();
+    for &bond_idx in component {
+        let bond = &perception.bonds[bond_idx];
+        for atom_id in [bond.start_atom_id, bond.end_atom_id] {
+            atom_ids.insert(atom_id);
+            let atom_idx = perception.atom_id_to_index[&atom_id];
+            if pi_bond_demand(perception, atom_idx, &component_set) == PiBondDemand::Required {
+                required.insert(atom_id);
+            }
+        }
+    }
+    let required: Vec<AtomId> = required.into_iter().collect();
+
+    let mut bonds: Vec<BondId> = component
+        .iter()
+        .map(|&bond_idx| perception.bonds[bond_idx].id)
+        .collect();
+    bonds.sort_unstable();
+
+    Err(PerceptionError::InvalidAromaticInput {
+        issue: AromaticInputIssue::NoKekuleStructure,
+        atoms: atom_ids.into_iter().collect(),
+        bonds,
+        suggested_fixes: suggest_fixes(perception, &required),
+    })
+}
+
+/// Suggests edits that remove one atom from the set needing a π bond.
+///
+/// Pyridine-type nitrogens are offered a hydrogen first; otherwise each
+/// carbon is offered a negative charge.
+fn suggest_fixes(perception: &ChemicalPerception, required: &[AtomId]) -> Vec<AromaticFix> {
+    let atom = |atom_id: &AtomId| &perception.atoms[perception.atom_id_to_index[atom_id]];
+
+    let hydrogen_fixes: Vec<AromaticFix> = required
+        .iter()
+        .filter(|atom_id| {
+            let atom = atom(atom_id);
+            matches!(atom.element, Element::N | Element::P) && atom.formal_charge == 0
+        })
+        .map(|&atom_id| AromaticFix::AddHydrogen(atom_id))
+        .collect();
+    if !hydrogen_fixes.is_empty() {
+        return hydrogen_fixes;
+    }
+
+    required
+        .iter()
+        .filter(|atom_id| atom(atom_id).element == Element::C)
+        .map(|&atom_id| AromaticFix::SetFormalCharge {
+            atom: atom_id,
+            charge: -1,
+        })
+        .collect()
+}
+
+/// Sorts a list of atom identifiers.
+fn sorted(mut atom_ids: Vec<AtomId>) -> Vec<AtomId> {
+    atom_ids.sort_unstable();
+    atom_ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::molecule::Molecule;
+
+    fn aromatic_ring(elements: &[Element]) -> (Molecule, Vec<AtomId>, Vec<BondId>) {
+        let mut molecule = Molecule::new();
+        let atoms: Vec<AtomId> = elements
+            .iter()
+            .map(|&element| molecule.add_atom(element, 0))
+            .collect();
+        let bonds = (0..atoms.len())
+            .map(|i| {
+                molecule
+                    .add_bond(atoms[i], atoms[(i + 1) % atoms.len()], BondOrder::Aromatic)
+                    .expect("failed to add ring bond")
+            })
+            .collect();
+        (molecule, atoms, bonds)
+    }
+
+    fn attach_hydrogen(molecule: &mut Molecule, atom: AtomId) {
+        let hydrogen = molecule.add_atom(Element::H, 0);
+        molecule
+            .add_bond(atom, hydrogen, BondOrder::Single)
+            .expect("failed to attach hydrogen");
+    }
+
+    fn validate(molecule: &Molecule) -> Result<(), PerceptionError> {
+        ChemicalPerception::from_graph(molecule).map(|_| ())
+    }
+
+    #[test]
+    fn benzene_and_pyrrole_pass_validation() {
+        let (benzene, _, _) = aromatic_ring(&[Element::C; 6]);
+        assert!(validate(&benzene).is_ok());
+
+        let (mut pyrrole, atoms, _) =
+            aromatic_ring(&[Element::N, Element::C, Element::C, Element::C, Element::C]);
+        attach_hydrogen(&mut pyrrole, atoms[0]);
+        assert!(validate(&pyrrole).is_ok());
+    }
+
+    #[test]
+    fn strict_pyrrole_without_hydrogen_suggests_adding_one_to_nitrogen() {
+        let (pyrrole, atoms, bonds) =
+            aromatic_ring(&[Element::N, Element::C, Element::C, Element::C, Element::C]);
+
+        let strict = PerceptionOptions {
+            guess_tautomers: false,
+            ..PerceptionOptions::default()
+        };
+        let result = ChemicalPerception::from_graph_with_options(&pyrrole, &strict).map(|_| ());
+        match result {
+            Err(PerceptionError::InvalidAromaticInput {
+                issue,
+                atoms: reported_atoms,
+                bonds: reported_bonds,
+                suggested_fixes,
+            }) => {
+                assert_eq!(issue, AromaticInputIssue::NoKekuleStructure);
+                assert_eq!(reported_atoms, atoms);
+                assert_eq!(reported_bonds, bonds);
+                assert_eq!(suggested_fixes, vec![AromaticFix::AddHydrogen(atoms[0])]);
+            }
+            other => panic!("expected invalid aromatic input, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn neutral_five_membered_carbocycle_suggests_a_carbanion() {
+        let (ring, atoms, _) = aromatic_ring(&[Element::C; 5]);
+
+        match validate(&ring) {
+            Err(PerceptionError::InvalidAromaticInput {
+                issue,
+                suggested_fixes,
+                ..
+            }) => {
+                assert_eq!(issue, AromaticInputIssue::NoKekuleStructure);
+                assert_eq!(suggested_fixes.len(), atoms.len());
+                assert!(suggested_fixes.contains(&AromaticFix::SetFormalCharge {
+                    atom: atoms[0],
+                    charge: -1,
+                }));
+            }
+            other => panic!("expected invalid aromatic input, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn aromatic_bond_on_sp3_carbon_is_rejected() {
+        let (mut molecule, atoms, _) = aromatic_ring(&[Element::C; 6]);
+        for _ in 0..2 {
+            attach_hydrogen(&mut molecule, atoms[0]);
+        }
+
+        match validate(&molecule) {
+            Err(PerceptionError::InvalidAromaticInput {
+                issue,
+                atoms: reported_atoms,
+                suggested_fixes,
+                ..
+            }) => {
+                assert_eq!(issue, AromaticInputIssue::NotSp2Capable);
+                assert_eq!(reported_atoms, vec![atoms[0]]);
+                assert_eq!(suggested_fixes.len(), 2);
+            }
+            other => panic!("expected invalid aromatic input, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn acyclic_aromatic_bond_is_rejected() {
+        let mut molecule = Molecule::new();
+        let c1 = molecule.add_atom(Element::C, 0);
+        let c2 = molecule.add_atom(Element::C, 0);
+        let bond = molecule
+            .add_bond(c1, c2, BondOrder::Aromatic)
+            .expect("failed to add bond");
+
+        match validate(&molecule) {
+            Err(PerceptionError::InvalidAromaticInput {
+                issue,
+                bonds,
+                suggested_fixes,
+                ..
+            }) => {
+                assert_eq!(issue, AromaticInputIssue::AcyclicBond);
+                assert_eq!(bonds, vec![bond]);
+                assert_eq!(suggested_fixes, vec![AromaticFix::MarkNonAromatic(bond)]);
+            }
+            other => panic!("expected invalid aromatic input, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn impossible_valences_are_listed() {
+        let mut molecule = Molecule::new();
+        // Five-bonded carbon.
+        let carbon = molecule.add_atom(Element::C, 0);
+        for _ in 0..5 {
+            attach_hydrogen(&mut molecule, carbon);
+        }
+        // Three-bonded neutral oxygen.
+        let oxygen = molecule.add_atom(Element::O, 0);
+        for _ in 0..3 {
+            attach_hydrogen(&mut molecule, oxygen);
+        }
+        // Methyl radical and hydronium are fine.
+        let radical = molecule.add_atom(Element::C, 0);
+        molecule
+            .set_implicit_hydrogens(radical, 3)
+            .expect("failed to set hydrogens");
+        molecule
+            .set_radical_electrons(radical, 1)
+            .expect("failed to set radical");
+        let hydronium = molecule.add_atom(Element::O, 1);
+        molecule
+            .set_implicit_hydrogens(hydronium, 3)
+            .expect("failed to set hydrogens");
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("lenient by default");
+        assert_eq!(
+            check_valences(&perception),
+            vec![
+                ValenceError {
+                    atom: carbon,
+                    element: Element::C,
+                    charge: 0,
+                    actual: 5,
+                    expected: vec![4],
+                },
+                ValenceError {
+                    atom: oxygen,
+                    element: Element::O,
+                    charge: 0,
+                    actual: 3,
+                    expected: vec![2],
+                },
+            ]
+        );
+
+        let strict = PerceptionOptions {
+            strict_valence: true,
+            ..PerceptionOptions::default()
+        };
+        match ChemicalPerception::from_graph_with_options(&molecule, &strict) {
+            Err(PerceptionError::InvalidValence { errors }) => {
+                assert_eq!(errors, check_valences(&perception));
+            }
+            Err(error) => panic!("expected InvalidValence, got {error}"),
+            Ok(_) => panic!("strict mode should reject the molecule"),
+        }
+    }
+}
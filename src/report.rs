@@ -0,0 +1,232 @@
+//! Aggregated results of a full perception run.
+//!
+//! [`PerceptionReport`] bundles the resonance systems with the ring-level
+//! metadata computed along the way, so callers that need more than the
+//! conjugated networks do not have to re-run individual stages.
+//! [`PerceptionOutcome`] is the lighter pairing of the systems with the
+//! warnings raised while finding them.
+
+use crate::core::atom::{AtomId, Element};
+use crate::core::bond::BondId;
+use crate::errors::{PerceptionWarning, ValenceError};
+use crate::perception::{ChemicalPerception, Geometry, Hybridization, Ring, RingAromaticity};
+use crate::resonance::{self, ResonanceSystem};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Hybridization and VSEPR shape perceived for a single atom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtomReport {
+    /// The atom.
+    pub atom: AtomId,
+    /// Hybridization of the atom, e.g. `SP3D2` for the sulfur of SF₆.
+    pub hybridization: Hybridization,
+    /// Bonded neighbours plus lone pairs that occupy hybrid orbitals.
+    pub steric_number: u8,
+    /// Shape of the atom's neighbours, e.g. square planar for the xenon of XeF₄.
+    pub geometry: Geometry,
+}
+
+/// A single SSSR ring together with its aromatic character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RingReport {
+    /// The ring's atoms and bonds.
+    pub ring: Ring,
+    /// Whether the ring on its own is aromatic, antiaromatic or neither.
+    pub aromaticity: RingAromaticity,
+}
+
+/// A fused ring system, i.e. SSSR rings connected through shared bonds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RingSystemReport {
+    /// Indices into [`PerceptionReport::rings`], sorted ascending.
+    pub rings: Vec<usize>,
+    /// Atoms of all member rings, sorted and de-duplicated.
+    pub atoms: Vec<AtomId>,
+    /// Bonds of all member rings, sorted and de-duplicated.
+    pub bonds: Vec<BondId>,
+    /// Whether the system taken as a whole is aromatic, antiaromatic or neither.
+    pub aromaticity: RingAromaticity,
+}
+
+/// Ligand atoms bound to a metal centre through one contiguous fragment.
+///
+/// A σ-bonded or dative ligand binds through a single atom; a hapto ligand
+/// such as η⁵-cyclopentadienyl binds through several bonded atoms at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetalLigand {
+    /// Ligand atoms bonded to the metal, sorted ascending.
+    pub atoms: Vec<AtomId>,
+    /// The detached metal–ligand bonds, sorted ascending.
+    pub bonds: Vec<BondId>,
+}
+
+impl MetalLigand {
+    /// Returns the number of contiguous ligand atoms bonded to the metal (the
+    /// η in η⁵-Cp).
+    pub fn hapticity(&self) -> usize {
+        self.atoms.len()
+    }
+}
+
+/// A metal atom and the ligands detached from it in metal-aware mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetalCentre {
+    /// The metal atom.
+    pub atom: AtomId,
+    /// Element of the metal atom.
+    pub element: Element,
+    /// Ligands ordered by their lowest atom identifier.
+    pub ligands: Vec<MetalLigand>,
+}
+
+/// Resonance systems together with the soft anomalies met while finding them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerceptionOutcome {
+    /// Connected networks of conjugated atoms and bonds.
+    pub systems: Vec<ResonanceSystem>,
+    /// Non-fatal warnings in pipeline order.
+    pub warnings: Vec<PerceptionWarning>,
+}
+
+/// Everything the perception pipeline learned about a molecular graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerceptionReport {
+    /// Per-atom hybridization and geometry, ordered by atom identifier.
+    pub atoms: Vec<AtomReport>,
+    /// Connected networks of conjugated atoms and bonds.
+    pub resonance_systems: Vec<ResonanceSystem>,
+    /// SSSR rings, in the order produced by ring perception.
+    pub rings: Vec<RingReport>,
+    /// Fused ring systems built from [`PerceptionReport::rings`].
+    pub ring_systems: Vec<RingSystemReport>,
+    /// Metal centres ordered by atom identifier; empty unless
+    /// [`PerceptionOptions::metal_aware`](crate::PerceptionOptions::metal_aware)
+    /// is set.
+    pub metal_centres: Vec<MetalCentre>,
+    /// Atoms whose valence is not allowed for their element and charge,
+    /// ordered by atom identifier.
+    pub valence_errors: Vec<ValenceError>,
+    /// Non-fatal warnings in pipeline order.
+    pub warnings: Vec<PerceptionWarning>,
+}
+
+impl PerceptionReport {
+    /// Assembles a report from a completed perception snapshot.
+    pub(crate) fn from_perception(perception: &ChemicalPerception) -> Self {
+        let ring_info = &perception.ring_info;
+
+        let rings = ring_info
+            .rings
+            .iter()
+            .zip(&ring_info.aromaticity)
+            .map(|(ring, &aromaticity)| RingReport {
+                ring: ring.clone(),
+                aromaticity,
+            })
+            .collect();
+
+        let ring_systems = ring_info
+            .systems
+            .iter()
+            .map(|system| {
+                let mut atoms = Vec::new();
+                let mut bonds = Vec::new();
+                for &ring_idx in &system.ring_indices {
+                    let ring = &ring_info.rings[ring_idx];
+                    atoms.extend_from_slice(&ring.atom_ids);
+                    bonds.extend_from_slice(&ring.bond_ids);
+                }
+                atoms.sort_unstable();
+                atoms.dedup();
+                bonds.sort_unstable();
+                bonds.dedup();
+
+                RingSystemReport {
+                    rings: system.ring_indices.clone(),
+                    atoms,
+                    bonds,
+                    aromaticity: system.aromaticity,
+                }
+            })
+            .collect();
+
+        let mut atoms: Vec<AtomReport> = perception
+            .atoms
+            .iter()
+            .map(|atom| AtomReport {
+                atom: atom.id,
+                hybridization: atom.hybridization,
+                steric_number: atom.steric_number,
+                geometry: atom.geometry,
+            })
+            .collect();
+        atoms.sort_unstable_by_key(|report| report.atom);
+
+        Self {
+            atoms,
+            resonance_systems: resonance::find_systems(perception),
+            rings,
+            ring_systems,
+            metal_centres: metal_centres(perception),
+            valence_errors: perception.valence_errors(),
+            warnings: perception.warnings.clone(),
+        }
+    }
+}
+
+/// Groups the detached metal–ligand bonds by metal and by ligand fragment.
+fn metal_centres(perception: &ChemicalPerception) -> Vec<MetalCentre> {
+    let mut links: BTreeMap<AtomId, Vec<(AtomId, BondId)>> = BTreeMap::new();
+    for bond in perception.bonds.iter().filter(|bond| bond.is_metal_ligand) {
+        let start_idx = perception.atom_id_to_index[&bond.start_atom_id];
+        let (metal, ligand) = if perception.atoms[start_idx].element.is_metal() {
+            (bond.start_atom_id, bond.end_atom_id)
+        } else {
+            (bond.end_atom_id, bond.start_atom_id)
+        };
+        links.entry(metal).or_default().push((ligand, bond.id));
+    }
+
+    links
+        .into_iter()
+        .map(|(metal, mut bound)| {
+            bound.sort_unstable();
+            let bound_atoms: BTreeSet<AtomId> = bound.iter().map(|&(atom, _)| atom).collect();
+
+            let mut assigned = BTreeSet::new();
+            let mut ligands = Vec::new();
+            for &seed in &bound_atoms {
+                if !assigned.insert(seed) {
+                    continue;
+                }
+                let mut atoms = vec![seed];
+                let mut stack = vec![seed];
+                while let Some(atom_id) = stack.pop() {
+                    let atom_idx = perception.atom_id_to_index[&atom_id];
+                    for &(neighbor_idx, _) in &perception.adjacency[atom_idx] {
+                        let neighbor_id = perception.atoms[neighbor_idx].id;
+                        if bound_atoms.contains(&neighbor_id) && assigned.insert(neighbor_id) {
+                            atoms.push(neighbor_id);
+                            stack.push(neighbor_id);
+                        }
+                    }
+                }
+                atoms.sort_unstable();
+
+                let mut bonds: Vec<BondId> = bound
+                    .iter()
+                    .filter(|(atom, _)| atoms.contains(atom))
+                    .map(|&(_, bond_id)| bond_id)
+                    .collect();
+                bonds.sort_unstable();
+                ligands.push(MetalLigand { atoms, bonds });
+            }
+
+            MetalCentre {
+                atom: metal,
+                element: perception.atoms[perception.atom_id_to_index[&metal]].element,
+                ligands,
+            }
+        })
+        .collect()
+}
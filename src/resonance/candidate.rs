@@ -2,7 +2,7 @@
 
 use crate::core::atom::Element;
 use crate::core::bond::BondOrder;
-use crate::perception::{ChemicalPerception, ConjugationRole, Hybridization};
+use crate::perception::{ChemicalPerception, ConjugationRole, Hybridization, PerceivedAtom};
 
 /// Marks atoms as conjugation candidates based on hybridization, charge, and
 /// hypervalent heuristics.
@@ -15,8 +15,10 @@ pub fn determine(perception: &mut ChemicalPerception) {
     reset_conjugation_state(perception);
     mark_hypervalent_bridges(perception);
     mark_intrinsic_pi_carriers(perception);
+    mark_empty_orbital_acceptors(perception);
     promote_lone_pair_donors(perception);
     promote_charged_carbons(perception);
+    promote_radical_centres(perception);
     finalize_candidate_flags(perception);
 }
 
@@ -49,6 +51,34 @@ fn mark_intrinsic_pi_carriers(perception: &mut ChemicalPerception) {
     }
 }
 
+/// Marks tricoordinate atoms with a vacant p orbital, such as neutral boron
+/// and aluminium or carbocations and silylium ions, as π acceptors.
+fn mark_empty_orbital_acceptors(perception: &mut ChemicalPerception) {
+    for atom in &mut perception.atoms {
+        if has_empty_p_orbital(atom) {
+            atom.conjugation_roles
+                .insert(ConjugationRole::EMPTY_ORBITAL_ACCEPTOR);
+        }
+    }
+}
+
+/// Checks whether an atom is tricoordinate with only σ bonds and no
+/// non-bonding electrons, leaving its p orbital empty.
+fn has_empty_p_orbital(atom: &PerceivedAtom) -> bool {
+    let charge = atom.effective_charge();
+    let is_acceptor_element = match atom.element {
+        Element::B | Element::Al | Element::Ga => charge == 0,
+        Element::C | Element::Si | Element::Ge => charge == 1,
+        _ => false,
+    };
+
+    is_acceptor_element
+        && atom.total_degree == 3
+        && atom.total_valence == 3
+        && atom.lone_pairs == 0
+        && atom.radical_electrons == 0
+}
+
 /// Identifies hypervalent bridge atoms and marks them accordingly.
 fn mark_hypervalent_bridges(perception: &mut ChemicalPerception) {
     for atom_idx in 0..perception.atoms.len() {
@@ -80,7 +110,7 @@ fn promote_lone_pair_donors(perception: &mut ChemicalPerception) {
             }
 
             if neighbor_roles.contains(ConjugationRole::HYPERVALENT_BRIDGE) {
-                if atom.formal_charge < 0 {
+                if atom.effective_charge() < 0 {
                     promote = true;
                     break;
                 }
@@ -95,7 +125,7 @@ fn promote_lone_pair_donors(perception: &mut ChemicalPerception) {
             continue;
         }
 
-        if atom.element == Element::O && atom.formal_charge == 0 && atom.total_degree > 1 {
+        if atom.element == Element::O && atom.effective_charge() == 0 && atom.total_degree > 1 {
             continue;
         }
 
@@ -113,8 +143,8 @@ fn promote_charged_carbons(perception: &mut ChemicalPerception) {
             continue;
         }
 
-        let is_delocalised =
-            (atom.formal_charge == 1 && atom.total_degree == 3) || atom.formal_charge == -1;
+        let is_delocalised = (atom.effective_charge() == 1 && atom.total_degree == 3)
+            || atom.effective_charge() == -1;
 
         if is_delocalised {
             atom.conjugation_roles
@@ -123,6 +153,16 @@ fn promote_charged_carbons(perception: &mut ChemicalPerception) {
     }
 }
 
+/// Promotes atoms carrying unpaired electrons to conjugation candidates, so
+/// that radical centres mediate conjugation like charged carbons do.
+fn promote_radical_centres(perception: &mut ChemicalPerception) {
+    for atom in &mut perception.atoms {
+        if atom.radical_electrons > 0 {
+            atom.conjugation_roles.insert(ConjugationRole::RADICAL);
+        }
+    }
+}
+
 /// Finalizes the candidate flags based on the assigned conjugation roles.
 fn finalize_candidate_flags(perception: &mut ChemicalPerception) {
     for atom in &mut perception.atoms {
@@ -134,7 +174,7 @@ fn finalize_candidate_flags(perception: &mut ChemicalPerception) {
 fn should_skip_intrinsic_pi(perception: &ChemicalPerception, atom_idx: usize) -> bool {
     let atom = &perception.atoms[atom_idx];
 
-    if atom.element == Element::O && atom.formal_charge == 0 && atom.total_degree > 1 {
+    if atom.element == Element::O && atom.effective_charge() == 0 && atom.total_degree > 1 {
         return perception.adjacency[atom_idx]
             .iter()
             .any(|&(neighbor_idx, _)| {
@@ -180,7 +220,7 @@ fn is_hypervalent_bridge(perception: &ChemicalPerception, atom_idx: usize) -> bo
         } else {
             let neighbor = &perception.atoms[neighbor_idx];
             if neighbor.lone_pairs > 0
-                || neighbor.formal_charge < 0
+                || neighbor.effective_charge() < 0
                 || neighbor.element.is_common_conjugation_element()
             {
                 has_sigma_partner = true;
@@ -552,4 +592,98 @@ mod tests {
             "bridging oxygen should remain outside the conjugated core"
         );
     }
+
+    #[test]
+    fn allyl_radical_centre_mediates_conjugation() {
+        let mut molecule = Molecule::new();
+        let carbons: Vec<AtomId> = (0..3).map(|_| molecule.add_atom(Element::C, 0)).collect();
+        molecule
+            .add_bond(carbons[0], carbons[1], BondOrder::Double)
+            .expect("add C=C");
+        molecule
+            .add_bond(carbons[1], carbons[2], BondOrder::Single)
+            .expect("add C-C");
+        for (&carbon, count) in carbons.iter().zip([2, 1, 2]) {
+            for _ in 0..count {
+                let h = molecule.add_atom(Element::H, 0);
+                molecule
+                    .add_bond(carbon, h, BondOrder::Single)
+                    .expect("add C-H");
+            }
+        }
+        molecule
+            .set_radical_electrons(carbons[2], 1)
+            .expect("set radical electrons");
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception");
+
+        let radical = &perception.atoms[index(&perception, carbons[2])];
+        assert!(radical.conjugation_roles.contains(ConjugationRole::RADICAL));
+        assert!(radical.is_conjugation_candidate);
+        assert_eq!(radical.hybridization, Hybridization::SP2);
+
+        let systems = crate::resonance::find_systems(&perception);
+        assert_eq!(systems.len(), 1);
+        assert_eq!(systems[0].atoms, carbons);
+    }
+
+    #[test]
+    fn boron_accepts_lone_pairs_from_adjacent_donors() {
+        // Aminoborane, H2B-NH2.
+        let mut molecule = Molecule::new();
+        let boron = molecule.add_atom(Element::B, 0);
+        let nitrogen = molecule.add_atom(Element::N, 0);
+        let bond = molecule
+            .add_bond(boron, nitrogen, BondOrder::Single)
+            .expect("add B-N");
+        for atom in [boron, nitrogen] {
+            for _ in 0..2 {
+                let h = molecule.add_atom(Element::H, 0);
+                molecule
+                    .add_bond(atom, h, BondOrder::Single)
+                    .expect("add X-H");
+            }
+        }
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception");
+
+        let boron_atom = &perception.atoms[index(&perception, boron)];
+        assert!(
+            boron_atom
+                .conjugation_roles
+                .contains(ConjugationRole::EMPTY_ORBITAL_ACCEPTOR)
+        );
+        let nitrogen_atom = &perception.atoms[index(&perception, nitrogen)];
+        assert!(
+            nitrogen_atom
+                .conjugation_roles
+                .contains(ConjugationRole::LONE_PAIR_DONOR)
+        );
+
+        let systems = crate::resonance::find_systems(&perception);
+        assert_eq!(systems.len(), 1);
+        assert_eq!(systems[0].atoms, vec![boron, nitrogen]);
+        assert_eq!(systems[0].bonds, vec![bond]);
+    }
+
+    #[test]
+    fn tetracoordinate_borate_is_not_an_acceptor() {
+        let mut molecule = Molecule::new();
+        let boron = molecule.add_atom(Element::B, -1);
+        for _ in 0..4 {
+            let h = molecule.add_atom(Element::H, 0);
+            molecule
+                .add_bond(boron, h, BondOrder::Single)
+                .expect("add B-H");
+        }
+
+        let perception = ChemicalPerception::from_graph(&molecule).expect("perception");
+
+        let borate = &perception.atoms[index(&perception, boron)];
+        assert!(
+            !borate
+                .conjugation_roles
+                .contains(ConjugationRole::EMPTY_ORBITAL_ACCEPTOR)
+        );
+    }
 }
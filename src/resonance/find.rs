@@ -2,8 +2,8 @@
 
 use super::system::ResonanceSystem;
 use crate::core::bond::BondOrder;
-use crate::perception::ChemicalPerception;
-use std::collections::{HashSet, VecDeque};
+use crate::perception::{ChemicalPerception, ConjugationRole, Hybridization, PerceivedBond};
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
 
 /// Traverses the perceived molecule and returns all resonance systems.
 ///
@@ -14,7 +14,8 @@ use std::collections::{HashSet, VecDeque};
 ///
 /// # Returns
 ///
-/// A vector of [`ResonanceSystem`] values sorted by bond identifiers.
+/// A vector of [`ResonanceSystem`] values sorted by bond identifiers. Each
+/// system spans one π plane, so systems may overlap at sp centres.
 pub fn find_systems(perception: &ChemicalPerception) -> Vec<ResonanceSystem> {
     if perception.bonds.is_empty() {
         return Vec::new();
@@ -30,8 +31,16 @@ fn find_and_expand_conjugated_bonds(perception: &ChemicalPerception) -> HashSet<
     let mut frontier: VecDeque<usize> = VecDeque::new();
 
     for (bond_idx, bond) in perception.bonds.iter().enumerate() {
-        let effective_order = bond.kekule_order.unwrap_or(bond.order);
-        if (bond.is_aromatic || matches!(effective_order, BondOrder::Double | BondOrder::Triple))
+        if bond.is_detached() {
+            continue;
+        }
+        let effective_order = effective_order(bond);
+        if (bond.is_aromatic
+            || matches!(
+                effective_order,
+                BondOrder::Double | BondOrder::Triple | BondOrder::Quadruple
+            )
+            || is_donor_acceptor_bond(perception, bond))
             && conjugated.insert(bond_idx)
         {
             frontier.push_back(bond_idx);
@@ -51,9 +60,11 @@ fn find_and_expand_conjugated_bonds(perception: &ChemicalPerception) -> HashSet<
 
             for &(_, neighbor_bond_id) in &perception.adjacency[atom_idx] {
                 let neighbor_bond_idx = perception.bond_id_to_index[&neighbor_bond_id];
-
-                if !conjugated.contains(&neighbor_bond_idx) {
-                    let neighbor_bond = &perception.bonds[neighbor_bond_idx];
+                let neighbor_bond = &perception.bonds[neighbor_bond_idx];
+                // A dative bond is a σ-only link and does not carry conjugation.
+                if neighbor_bond.order != BondOrder::Dative
+                    && !conjugated.contains(&neighbor_bond_idx)
+                {
                     let other_end_id = neighbor_bond.other_end(atom.id);
                     let other_end_idx = perception.atom_id_to_index[&other_end_id];
 
@@ -70,55 +81,241 @@ fn find_and_expand_conjugated_bonds(perception: &ChemicalPerception) -> HashSet<
     conjugated
 }
 
-/// Groups conjugated bonds into connected components and records their atoms.
+/// Checks whether a σ bond joins a lone-pair donor to an empty-orbital
+/// acceptor, giving it partial π character as in aminoboranes (B–N).
+fn is_donor_acceptor_bond(perception: &ChemicalPerception, bond: &PerceivedBond) -> bool {
+    if bond.is_detached() || bond.order == BondOrder::Dative {
+        return false;
+    }
+    let roles = |atom_id| perception.atoms[perception.atom_id_to_index[&atom_id]].conjugation_roles;
+    let (start, end) = (roles(bond.start_atom_id), roles(bond.end_atom_id));
+    let donor = ConjugationRole::LONE_PAIR_DONOR;
+    let acceptor = ConjugationRole::EMPTY_ORBITAL_ACCEPTOR;
+    (start.contains(donor) && end.contains(acceptor))
+        || (start.contains(acceptor) && end.contains(donor))
+}
+
+/// Groups conjugated bonds into resonance systems, one per π plane.
+///
+/// Conjugation runs between parallel p orbitals, so bonds are grouped through
+/// the orbitals they connect rather than through shared atoms. Most atoms
+/// offer one p orbital, but an sp centre (alkyne carbon, nitrile nitrogen,
+/// the central atom of an allene, ketene or CO₂) offers two orthogonal ones,
+/// as does a lone-pair terminus π-bonded to such a centre (the oxygen of a
+/// ketene). An allene therefore splits into two systems, while the two planes
+/// of CO₂ or of a diyne span the same atoms and are reported once.
 fn group_systems(
     perception: &ChemicalPerception,
     conjugated_bond_indices: &HashSet<usize>,
 ) -> Vec<ResonanceSystem> {
-    let mut systems = Vec::new();
-    let mut visited_bonds = vec![false; perception.bonds.len()];
-
-    for &start_bond_idx in conjugated_bond_indices {
-        if visited_bonds[start_bond_idx] {
-            continue;
+    let mut bond_indices: Vec<usize> = conjugated_bond_indices.iter().copied().collect();
+    bond_indices.sort_unstable();
+
+    let orbitals = PiOrbitals::new(perception, &bond_indices);
+    let mut parent: Vec<usize> = (0..orbitals.owners.len()).collect();
+    let mut links = Vec::new();
+    for &bond_idx in &bond_indices {
+        for (first, second, is_pi) in orbitals.links(perception, bond_idx) {
+            let (root_first, root_second) = (
+                find_root(&mut parent, first),
+                find_root(&mut parent, second),
+            );
+            parent[root_first] = root_second;
+            links.push((first, bond_idx, is_pi));
         }
+    }
 
-        let mut queue = VecDeque::new();
-        let mut system_bond_ids = Vec::new();
-        let mut system_atom_ids = HashSet::new();
+    let mut planes: BTreeMap<usize, (BTreeSet<usize>, BTreeSet<usize>, bool)> = BTreeMap::new();
+    for (orbital, bond_idx, is_pi) in links {
+        let root = find_root(&mut parent, orbital);
+        let (atoms, bonds, has_pi) = planes.entry(root).or_default();
+        let bond = &perception.bonds[bond_idx];
+        atoms.insert(bond.start_atom_id);
+        atoms.insert(bond.end_atom_id);
+        bonds.insert(bond.id);
+        *has_pi |= is_pi;
+    }
 
-        queue.push_back(start_bond_idx);
-        visited_bonds[start_bond_idx] = true;
+    let mut systems: Vec<ResonanceSystem> = planes
+        .into_values()
+        .filter(|(_, _, has_pi)| *has_pi)
+        .map(|(atoms, bonds, _)| {
+            ResonanceSystem::new(atoms.into_iter().collect(), bonds.into_iter().collect())
+        })
+        .collect();
 
-        while let Some(bond_idx) = queue.pop_front() {
-            let bond = &perception.bonds[bond_idx];
-            system_bond_ids.push(bond.id);
-            system_atom_ids.insert(bond.start_atom_id);
-            system_atom_ids.insert(bond.end_atom_id);
+    systems.sort_by(|a, b| a.bonds.cmp(&b.bonds).then_with(|| a.atoms.cmp(&b.atoms)));
+    systems.dedup();
+    systems
+}
+
+/// Returns the representative of an orbital's plane, compressing the path.
+fn find_root(parent: &mut [usize], orbital: usize) -> usize {
+    let mut root = orbital;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    let mut current = orbital;
+    while parent[current] != root {
+        let next = parent[current];
+        parent[current] = root;
+        current = next;
+    }
+    root
+}
+
+/// The p orbitals offered by the atoms of the conjugated bonds.
+struct PiOrbitals {
+    /// First orbital of each atom, indexed like `perception.atoms`.
+    first: Vec<usize>,
+    /// Number of orbitals of each atom: 0, 1 or 2.
+    counts: Vec<usize>,
+    /// Atom index owning each orbital.
+    owners: Vec<usize>,
+}
 
+impl PiOrbitals {
+    /// Assigns one or two orbitals to every atom of the conjugated bonds.
+    fn new(perception: &ChemicalPerception, bond_indices: &[usize]) -> Self {
+        let mut counts = vec![0; perception.atoms.len()];
+        for &bond_idx in bond_indices {
+            let bond = &perception.bonds[bond_idx];
             for atom_id in [bond.start_atom_id, bond.end_atom_id] {
                 let atom_idx = perception.atom_id_to_index[&atom_id];
-                for &(_, neighbor_bond_id) in &perception.adjacency[atom_idx] {
-                    let neighbor_bond_idx = perception.bond_id_to_index[&neighbor_bond_id];
-                    if conjugated_bond_indices.contains(&neighbor_bond_idx)
-                        && !visited_bonds[neighbor_bond_idx]
-                    {
-                        visited_bonds[neighbor_bond_idx] = true;
-                        queue.push_back(neighbor_bond_idx);
-                    }
+                counts[atom_idx] = 1;
+            }
+        }
+
+        for (atom_idx, count) in counts.iter_mut().enumerate() {
+            if *count == 1 && is_sp_centre(perception, atom_idx) {
+                *count = 2;
+            }
+        }
+        // A lone pair on a terminal atom fills the p orbital orthogonal to its
+        // π bond to an sp centre, as on the oxygen of a ketene.
+        for &bond_idx in bond_indices {
+            let bond = &perception.bonds[bond_idx];
+            if effective_order(bond) != BondOrder::Double {
+                continue;
+            }
+            let start = perception.atom_id_to_index[&bond.start_atom_id];
+            let end = perception.atom_id_to_index[&bond.end_atom_id];
+            for (terminus, centre) in [(start, end), (end, start)] {
+                let atom = &perception.atoms[terminus];
+                if is_sp_centre(perception, centre) && atom.total_degree == 1 && atom.lone_pairs > 0
+                {
+                    counts[terminus] = 2;
                 }
             }
         }
 
-        if !system_bond_ids.is_empty() {
-            let system =
-                ResonanceSystem::new(system_atom_ids.into_iter().collect(), system_bond_ids);
-            systems.push(system);
+        let mut first = Vec::with_capacity(counts.len());
+        let mut owners = Vec::new();
+        for (atom_idx, &count) in counts.iter().enumerate() {
+            first.push(owners.len());
+            owners.extend(std::iter::repeat_n(atom_idx, count));
+        }
+
+        Self {
+            first,
+            counts,
+            owners,
         }
     }
 
-    systems.sort_by(|a, b| a.bonds.cmp(&b.bonds));
-    systems
+    /// Lists the orbital pairs a conjugated bond connects, flagging those
+    /// joined by one of its π bonds.
+    ///
+    /// A single π bond occupies one orbital at each end; when both ends have
+    /// two orbitals, the remaining pair lies in the orthogonal plane and is
+    /// connected through the σ bond. A triple bond, or a σ bond between two
+    /// sp atoms, connects the orbitals pairwise. A σ bond to a single-orbital
+    /// substituent is aligned with the first plane, as in a planar conformer.
+    fn links(&self, perception: &ChemicalPerception, bond_idx: usize) -> Vec<(usize, usize, bool)> {
+        let bond = &perception.bonds[bond_idx];
+        let start = perception.atom_id_to_index[&bond.start_atom_id];
+        let end = perception.atom_id_to_index[&bond.end_atom_id];
+        let orbital = |atom_idx: usize, slot: usize| {
+            self.first[atom_idx] + slot.min(self.counts[atom_idx] - 1)
+        };
+        let both_split = self.counts[start] == 2 && self.counts[end] == 2;
+
+        match effective_order(bond) {
+            BondOrder::Double => {
+                let start_slot = self.pi_slot(perception, start, bond_idx);
+                let end_slot = self.pi_slot(perception, end, bond_idx);
+                let mut links = vec![(orbital(start, start_slot), orbital(end, end_slot), true)];
+                if both_split {
+                    links.push((
+                        orbital(start, 1 - start_slot),
+                        orbital(end, 1 - end_slot),
+                        false,
+                    ));
+                }
+                links
+            }
+            BondOrder::Triple | BondOrder::Quadruple => {
+                vec![
+                    (orbital(start, 0), orbital(end, 0), true),
+                    (orbital(start, 1), orbital(end, 1), true),
+                ]
+            }
+            _ => {
+                // Aromatic σ bonds and donor–acceptor bonds carry π character.
+                let is_pi = bond.is_aromatic || is_donor_acceptor_bond(perception, bond);
+                let mut links = vec![(orbital(start, 0), orbital(end, 0), is_pi)];
+                if both_split {
+                    links.push((orbital(start, 1), orbital(end, 1), false));
+                }
+                links
+            }
+        }
+    }
+
+    /// Picks the orbital an atom uses for the π bond of a double bond.
+    ///
+    /// A cumulated centre uses one orbital per double bond, in bond order;
+    /// every other atom uses its first orbital.
+    fn pi_slot(&self, perception: &ChemicalPerception, atom_idx: usize, bond_idx: usize) -> usize {
+        if self.counts[atom_idx] < 2 {
+            return 0;
+        }
+        let mut doubles: Vec<usize> = perception.adjacency[atom_idx]
+            .iter()
+            .map(|(_, bond_id)| perception.bond_id_to_index[bond_id])
+            .filter(|&idx| effective_order(&perception.bonds[idx]) == BondOrder::Double)
+            .collect();
+        doubles.sort_unstable();
+        doubles
+            .iter()
+            .position(|&idx| idx == bond_idx)
+            .unwrap_or(0)
+            .min(1)
+    }
+}
+
+/// Returns the Kekulé order of a bond when assigned, else its input order.
+fn effective_order(bond: &PerceivedBond) -> BondOrder {
+    bond.kekule_order.unwrap_or(bond.order)
+}
+
+/// Checks whether an atom is an sp centre with two π bonds, such as an
+/// alkyne carbon or the central carbon of an allene.
+fn is_sp_centre(perception: &ChemicalPerception, atom_idx: usize) -> bool {
+    if perception.atoms[atom_idx].hybridization != Hybridization::SP {
+        return false;
+    }
+    let pi_bonds: usize = perception.adjacency[atom_idx]
+        .iter()
+        .map(|(_, bond_id)| {
+            match effective_order(&perception.bonds[perception.bond_id_to_index[bond_id]]) {
+                BondOrder::Double => 1,
+                BondOrder::Triple | BondOrder::Quadruple => 2,
+                _ => 0,
+            }
+        })
+        .sum();
+    pi_bonds == 2
 }
 
 #[cfg(test)]
@@ -126,8 +323,9 @@ mod tests {
     use super::*;
     use crate::core::atom::{AtomId, Element};
     use crate::core::bond::{BondId, BondOrder};
+    use crate::molecule::Molecule;
     use crate::perception::{
-        ChemicalPerception, ConjugationRole, Hybridization, PerceivedAtom, PerceivedBond,
+        ChemicalPerception, ConjugationRole, Geometry, Hybridization, PerceivedAtom, PerceivedBond,
     };
     use std::collections::HashMap;
 
@@ -192,8 +390,8 @@ mod tests {
         let mut bond_id_to_index = HashMap::new();
 
         for (idx, bond) in bonds.iter().enumerate() {
-            let start = bond.start as usize;
-            let end = bond.end as usize;
+            let start = bond.start;
+            let end = bond.end;
             adjacency[start].push((end, bond.id));
             adjacency[end].push((start, bond.id));
             bond_id_to_index.insert(bond.id, idx);
@@ -205,6 +403,7 @@ mod tests {
                 is_in_ring: false,
                 is_aromatic: bond.is_aromatic,
                 kekule_order: bond.kekule_order,
+                is_metal_ligand: false,
             });
         }
 
@@ -222,10 +421,16 @@ mod tests {
                 element: atom.element,
                 formal_charge: 0,
                 total_degree: adjacency[idx].len() as u8,
+                implicit_hydrogens: 0,
+                tautomer_guessed: false,
+                radical_electrons: 0,
+                ligand_charge: 0,
                 total_valence: 0,
                 is_in_ring: false,
                 is_aromatic: false,
                 hybridization,
+                steric_number: hybridization.steric_number().unwrap_or(0),
+                geometry: Geometry::Unknown,
                 is_conjugation_candidate: atom.is_candidate,
                 lone_pairs: 0,
                 conjugation_roles: if atom.is_candidate {
@@ -244,6 +449,7 @@ mod tests {
             atom_id_to_index,
             bond_id_to_index,
             ring_info: Default::default(),
+            warnings: Vec::new(),
         }
     }
 
@@ -256,6 +462,7 @@ mod tests {
             atom_id_to_index: HashMap::new(),
             bond_id_to_index: HashMap::new(),
             ring_info: Default::default(),
+            warnings: Vec::new(),
         };
 
         assert!(find_systems(&perception).is_empty());
@@ -359,4 +566,115 @@ mod tests {
         assert_eq!(systems[0].atoms, vec![0, 1, 2]);
         assert_eq!(systems[0].bonds, vec![0, 3]);
     }
+
+    /// Builds a molecule from heavy-atom specs and bonds, fills every open
+    /// carbon valence with hydrogens, and runs the full pipeline.
+    fn perceive_chain(
+        elements: &[Element],
+        bonds: &[(usize, usize, BondOrder)],
+    ) -> ChemicalPerception {
+        let mut molecule = Molecule::new();
+        let atoms: Vec<AtomId> = elements
+            .iter()
+            .map(|&element| molecule.add_atom(element, 0))
+            .collect();
+        let mut valence = vec![0u8; atoms.len()];
+        for &(start, end, order) in bonds {
+            molecule
+                .add_bond(atoms[start], atoms[end], order)
+                .expect("failed to add bond");
+            valence[start] += order.multiplicity();
+            valence[end] += order.multiplicity();
+        }
+        for (idx, &element) in elements.iter().enumerate() {
+            if element == Element::C {
+                for _ in valence[idx]..4 {
+                    let h = molecule.add_atom(Element::H, 0);
+                    molecule
+                        .add_bond(atoms[idx], h, BondOrder::Single)
+                        .expect("failed to add hydrogen");
+                }
+            }
+        }
+        ChemicalPerception::from_graph(&molecule).expect("perception")
+    }
+
+    #[test]
+    fn allene_splits_into_two_orthogonal_systems() {
+        let perception = perceive_chain(
+            &[Element::C; 3],
+            &[(0, 1, BondOrder::Double), (1, 2, BondOrder::Double)],
+        );
+
+        let systems = find_systems(&perception);
+        assert_eq!(systems.len(), 2);
+        assert_eq!(systems[0].atoms, vec![0, 1]);
+        assert_eq!(systems[0].bonds, vec![0]);
+        assert_eq!(systems[1].atoms, vec![1, 2]);
+        assert_eq!(systems[1].bonds, vec![1]);
+    }
+
+    #[test]
+    fn ketene_oxygen_lone_pair_joins_the_orthogonal_plane() {
+        let perception = perceive_chain(
+            &[Element::C, Element::C, Element::O],
+            &[(0, 1, BondOrder::Double), (1, 2, BondOrder::Double)],
+        );
+
+        let systems = find_systems(&perception);
+        assert_eq!(systems.len(), 2);
+        assert_eq!(systems[0].atoms, vec![0, 1, 2]);
+        assert_eq!(systems[0].bonds, vec![0, 1]);
+        assert_eq!(systems[1].atoms, vec![1, 2]);
+        assert_eq!(systems[1].bonds, vec![1]);
+    }
+
+    #[test]
+    fn carbon_dioxide_planes_coincide_into_one_system() {
+        let perception = perceive_chain(
+            &[Element::O, Element::C, Element::O],
+            &[(0, 1, BondOrder::Double), (1, 2, BondOrder::Double)],
+        );
+
+        let systems = find_systems(&perception);
+        assert_eq!(systems.len(), 1);
+        assert_eq!(systems[0].atoms, vec![0, 1, 2]);
+        assert_eq!(systems[0].bonds, vec![0, 1]);
+    }
+
+    #[test]
+    fn diyne_planes_coincide_into_one_system() {
+        let perception = perceive_chain(
+            &[Element::C; 4],
+            &[
+                (0, 1, BondOrder::Triple),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Triple),
+            ],
+        );
+
+        let systems = find_systems(&perception);
+        assert_eq!(systems.len(), 1);
+        assert_eq!(systems[0].atoms, vec![0, 1, 2, 3]);
+        assert_eq!(systems[0].bonds, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn vinylacetylene_keeps_the_out_of_plane_alkyne_bond_separate() {
+        let perception = perceive_chain(
+            &[Element::C; 4],
+            &[
+                (0, 1, BondOrder::Double),
+                (1, 2, BondOrder::Single),
+                (2, 3, BondOrder::Triple),
+            ],
+        );
+
+        let systems = find_systems(&perception);
+        assert_eq!(systems.len(), 2);
+        assert_eq!(systems[0].atoms, vec![0, 1, 2, 3]);
+        assert_eq!(systems[0].bonds, vec![0, 1, 2]);
+        assert_eq!(systems[1].atoms, vec![2, 3]);
+        assert_eq!(systems[1].bonds, vec![2]);
+    }
 }
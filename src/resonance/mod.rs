@@ -7,7 +7,7 @@ pub mod candidate;
 mod find;
 mod system;
 
-/// Identifies conjugated components and constructs [`ResonanceSystem`] values.
+/// Identifies conjugated π networks and constructs [`ResonanceSystem`] values.
 pub use find::find_systems;
 /// Canonical representation of a resonance system.
 pub use system::ResonanceSystem;
@@ -3,7 +3,11 @@
 use crate::core::atom::AtomId;
 use crate::core::bond::BondId;
 
-/// Connected conjugated component identified by the resonance detector.
+/// Conjugated network in one π plane identified by the resonance detector.
+///
+/// Systems are grouped by π plane rather than by shared atoms, so two systems
+/// can overlap at an sp centre: they may share its atom and any triple bond
+/// that carries both planes.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ResonanceSystem {
     /// Stable atom identifiers that form the resonance system.
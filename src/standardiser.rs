@@ -0,0 +1,388 @@
+//! Normalisation of functional groups with more than one accepted drawing.
+//!
+//! Semipolar bonds can be drawn charge-separated (N⁺–O⁻, S⁺–O⁻, P⁺–C⁻) or as
+//! a double bond on a hypervalent centre (N=O, S=O, P=C). Perception treats
+//! the two drawings differently, so this module rewrites every recognised
+//! group into one [`StandardForm`] and records each rewrite.
+
+use crate::convert::ConvertedMolecule;
+use crate::core::atom::{AtomId, Element};
+use crate::core::bond::{BondId, BondOrder};
+use crate::perception::ChemicalPerception;
+
+/// Convention that [`standardise`](crate::standardise()) rewrites groups into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StandardForm {
+    /// Semipolar bonds as a single bond between opposite charges, e.g.
+    /// N⁺(=O)O⁻ for nitro groups. This is the form most toolkits write.
+    ChargeSeparated,
+    /// Semipolar bonds as a neutral double bond on a hypervalent centre, e.g.
+    /// N(=O)=O, the form recognised as a hypervalent resonance bridge.
+    Hypervalent,
+}
+
+/// Functional group rewritten by the standardiser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StandardisationRule {
+    /// Nitro and nitrate nitrogens carrying two terminal oxygens.
+    Nitro,
+    /// Amine and pyridine N-oxides.
+    NOxide,
+    /// Sulfoxides; sulfones and sulfates are left hypervalent.
+    Sulfoxide,
+    /// Phosphorus and sulfur ylides.
+    Ylide,
+}
+
+/// One semipolar bond rewritten by the standardiser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AppliedRule {
+    /// The functional group that was recognised.
+    pub rule: StandardisationRule,
+    /// The cationic or hypervalent centre, in source graph identifiers.
+    pub centre: AtomId,
+    /// The anionic or double-bonded partner, in source graph identifiers.
+    pub partner: AtomId,
+    /// The rewritten bond, in source graph identifiers.
+    pub bond: BondId,
+}
+
+/// A standardised copy of a molecular graph.
+#[derive(Debug, Clone)]
+pub struct Standardisation {
+    /// The rewritten molecule with mappings to the source identifiers.
+    pub molecule: ConvertedMolecule,
+    /// Every rewrite in the order it was applied.
+    pub applied: Vec<AppliedRule>,
+}
+
+/// A semipolar bond between a centre and a partner element.
+struct SemipolarPattern {
+    centre: Element,
+    partner: Element,
+    /// Valence of the centre when the bond is drawn as a double bond.
+    hypervalent_valence: u8,
+    /// Whether the partner must carry no other bonds or hydrogens.
+    terminal_partner: bool,
+}
+
+/// Patterns in priority order, so S=O is preferred over S=C.
+const PATTERNS: [SemipolarPattern; 4] = [
+    SemipolarPattern {
+        centre: Element::N,
+        partner: Element::O,
+        hypervalent_valence: 5,
+        terminal_partner: true,
+    },
+    SemipolarPattern {
+        centre: Element::S,
+        partner: Element::O,
+        hypervalent_valence: 4,
+        terminal_partner: true,
+    },
+    SemipolarPattern {
+        centre: Element::P,
+        partner: Element::C,
+        hypervalent_valence: 5,
+        terminal_partner: false,
+    },
+    SemipolarPattern {
+        centre: Element::S,
+        partner: Element::C,
+        hypervalent_valence: 4,
+        terminal_partner: false,
+    },
+];
+
+/// Rewrites every recognised semipolar bond of a bare topology into `form`.
+///
+/// Each centre is rewritten at most once, so a nitro group keeps one N=O in
+/// the charge-separated form. Aromatic bonds around a centre count as one
+/// π bond in total, which lets pyridine N-oxides be recognised either way.
+pub(crate) fn standardise(
+    mut perception: ChemicalPerception,
+    form: StandardForm,
+) -> Standardisation {
+    let mut applied = Vec::new();
+
+    for centre_idx in 0..perception.atoms.len() {
+        let Some((pattern, partner_idx, bond_idx)) = find_semipolar(&perception, centre_idx, form)
+        else {
+            continue;
+        };
+
+        let (order, centre_charge, partner_charge) = match form {
+            StandardForm::ChargeSeparated => (BondOrder::Single, 1, -1),
+            StandardForm::Hypervalent => (BondOrder::Double, 0, 0),
+        };
+        let rule = match pattern.partner {
+            Element::C => StandardisationRule::Ylide,
+            _ if pattern.centre == Element::S => StandardisationRule::Sulfoxide,
+            _ if terminal_oxygens(&perception, centre_idx) >= 2 => StandardisationRule::Nitro,
+            _ => StandardisationRule::NOxide,
+        };
+
+        perception.bonds[bond_idx].order = order;
+        perception.atoms[centre_idx].formal_charge = centre_charge;
+        perception.atoms[partner_idx].formal_charge = partner_charge;
+        applied.push(AppliedRule {
+            rule,
+            centre: perception.atoms[centre_idx].id,
+            partner: perception.atoms[partner_idx].id,
+            bond: perception.bonds[bond_idx].id,
+        });
+    }
+
+    Standardisation {
+        molecule: ConvertedMolecule::from_perception(&perception, |bond| bond.order),
+        applied,
+    }
+}
+
+/// Finds the lowest-indexed semipolar bond of `centre_idx` that is not yet
+/// drawn in `form`, returning the matched pattern, partner and bond indices.
+fn find_semipolar(
+    perception: &ChemicalPerception,
+    centre_idx: usize,
+    form: StandardForm,
+) -> Option<(&'static SemipolarPattern, usize, usize)> {
+    let centre = &perception.atoms[centre_idx];
+    let valence = bonded_valence(perception, centre_idx);
+    // The drawing a bond must currently have to be rewritten into `form`.
+    let (charge, valence_offset, order, partner_charge) = match form {
+        StandardForm::ChargeSeparated => (0, 0, BondOrder::Double, 0),
+        StandardForm::Hypervalent => (1, 1, BondOrder::Single, -1),
+    };
+    if centre.formal_charge != charge {
+        return None;
+    }
+
+    PATTERNS
+        .iter()
+        .filter(|pattern| {
+            pattern.centre == centre.element
+                && valence + valence_offset == pattern.hypervalent_valence
+        })
+        .find_map(|pattern| {
+            let mut partners: Vec<(usize, usize)> = perception.adjacency[centre_idx]
+                .iter()
+                .map(|&(partner_idx, bond_id)| (perception.bond_id_to_index[&bond_id], partner_idx))
+                .filter(|&(bond_idx, partner_idx)| {
+                    let partner = &perception.atoms[partner_idx];
+                    perception.bonds[bond_idx].order == order
+                        && partner.element == pattern.partner
+                        && partner.formal_charge == partner_charge
+                        && (!pattern.terminal_partner || partner.total_degree == 1)
+                })
+                .collect();
+            partners.sort_unstable();
+            partners
+                .first()
+                .map(|&(bond_idx, partner_idx)| (pattern, partner_idx, bond_idx))
+        })
+}
+
+/// Counts the oxygens bonded to an atom that carry nothing else.
+fn terminal_oxygens(perception: &ChemicalPerception, atom_idx: usize) -> usize {
+    perception.adjacency[atom_idx]
+        .iter()
+        .filter(|&&(neighbor_idx, _)| {
+            let neighbor = &perception.atoms[neighbor_idx];
+            neighbor.element == Element::O && neighbor.total_degree == 1
+        })
+        .count()
+}
+
+/// Sums the bond orders and hydrogens of an atom, counting its aromatic
+/// bonds together as one σ bond each plus a single π bond.
+fn bonded_valence(perception: &ChemicalPerception, atom_idx: usize) -> u8 {
+    let atom = &perception.atoms[atom_idx];
+    let mut valence = atom.implicit_hydrogens;
+    let mut has_aromatic_bond = false;
+    for (_, bond_id) in &perception.adjacency[atom_idx] {
+        let bond = &perception.bonds[perception.bond_id_to_index[bond_id]];
+        has_aromatic_bond |= bond.order == BondOrder::Aromatic;
+        valence = valence.saturating_add(
+            bond.order
+                .valence_contribution(bond.start_atom_id == atom.id),
+        );
+    }
+    if has_aromatic_bond {
+        valence = valence.saturating_add(1);
+    }
+    valence
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::{AtomView, BondView, MoleculeGraph};
+    use crate::molecule::Molecule;
+
+    fn run(molecule: &Molecule, form: StandardForm) -> Standardisation {
+        let perception = ChemicalPerception::from_topology(molecule).expect("topology failed");
+        standardise(perception, form)
+    }
+
+    fn charge(standardisation: &Standardisation, atom: AtomId) -> i8 {
+        let converted = &standardisation.molecule;
+        converted
+            .molecule
+            .atom(converted.atom_id(atom).expect("atom missing"))
+            .expect("atom missing")
+            .formal_charge()
+    }
+
+    fn order(standardisation: &Standardisation, bond: BondId) -> BondOrder {
+        let converted = &standardisation.molecule;
+        converted
+            .molecule
+            .bond(converted.bond_id(bond).expect("bond missing"))
+            .expect("bond missing")
+            .order()
+    }
+
+    fn add_methyl(molecule: &mut Molecule, atom: AtomId) -> AtomId {
+        let carbon = molecule.add_atom(Element::C, 0);
+        molecule
+            .set_implicit_hydrogens(carbon, 3)
+            .expect("failed to set hydrogens");
+        molecule
+            .add_bond(atom, carbon, BondOrder::Single)
+            .expect("failed to add methyl bond");
+        carbon
+    }
+
+    /// Builds nitromethane with the given N charge and N–O bond orders; the
+    /// first oxygen takes the charge that balances the nitrogen.
+    fn nitromethane(
+        nitrogen_charge: i8,
+        orders: [BondOrder; 2],
+    ) -> (Molecule, AtomId, [AtomId; 2], [BondId; 2]) {
+        let mut molecule = Molecule::new();
+        let nitrogen = molecule.add_atom(Element::N, nitrogen_charge);
+        add_methyl(&mut molecule, nitrogen);
+        let o0 = molecule.add_atom(Element::O, -nitrogen_charge);
+        let o1 = molecule.add_atom(Element::O, 0);
+        let b0 = molecule
+            .add_bond(nitrogen, o0, orders[0])
+            .expect("failed to add N-O bond");
+        let b1 = molecule
+            .add_bond(nitrogen, o1, orders[1])
+            .expect("failed to add N-O bond");
+        (molecule, nitrogen, [o0, o1], [b0, b1])
+    }
+
+    #[test]
+    fn nitro_groups_round_trip_between_forms() {
+        let (hypervalent, nitrogen, oxygens, bonds) =
+            nitromethane(0, [BondOrder::Double, BondOrder::Double]);
+        let separated = run(&hypervalent, StandardForm::ChargeSeparated);
+        assert_eq!(
+            separated.applied,
+            vec![AppliedRule {
+                rule: StandardisationRule::Nitro,
+                centre: nitrogen,
+                partner: oxygens[0],
+                bond: bonds[0],
+            }]
+        );
+        assert_eq!(charge(&separated, nitrogen), 1);
+        assert_eq!(charge(&separated, oxygens[0]), -1);
+        assert_eq!(order(&separated, bonds[0]), BondOrder::Single);
+        assert_eq!(order(&separated, bonds[1]), BondOrder::Double);
+
+        let (drawn, nitrogen, oxygens, bonds) =
+            nitromethane(1, [BondOrder::Single, BondOrder::Double]);
+        let restored = run(&drawn, StandardForm::Hypervalent);
+        assert_eq!(restored.applied.len(), 1);
+        assert_eq!(charge(&restored, nitrogen), 0);
+        assert_eq!(charge(&restored, oxygens[0]), 0);
+        assert_eq!(order(&restored, bonds[0]), BondOrder::Double);
+
+        // Groups already in the requested form are left alone.
+        assert!(
+            run(&drawn, StandardForm::ChargeSeparated)
+                .applied
+                .is_empty()
+        );
+        assert!(
+            run(&hypervalent, StandardForm::Hypervalent)
+                .applied
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn sulfoxides_are_rewritten_but_sulfones_are_not() {
+        let mut molecule = Molecule::new();
+        let sulfoxide = molecule.add_atom(Element::S, 0);
+        add_methyl(&mut molecule, sulfoxide);
+        add_methyl(&mut molecule, sulfoxide);
+        let oxygen = molecule.add_atom(Element::O, 0);
+        molecule
+            .add_bond(sulfoxide, oxygen, BondOrder::Double)
+            .expect("failed to add S=O bond");
+
+        let sulfone = molecule.add_atom(Element::S, 0);
+        add_methyl(&mut molecule, sulfone);
+        add_methyl(&mut molecule, sulfone);
+        for _ in 0..2 {
+            let oxygen = molecule.add_atom(Element::O, 0);
+            molecule
+                .add_bond(sulfone, oxygen, BondOrder::Double)
+                .expect("failed to add S=O bond");
+        }
+
+        let standardisation = run(&molecule, StandardForm::ChargeSeparated);
+        assert_eq!(standardisation.applied.len(), 1);
+        assert_eq!(
+            standardisation.applied[0].rule,
+            StandardisationRule::Sulfoxide
+        );
+        assert_eq!(charge(&standardisation, sulfoxide), 1);
+        assert_eq!(charge(&standardisation, oxygen), -1);
+        assert_eq!(charge(&standardisation, sulfone), 0);
+    }
+
+    #[test]
+    fn ylides_and_n_oxides_are_recognised() {
+        // Methylenetriphenylphosphorane, simplified to trimethyl.
+        let mut molecule = Molecule::new();
+        let phosphorus = molecule.add_atom(Element::P, 1);
+        for _ in 0..3 {
+            add_methyl(&mut molecule, phosphorus);
+        }
+        let methylene = molecule.add_atom(Element::C, -1);
+        molecule
+            .set_implicit_hydrogens(methylene, 2)
+            .expect("failed to set hydrogens");
+        molecule
+            .add_bond(phosphorus, methylene, BondOrder::Single)
+            .expect("failed to add P-C bond");
+
+        // Trimethylamine N-oxide.
+        let nitrogen = molecule.add_atom(Element::N, 1);
+        for _ in 0..3 {
+            add_methyl(&mut molecule, nitrogen);
+        }
+        let oxide = molecule.add_atom(Element::O, -1);
+        molecule
+            .add_bond(nitrogen, oxide, BondOrder::Single)
+            .expect("failed to add N-O bond");
+
+        let standardisation = run(&molecule, StandardForm::Hypervalent);
+        let rules: Vec<StandardisationRule> = standardisation
+            .applied
+            .iter()
+            .map(|applied| applied.rule)
+            .collect();
+        assert_eq!(
+            rules,
+            vec![StandardisationRule::Ylide, StandardisationRule::NOxide]
+        );
+        for atom in standardisation.molecule.molecule.atoms() {
+            assert_eq!(atom.formal_charge(), 0);
+        }
+    }
+}
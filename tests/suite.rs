@@ -3,9 +3,17 @@
 mod cases;
 
 use cases::ResonanceCase;
-use pauling::{ResonanceSystem, find_resonance_systems};
+use pauling::traits::{AtomView, BondView, MoleculeGraph};
+use pauling::{
+    BondOrder, ConvertedMolecule, Element, Geometry, Hybridization, MolecularFormula, Molecule,
+    OxidationStateModel, PerceptionError, PerceptionOptions, PerceptionStage, PerceptionWarning,
+    ResonanceSystem, RingAromaticity, StandardForm, StandardisationRule, assign_formal_charges,
+    assign_oxidation_states, find_resonance_systems, find_resonance_systems_with_options,
+    find_resonance_systems_with_warnings, molecular_formula, perceive, standardise,
+    to_aromatic_molecule, to_kekule_molecule,
+};
 use std::cmp::Ordering;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 fn system_cmp(a: &ResonanceSystem, b: &ResonanceSystem) -> Ordering {
     a.atoms.cmp(&b.atoms).then_with(|| a.bonds.cmp(&b.bonds))
@@ -69,3 +77,755 @@ fn catalog_has_unique_slugs() {
         );
     }
 }
+
+#[test]
+fn perception_report_matches_resonance_systems() {
+    for case in cases::ALL_CASES {
+        let molecule = (case.build)();
+        let systems = find_resonance_systems(&molecule).expect("perception should succeed");
+        let report =
+            perceive(&molecule, &PerceptionOptions::default()).expect("perception should succeed");
+
+        assert_eq!(
+            report.resonance_systems, systems,
+            "case {} ({}): report disagrees with find_resonance_systems",
+            case.slug, case.title
+        );
+        for system in &report.ring_systems {
+            assert!(
+                system.rings.iter().all(|&idx| idx < report.rings.len()),
+                "case {} ({}): ring system references an unknown ring",
+                case.slug,
+                case.title
+            );
+        }
+
+        // No well-formed catalog molecule is antiaromatic; the misplaced
+        // double bond of the histidine builder leaves its imidazole with
+        // four π electrons. A ring drawn with aromatic bonds stays aromatic,
+        // and one drawn with single bonds only is not.
+        let drawn_orders: HashMap<_, _> = molecule
+            .bonds()
+            .map(|bond| (bond.id(), bond.order()))
+            .collect();
+        for ring in &report.rings {
+            let orders: Vec<_> = ring
+                .ring
+                .bond_ids
+                .iter()
+                .map(|id| drawn_orders[id])
+                .collect();
+            let expected = if orders.iter().all(|&order| order == BondOrder::Aromatic) {
+                Some(RingAromaticity::Aromatic)
+            } else if orders.iter().all(|&order| order == BondOrder::Single) {
+                Some(RingAromaticity::NonAromatic)
+            } else {
+                None
+            };
+            assert!(
+                ring.aromaticity != RingAromaticity::Antiaromatic
+                    || case.slug == "histidine_zwitterion_kekule",
+                "case {} ({}): ring {:?} reported antiaromatic",
+                case.slug,
+                case.title,
+                ring.ring.atom_ids
+            );
+            if let Some(expected) = expected {
+                assert_eq!(
+                    ring.aromaticity, expected,
+                    "case {} ({}): ring {:?} misclassified",
+                    case.slug, case.title, ring.ring.atom_ids
+                );
+            }
+        }
+        for system in &report.ring_systems {
+            let all_aromatic = system
+                .rings
+                .iter()
+                .all(|&idx| report.rings[idx].aromaticity == RingAromaticity::Aromatic);
+            assert_eq!(
+                system.aromaticity == RingAromaticity::Aromatic,
+                all_aromatic,
+                "case {} ({}): ring system {:?} disagrees with its rings",
+                case.slug,
+                case.title,
+                system.rings
+            );
+        }
+    }
+}
+
+#[test]
+fn kekule_and_aromatic_drawings_classify_rings_alike() {
+    // The malformed Kekulé builders over-bond an atom that their aromatic
+    // counterparts do not.
+    let malformed = [
+        "acridine_kekule",
+        "guanine_kekule",
+        "histidine_zwitterion_kekule",
+    ];
+    let classify = |case: &ResonanceCase| {
+        let report = perceive(&(case.build)(), &PerceptionOptions::default())
+            .expect("perception should succeed");
+        let mut rings: Vec<_> = report
+            .rings
+            .into_iter()
+            .map(|ring| (ring.ring.atom_ids, ring.aromaticity))
+            .collect();
+        rings.sort_by(|a, b| a.0.cmp(&b.0));
+        rings
+    };
+
+    for case in cases::ALL_CASES {
+        let Some(stem) = case.slug.strip_suffix("_kekule") else {
+            continue;
+        };
+        if malformed.contains(&case.slug) {
+            continue;
+        }
+        let aromatic_case = cases::ALL_CASES
+            .iter()
+            .find(|other| other.slug.strip_suffix("_aromatic") == Some(stem))
+            .expect("every Kekulé case has an aromatic counterpart");
+        assert_eq!(
+            classify(case),
+            classify(aromatic_case),
+            "{} and {} classify their rings differently",
+            case.slug,
+            aromatic_case.slug
+        );
+    }
+}
+
+fn systems_in_original_ids(converted: &ConvertedMolecule) -> Vec<ResonanceSystem> {
+    let mut systems: Vec<_> = find_resonance_systems(&converted.molecule)
+        .expect("perception of converted molecule should succeed")
+        .into_iter()
+        .map(|system| {
+            ResonanceSystem::new(
+                system
+                    .atoms
+                    .iter()
+                    .map(|&id| converted.original_atom_ids[id])
+                    .collect(),
+                system
+                    .bonds
+                    .iter()
+                    .map(|&id| converted.original_bond_ids[id])
+                    .collect(),
+            )
+        })
+        .collect();
+    systems.sort_by(system_cmp);
+    systems
+}
+
+#[test]
+fn kekule_export_removes_aromatic_bonds_and_preserves_systems() {
+    for case in cases::ALL_CASES {
+        let molecule = (case.build)();
+        let converted = to_kekule_molecule(&molecule, &PerceptionOptions::default())
+            .expect("conversion should succeed");
+
+        assert!(
+            converted
+                .molecule
+                .bonds()
+                .all(|bond| bond.order() != BondOrder::Aromatic),
+            "case {} ({}): Kekulé export still contains aromatic bonds",
+            case.slug,
+            case.title
+        );
+
+        let mut expected = case.expected_systems();
+        expected.sort_by(system_cmp);
+        assert_eq!(
+            systems_in_original_ids(&converted),
+            expected,
+            "case {} ({}): Kekulé export changed the resonance systems",
+            case.slug,
+            case.title
+        );
+    }
+}
+
+#[test]
+fn converters_remap_sparse_identifiers_and_reject_self_loops() {
+    // Acetaldehyde with gaps in both identifier ranges.
+    let mut molecule = cases::TestMolecule::new();
+    molecule.add_atom(10, Element::C, 0);
+    molecule.add_atom(42, Element::C, 0);
+    molecule.add_atom(7, Element::O, 0);
+    molecule.add_bond(100, 10, 42, BondOrder::Single);
+    molecule.add_bond(3, 42, 7, BondOrder::Double);
+    for (id, carbon) in [(50, 10), (51, 10), (52, 10), (60, 42)] {
+        molecule.add_atom(id, Element::H, 0);
+        molecule.add_bond(id + 1000, carbon, id, BondOrder::Single);
+    }
+
+    let options = PerceptionOptions::default();
+    let converted = to_kekule_molecule(&molecule, &options).expect("valid topology");
+    assert_eq!(converted.original_atom_ids[..3], [10, 42, 7]);
+    assert_eq!(converted.original_bond_ids[..2], [100, 3]);
+    let carbonyl = converted.bond_id(3).expect("bond was converted");
+    assert_eq!(
+        converted
+            .molecule
+            .bond(carbonyl)
+            .expect("bond exists")
+            .order(),
+        BondOrder::Double
+    );
+    let assignment = assign_formal_charges(&molecule, &options).expect("valid topology");
+    assert!(assignment.discrepancies.is_empty());
+
+    molecule.add_bond(8, 42, 42, BondOrder::Single);
+    let is_self_loop =
+        |error: PerceptionError| matches!(error, PerceptionError::SelfLoop { bond: 8, atom: 42 });
+    assert!(is_self_loop(
+        to_kekule_molecule(&molecule, &options).expect_err("self-loop must fail")
+    ));
+    assert!(is_self_loop(
+        to_aromatic_molecule(&molecule, &options).expect_err("self-loop must fail")
+    ));
+    assert!(is_self_loop(
+        assign_formal_charges(&molecule, &options).expect_err("self-loop must fail")
+    ));
+    assert!(is_self_loop(
+        standardise(&molecule, StandardForm::ChargeSeparated).expect_err("self-loop must fail")
+    ));
+}
+
+fn aromatic_bond_orders(case: &ResonanceCase) -> HashMap<usize, BondOrder> {
+    let molecule = (case.build)();
+    let converted = to_aromatic_molecule(&molecule, &PerceptionOptions::default())
+        .expect("conversion should succeed");
+    converted
+        .molecule
+        .bonds()
+        .map(|bond| (converted.original_bond_ids[bond.id()], bond.order()))
+        .collect()
+}
+
+#[test]
+fn aromatic_export_normalises_kekule_and_aromatic_drawings() {
+    // The histidine and guanine Kekulé builders are not valence-correct
+    // Kekulé structures, so they are left out of this comparison.
+    let pairs = [
+        (
+            &cases::phenylalanine_zwitterion_aromatic,
+            &cases::phenylalanine_zwitterion_kekule,
+        ),
+        (
+            &cases::tryptophan_zwitterion_aromatic,
+            &cases::tryptophan_zwitterion_kekule,
+        ),
+        (
+            &cases::tyrosine_zwitterion_aromatic,
+            &cases::tyrosine_zwitterion_kekule,
+        ),
+        (&cases::adenine_aromatic, &cases::adenine_kekule),
+        (&cases::cytosine_aromatic, &cases::cytosine_kekule),
+        (&cases::thymine_aromatic, &cases::thymine_kekule),
+        (&cases::uracil_aromatic, &cases::uracil_kekule),
+        (&cases::adenosine_aromatic, &cases::adenosine_kekule),
+        (
+            &cases::deoxyadenosine_aromatic,
+            &cases::deoxyadenosine_kekule,
+        ),
+        (&cases::acridine_aromatic, &cases::acridine_kekule),
+        (
+            &cases::methyl_pyridine_aromatic,
+            &cases::methyl_pyridine_kekule,
+        ),
+        (
+            &cases::trinitrobenzene_aromatic,
+            &cases::trinitrobenzene_kekule,
+        ),
+    ];
+
+    for (aromatic_case, kekule_case) in pairs {
+        assert_eq!(
+            aromatic_bond_orders(aromatic_case),
+            aromatic_bond_orders(kekule_case),
+            "{} and {} normalise to different aromatic forms",
+            aromatic_case.slug,
+            kekule_case.slug
+        );
+    }
+}
+
+fn build_ferrocene() -> Molecule {
+    let mut molecule = Molecule::new();
+    let iron = molecule.add_atom(Element::Fe, 0);
+    for _ in 0..2 {
+        let carbons: Vec<_> = (0..5).map(|_| molecule.add_atom(Element::C, 0)).collect();
+        for i in 0..5 {
+            let order = if i == 0 || i == 2 {
+                BondOrder::Double
+            } else {
+                BondOrder::Single
+            };
+            molecule
+                .add_bond(carbons[i], carbons[(i + 1) % 5], order)
+                .expect("failed to add ring bond");
+            let hydrogen = molecule.add_atom(Element::H, 0);
+            molecule
+                .add_bond(carbons[i], hydrogen, BondOrder::Single)
+                .expect("failed to add hydrogen");
+            molecule
+                .add_bond(iron, carbons[i], BondOrder::Single)
+                .expect("failed to add metal bond");
+        }
+    }
+    molecule
+}
+
+#[test]
+fn metal_aware_report_lists_hapto_ligands() {
+    let ferrocene = build_ferrocene();
+    let options = PerceptionOptions {
+        metal_aware: true,
+        ..PerceptionOptions::default()
+    };
+    let report = perceive(&ferrocene, &options).expect("perception should succeed");
+
+    assert_eq!(report.rings.len(), 2);
+    assert!(
+        report
+            .rings
+            .iter()
+            .all(|ring| ring.aromaticity == RingAromaticity::Aromatic)
+    );
+    assert_eq!(report.resonance_systems.len(), 2);
+
+    assert_eq!(report.metal_centres.len(), 1);
+    let centre = &report.metal_centres[0];
+    assert_eq!(centre.element, Element::Fe);
+    assert_eq!(centre.ligands.len(), 2);
+    for (ligand, ring) in centre.ligands.iter().zip(&report.rings) {
+        assert_eq!(ligand.hapticity(), 5);
+        assert_eq!(ligand.atoms, ring.ring.atom_ids);
+    }
+
+    let default_report =
+        perceive(&ferrocene, &PerceptionOptions::default()).expect("perception should succeed");
+    assert!(default_report.metal_centres.is_empty());
+}
+
+#[test]
+fn report_gives_hybridization_and_geometry_per_atom() {
+    let perchlorate = (cases::perchlorate_anion_delocalization.build)();
+    let report =
+        perceive(&perchlorate, &PerceptionOptions::default()).expect("perception should succeed");
+    assert_eq!(report.atoms.len(), perchlorate.atoms().count());
+    let chlorine = &report.atoms[0];
+    assert_eq!(chlorine.hybridization, Hybridization::SP3);
+    assert_eq!(chlorine.steric_number, 4);
+    assert_eq!(chlorine.geometry, Geometry::Tetrahedral);
+
+    let mut sulfur_hexafluoride = Molecule::new();
+    let sulfur = sulfur_hexafluoride.add_atom(Element::S, 0);
+    for _ in 0..6 {
+        let fluorine = sulfur_hexafluoride.add_atom(Element::F, 0);
+        sulfur_hexafluoride
+            .add_bond(sulfur, fluorine, BondOrder::Single)
+            .expect("failed to add S-F bond");
+    }
+    let report = perceive(&sulfur_hexafluoride, &PerceptionOptions::default())
+        .expect("perception should succeed");
+    let centre = report
+        .atoms
+        .iter()
+        .find(|atom| atom.atom == sulfur)
+        .expect("sulfur should be reported");
+    assert_eq!(centre.hybridization, Hybridization::SP3D2);
+    assert_eq!(centre.steric_number, 6);
+    assert_eq!(centre.geometry, Geometry::Octahedral);
+}
+
+#[test]
+fn catalog_formal_charges_are_consistent() {
+    // These builders misplace a double bond, leaving atoms above or below
+    // their valence; the charge check is expected to catch them.
+    let malformed = [
+        "acridine_aromatic",
+        "acridine_kekule",
+        "guanine_kekule",
+        "histidine_zwitterion_kekule",
+    ];
+    for case in cases::ALL_CASES {
+        let molecule = (case.build)();
+        let assignment = assign_formal_charges(&molecule, &PerceptionOptions::default())
+            .expect("perception should succeed");
+        assert_eq!(
+            assignment.discrepancies.is_empty(),
+            !malformed.contains(&case.slug),
+            "{}: {:?}",
+            case.slug,
+            assignment.discrepancies
+        );
+    }
+}
+
+#[test]
+fn standardiser_round_trips_charge_separated_groups() {
+    let cases_and_rules = [
+        (
+            &cases::trinitrobenzene_kekule,
+            vec![StandardisationRule::Nitro; 3],
+        ),
+        (
+            &cases::dimethyl_sulfoxide_polar_bond,
+            vec![StandardisationRule::Sulfoxide],
+        ),
+    ];
+    for (case, rules) in cases_and_rules {
+        let molecule = (case.build)();
+        let hypervalent =
+            standardise(&molecule, StandardForm::Hypervalent).expect("valid topology");
+        let applied: Vec<_> = hypervalent.applied.iter().map(|rule| rule.rule).collect();
+        assert_eq!(applied, rules, "{}", case.slug);
+        assert!(
+            hypervalent
+                .molecule
+                .molecule
+                .atoms()
+                .all(|atom| atom.formal_charge() == 0),
+            "{}",
+            case.slug
+        );
+
+        let separated = standardise(
+            &hypervalent.molecule.molecule,
+            StandardForm::ChargeSeparated,
+        )
+        .expect("valid topology");
+        let charges: Vec<i8> = separated
+            .molecule
+            .molecule
+            .atoms()
+            .map(|atom| atom.formal_charge())
+            .collect();
+        let expected: Vec<i8> = molecule.atoms().map(|atom| atom.formal_charge()).collect();
+        assert_eq!(charges, expected, "{}", case.slug);
+    }
+}
+
+#[test]
+fn strict_valence_accepts_the_catalog() {
+    let strict = PerceptionOptions {
+        strict_valence: true,
+        ..PerceptionOptions::default()
+    };
+    // The same malformed builders that fail the formal charge check.
+    let malformed = [
+        "acridine_aromatic",
+        "acridine_kekule",
+        "guanine_kekule",
+        "histidine_zwitterion_kekule",
+    ];
+    for case in cases::ALL_CASES {
+        let molecule = (case.build)();
+        let report =
+            perceive(&molecule, &PerceptionOptions::default()).expect("perception should succeed");
+        let strict_result = find_resonance_systems_with_options(&molecule, &strict);
+        if malformed.contains(&case.slug) {
+            assert!(!report.valence_errors.is_empty(), "{}", case.slug);
+            assert!(
+                matches!(strict_result, Err(PerceptionError::InvalidValence { .. })),
+                "{}",
+                case.slug
+            );
+        } else {
+            assert!(
+                report.valence_errors.is_empty(),
+                "{}: {:?}",
+                case.slug,
+                report.valence_errors
+            );
+            assert!(strict_result.is_ok(), "{}", case.slug);
+        }
+    }
+}
+
+#[test]
+fn catalog_warnings_match_known_anomalies() {
+    let options = PerceptionOptions::default();
+    for case in cases::ALL_CASES {
+        let molecule = (case.build)();
+        let outcome = find_resonance_systems_with_warnings(&molecule, &options)
+            .expect("perception should succeed");
+
+        let mut systems = outcome.systems;
+        let mut expected = find_resonance_systems_with_options(&molecule, &options)
+            .expect("perception should succeed");
+        systems.sort_by(system_cmp);
+        expected.sort_by(system_cmp);
+        assert_eq!(systems, expected, "{}", case.slug);
+
+        let report = perceive(&molecule, &options).expect("perception should succeed");
+        assert_eq!(report.warnings, outcome.warnings, "{}", case.slug);
+
+        // Adamantane and C60 have more smallest rings than the SSSR can hold;
+        // the malformed builders may over-bond an atom.
+        let ambiguous = [
+            "adamantane_saturated_polycycle",
+            "fullerene_c60_conjugated_sphere",
+        ];
+        let malformed = [
+            "acridine_aromatic",
+            "acridine_kekule",
+            "guanine_kekule",
+            "histidine_zwitterion_kekule",
+        ];
+        for warning in &outcome.warnings {
+            let expected = match warning {
+                PerceptionWarning::AmbiguousSssr { ring_size, .. } => {
+                    *ring_size == 6 && ambiguous.contains(&case.slug)
+                }
+                PerceptionWarning::ClampedLonePairs { .. } => malformed.contains(&case.slug),
+                _ => false,
+            };
+            assert!(expected, "{}: {warning}", case.slug);
+        }
+        if ambiguous.contains(&case.slug) {
+            assert!(!outcome.warnings.is_empty(), "{}", case.slug);
+        }
+    }
+}
+
+#[test]
+fn lenient_kekulization_keeps_the_rest_of_the_molecule() {
+    // Neutral C5H5 drawn aromatic has an odd number of carbons to pair up;
+    // the benzene ring next to it is fine.
+    let mut molecule = Molecule::new();
+    let mut rings = Vec::new();
+    for size in [5, 6] {
+        let carbons: Vec<_> = (0..size)
+            .map(|_| molecule.add_atom(Element::C, 0))
+            .collect();
+        for i in 0..size {
+            molecule
+                .add_bond(carbons[i], carbons[(i + 1) % size], BondOrder::Aromatic)
+                .expect("failed to add ring bond");
+            molecule
+                .set_implicit_hydrogens(carbons[i], 1)
+                .expect("failed to set hydrogens");
+        }
+        rings.push(carbons);
+    }
+
+    let strict = PerceptionOptions::default();
+    assert!(matches!(
+        find_resonance_systems_with_options(&molecule, &strict),
+        Err(PerceptionError::InvalidAromaticInput { .. })
+    ));
+
+    let lenient = PerceptionOptions {
+        lenient_kekulization: true,
+        ..PerceptionOptions::default()
+    };
+    let outcome = find_resonance_systems_with_warnings(&molecule, &lenient)
+        .expect("lenient perception should succeed");
+    assert!(matches!(
+        &outcome.warnings[..],
+        [PerceptionWarning::UnkekulizedComponent { atoms, .. }] if *atoms == rings[0]
+    ));
+
+    let mut system_atoms: Vec<_> = outcome
+        .systems
+        .iter()
+        .map(|system| system.atoms.clone())
+        .collect();
+    system_atoms.sort();
+    assert_eq!(system_atoms, rings);
+
+    let kekule = to_kekule_molecule(&molecule, &lenient).expect("export should succeed");
+    let aromatic_bonds = kekule
+        .molecule
+        .bonds()
+        .filter(|bond| bond.order() == BondOrder::Aromatic)
+        .count();
+    assert_eq!(aromatic_bonds, 5);
+}
+
+#[test]
+fn perception_errors_name_stage_atoms_and_bonds() {
+    let mut duplicate = cases::TestMolecule::new();
+    duplicate.add_atom(0, Element::C, 0);
+    duplicate.add_atom(1, Element::O, 0);
+    duplicate.add_bond(0, 0, 1, BondOrder::Single);
+    duplicate.add_bond(1, 1, 0, BondOrder::Single);
+    let error = find_resonance_systems(&duplicate).expect_err("duplicate bond must fail");
+    assert_eq!(error.stage(), PerceptionStage::GraphConstruction);
+    assert_eq!(error.atoms(), vec![0, 1]);
+    assert_eq!(error.bonds(), vec![0, 1]);
+    assert_eq!(
+        error.to_string(),
+        "graph construction: atoms 1 and 0 are joined by more than one bond (bonds 0, 1)"
+    );
+
+    let mut dangling = cases::TestMolecule::new();
+    dangling.add_atom(0, Element::C, 0);
+    dangling.add_bond(4, 0, 7, BondOrder::Single);
+    let error = find_resonance_systems(&dangling).expect_err("dangling bond must fail");
+    assert!(matches!(
+        error,
+        PerceptionError::InconsistentGraph { bond: 4, atom: 7 }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "graph construction: bond 4 refers to atom 7, which is not part of the molecule"
+    );
+
+    let mut self_loop = cases::TestMolecule::new();
+    self_loop.add_atom(3, Element::C, 0);
+    self_loop.add_bond(5, 3, 3, BondOrder::Single);
+    let error = find_resonance_systems(&self_loop).expect_err("self-loop must fail");
+    assert!(matches!(
+        error,
+        PerceptionError::SelfLoop { bond: 5, atom: 3 }
+    ));
+    assert_eq!(error.stage(), PerceptionStage::GraphConstruction);
+    assert_eq!(
+        error.to_string(),
+        "graph construction: bond 5 joins atom 3 to itself"
+    );
+
+    // Neutral C5H5 drawn aromatic has no Kekulé structure.
+    let mut cyclopentadienyl = Molecule::new();
+    let carbons: Vec<_> = (0..5)
+        .map(|_| cyclopentadienyl.add_atom(Element::C, 0))
+        .collect();
+    for i in 0..5 {
+        cyclopentadienyl
+            .add_bond(carbons[i], carbons[(i + 1) % 5], BondOrder::Aromatic)
+            .expect("failed to add ring bond");
+        cyclopentadienyl
+            .set_implicit_hydrogens(carbons[i], 1)
+            .expect("failed to set hydrogens");
+    }
+    let error = find_resonance_systems(&cyclopentadienyl).expect_err("C5H5 must fail");
+    assert_eq!(error.stage(), PerceptionStage::AromaticityValidation);
+    assert_eq!(error.atoms(), carbons);
+    let message = error.to_string();
+    assert!(
+        message.starts_with(
+            "aromaticity validation: aromatic system has no Kekulé structure at atoms 0, 1, 2, 3, 4"
+        ),
+        "{message}"
+    );
+    assert!(
+        message.contains("to fix, set the formal charge of atom 0 to -1"),
+        "{message}"
+    );
+
+    let mut pentavalent = Molecule::new();
+    let carbon = pentavalent.add_atom(Element::C, 0);
+    for _ in 0..5 {
+        let fluorine = pentavalent.add_atom(Element::F, 0);
+        pentavalent
+            .add_bond(carbon, fluorine, BondOrder::Single)
+            .expect("failed to add C-F bond");
+    }
+    let strict = PerceptionOptions {
+        strict_valence: true,
+        ..PerceptionOptions::default()
+    };
+    let error = find_resonance_systems_with_options(&pentavalent, &strict)
+        .expect_err("pentavalent carbon must fail");
+    assert_eq!(error.stage(), PerceptionStage::ValenceCheck);
+    assert_eq!(error.atoms(), vec![carbon]);
+    assert!(error.bonds().is_empty());
+}
+
+#[test]
+fn oxidation_state_averaging_is_independent_of_the_drawn_contributor() {
+    let options = PerceptionOptions::default();
+    let states = |case: &ResonanceCase, model| -> Vec<f64> {
+        let molecule = (case.build)();
+        assign_oxidation_states(&molecule, &options, model)
+            .expect("perception should succeed")
+            .into_iter()
+            .map(|state| state.value)
+            .collect()
+    };
+
+    for case in cases::ALL_CASES {
+        let localised = states(case, OxidationStateModel::Localised);
+        let averaged = states(case, OxidationStateModel::ResonanceAveraged);
+        assert!(
+            localised.iter().all(|value| value.fract() == 0.0),
+            "{}",
+            case.slug
+        );
+        let shift: f64 = localised.iter().zip(&averaged).map(|(a, b)| b - a).sum();
+        assert!(
+            shift.abs() < 1e-9,
+            "{}: total shifted by {shift}",
+            case.slug
+        );
+
+        // The malformed Kekulé builders over-bond an atom that their aromatic
+        // counterparts do not.
+        let malformed = [
+            "acridine_kekule",
+            "guanine_kekule",
+            "histidine_zwitterion_kekule",
+        ];
+        let Some(stem) = case.slug.strip_suffix("_kekule") else {
+            continue;
+        };
+        if malformed.contains(&case.slug) {
+            continue;
+        }
+        let aromatic = format!("{stem}_aromatic");
+        let Some(other) = cases::ALL_CASES.iter().find(|other| other.slug == aromatic) else {
+            continue;
+        };
+        let other = states(other, OxidationStateModel::ResonanceAveraged);
+        for (a, b) in averaged.iter().zip(&other) {
+            assert!(
+                (a - b).abs() < 1e-9,
+                "{}: {averaged:?} vs {other:?}",
+                case.slug
+            );
+        }
+    }
+}
+
+#[test]
+fn catalog_formulas_round_trip_and_match_across_drawings() {
+    for case in cases::ALL_CASES {
+        let molecule = (case.build)();
+        let formula = molecular_formula(&molecule);
+        let parsed: MolecularFormula = formula
+            .to_string()
+            .parse()
+            .expect("formatted formula should parse");
+        assert_eq!(parsed, formula, "{}", case.slug);
+        assert_eq!(
+            formula.heavy_atom_count() as usize,
+            molecule
+                .atoms()
+                .filter(|atom| atom.element() != Element::H)
+                .count(),
+            "{}",
+            case.slug
+        );
+
+        let Some(stem) = case.slug.strip_suffix("_kekule") else {
+            continue;
+        };
+        let aromatic = format!("{stem}_aromatic");
+        if let Some(other) = cases::ALL_CASES.iter().find(|other| other.slug == aromatic) {
+            assert_eq!(
+                molecular_formula(&(other.build)()),
+                formula,
+                "{}",
+                case.slug
+            );
+        }
+    }
+}